@@ -0,0 +1,46 @@
+use super::{
+    string::{qiniu_ng_char_t, ucstr},
+    utils::qiniu_ng_str_t,
+};
+use qiniu_ng::cdn::TimestampSigner;
+use std::time::{Duration, SystemTime};
+
+/// @brief 为 URL 生成 CDN 时间戳防盗链签名
+/// @param[in] url 需要签名的 URL，必须包含协议和域名
+/// @param[in] key CDN 域名配置的防盗链密钥
+/// @param[in] deadline 过期时间，使用以秒为单位的 UNIX 时间戳表示
+/// @retval qiniu_ng_str_t 返回签名后的 URL，原 URL 中的查询字符串将被保留
+/// @note 如果 `url` 无法被解析，将返回 `NULL`，可以通过 `qiniu_ng_str_is_null()` 判定
+/// @warning 对于获取的 URL，一旦使用完毕，应该调用 `qiniu_ng_str_free()` 释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_cdn_sign_url_with_deadline(
+    url: *const qiniu_ng_char_t,
+    key: *const qiniu_ng_char_t,
+    deadline: u64,
+) -> qiniu_ng_str_t {
+    let signed_url = TimestampSigner::new(unsafe { ucstr::from_ptr(key) }.to_string().unwrap()).sign_with_deadline(
+        unsafe { ucstr::from_ptr(url) }.to_string().unwrap(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(deadline),
+    );
+    unsafe { qiniu_ng_str_t::from_optional_string_unchecked(signed_url.ok()) }
+}
+
+/// @brief 为 URL 生成 CDN 时间戳防盗链签名
+/// @param[in] url 需要签名的 URL，必须包含协议和域名
+/// @param[in] key CDN 域名配置的防盗链密钥
+/// @param[in] lifetime 签名有效期，单位为秒
+/// @retval qiniu_ng_str_t 返回签名后的 URL，原 URL 中的查询字符串将被保留
+/// @note 如果 `url` 无法被解析，将返回 `NULL`，可以通过 `qiniu_ng_str_is_null()` 判定
+/// @warning 对于获取的 URL，一旦使用完毕，应该调用 `qiniu_ng_str_free()` 释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_cdn_sign_url_with_lifetime(
+    url: *const qiniu_ng_char_t,
+    key: *const qiniu_ng_char_t,
+    lifetime: u64,
+) -> qiniu_ng_str_t {
+    let signed_url = TimestampSigner::new(unsafe { ucstr::from_ptr(key) }.to_string().unwrap()).sign_with_lifetime(
+        unsafe { ucstr::from_ptr(url) }.to_string().unwrap(),
+        Duration::from_secs(lifetime),
+    );
+    unsafe { qiniu_ng_str_t::from_optional_string_unchecked(signed_url.ok()) }
+}
@@ -220,6 +220,23 @@ pub extern "C" fn qiniu_ng_config_builder_uplog_host(
     let _ = qiniu_ng_config_builder_t::from(builder);
 }
 
+/// @brief 指定客户端配置中的 Fusion 服务器地址
+/// @param[in] builder 客户端配置生成器实例
+/// @param[in] fusion_host Fusion 服务器地址（仅需要指定主机地址和端口，无需包含协议）
+/// @note 默认将会使用七牛公有云的 Fusion 服务器地址，因此仅在使用私有云时才需要配置
+/// @note 调用该方法时，输入的 `fusion_host` 将被复制并存储，因此 `fusion_host` 的调用完毕后即可释放
+#[no_mangle]
+pub extern "C" fn qiniu_ng_config_builder_fusion_host(
+    builder: qiniu_ng_config_builder_t,
+    fusion_host: *const qiniu_ng_char_t,
+) {
+    let mut builder = Option::<Box<Builder>>::from(builder).unwrap();
+    builder.config_builder = builder
+        .config_builder
+        .fusion_host(unsafe { ucstr::from_ptr(fusion_host) }.to_string().unwrap());
+    let _ = qiniu_ng_config_builder_t::from(builder);
+}
+
 /// @brief 指定客户端配置中的上传凭证有效期
 /// @param[in] builder 客户端配置生成器实例
 /// @param[in] upload_token_lifetime 上传凭证有效期，单位为秒
@@ -236,6 +253,22 @@ pub extern "C" fn qiniu_ng_config_builder_upload_token_lifetime(
     let _ = qiniu_ng_config_builder_t::from(builder);
 }
 
+/// @brief 指定客户端配置中的私有空间下载地址有效期
+/// @param[in] builder 客户端配置生成器实例
+/// @param[in] download_url_lifetime 私有空间下载地址有效期，单位为秒
+/// @note 默认为 1 小时
+#[no_mangle]
+pub extern "C" fn qiniu_ng_config_builder_download_url_lifetime(
+    builder: qiniu_ng_config_builder_t,
+    download_url_lifetime: u64,
+) {
+    let mut builder = Option::<Box<Builder>>::from(builder).unwrap();
+    builder.config_builder = builder
+        .config_builder
+        .download_url_lifetime(Duration::from_secs(download_url_lifetime));
+    let _ = qiniu_ng_config_builder_t::from(builder);
+}
+
 /// @brief 指定客户端配置中的最大批量操作数
 /// @param[in] builder 客户端配置生成器实例
 /// @param[in] batch_max_operation_size 最大批量操作数
@@ -1311,6 +1344,30 @@ pub extern "C" fn qiniu_ng_config_get_uplog_url(config: qiniu_ng_config_t) -> qi
     })
 }
 
+/// @brief 获取客户端配置的 Fusion 服务器地址
+/// @param[in] config 客户端配置实例
+/// @retval qiniu_ng_str_t Fusion 服务器地址
+/// @warning 当 `qiniu_ng_str_t` 使用完毕后，请务必调用 `qiniu_ng_str_free()` 方法释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_config_get_fusion_host(config: qiniu_ng_config_t) -> qiniu_ng_str_t {
+    let config = Option::<Config>::from(config).unwrap();
+    unsafe { qiniu_ng_str_t::from_str_unchecked(config.fusion_host()) }.tap(|_| {
+        let _ = qiniu_ng_config_t::from(config);
+    })
+}
+
+/// @brief 获取客户端配置的 Fusion 服务器 URL
+/// @param[in] config 客户端配置实例
+/// @retval qiniu_ng_str_t Fusion 服务器 URL
+/// @warning 当 `qiniu_ng_str_t` 使用完毕后，请务必调用 `qiniu_ng_str_free()` 方法释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_config_get_fusion_url(config: qiniu_ng_config_t) -> qiniu_ng_str_t {
+    let config = Option::<Config>::from(config).unwrap();
+    unsafe { qiniu_ng_str_t::from_string_unchecked(config.fusion_url()) }.tap(|_| {
+        let _ = qiniu_ng_config_t::from(config);
+    })
+}
+
 /// @brief 获取客户端配置的上传凭证有效期
 /// @param[in] config 客户端配置实例
 /// @retval uint64_t 上传凭证有效期，单位为秒
@@ -1322,6 +1379,17 @@ pub extern "C" fn qiniu_ng_config_get_upload_token_lifetime(config: qiniu_ng_con
     })
 }
 
+/// @brief 获取客户端配置的私有空间下载地址有效期
+/// @param[in] config 客户端配置实例
+/// @retval uint64_t 私有空间下载地址有效期，单位为秒
+#[no_mangle]
+pub extern "C" fn qiniu_ng_config_get_download_url_lifetime(config: qiniu_ng_config_t) -> u64 {
+    let config = Option::<Config>::from(config).unwrap();
+    config.download_url_lifetime().as_secs().tap(|_| {
+        let _ = qiniu_ng_config_t::from(config);
+    })
+}
+
 /// @brief 获取客户端配置的最大批量操作数
 /// @param[in] config 客户端配置实例
 /// @retval size_t 最大批量操作数
@@ -43,6 +43,8 @@ pub enum qiniu_ng_http_method_t {
     qiniu_ng_http_method_post,
     /// PUT 方法
     qiniu_ng_http_method_put,
+    /// DELETE 方法
+    qiniu_ng_http_method_delete,
 }
 
 impl qiniu_ng_http_method_t {
@@ -60,6 +62,9 @@ impl qiniu_ng_http_method_t {
             qiniu_ng_http_method_t::qiniu_ng_http_method_put => unsafe {
                 CStr::from_bytes_with_nul_unchecked(b"PUT\0")
             },
+            qiniu_ng_http_method_t::qiniu_ng_http_method_delete => unsafe {
+                CStr::from_bytes_with_nul_unchecked(b"DELETE\0")
+            },
         }
     }
 }
@@ -71,6 +76,7 @@ impl From<Method> for qiniu_ng_http_method_t {
             Method::HEAD => qiniu_ng_http_method_t::qiniu_ng_http_method_head,
             Method::POST => qiniu_ng_http_method_t::qiniu_ng_http_method_post,
             Method::PUT => qiniu_ng_http_method_t::qiniu_ng_http_method_put,
+            Method::DELETE => qiniu_ng_http_method_t::qiniu_ng_http_method_delete,
         }
     }
 }
@@ -82,6 +88,7 @@ impl From<qiniu_ng_http_method_t> for Method {
             qiniu_ng_http_method_t::qiniu_ng_http_method_head => Method::HEAD,
             qiniu_ng_http_method_t::qiniu_ng_http_method_post => Method::POST,
             qiniu_ng_http_method_t::qiniu_ng_http_method_put => Method::PUT,
+            qiniu_ng_http_method_t::qiniu_ng_http_method_delete => Method::DELETE,
         }
     }
 }
@@ -1,4 +1,5 @@
 mod bucket;
+mod cdn;
 mod client;
 mod config;
 mod etag;
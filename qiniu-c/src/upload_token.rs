@@ -320,6 +320,66 @@ pub extern "C" fn qiniu_ng_upload_policy_builder_set_callback(
     let _ = qiniu_ng_upload_policy_builder_t::from(builder);
 }
 
+/// @brief 上传成功后，触发执行的持久化数据处理指令列表
+/// @details 每个指令是一个 API 规格字符串，多个指令将并行处理
+/// @param[in] builder 客户端配置生成器实例
+/// @param[in] persistent_ops 持久化数据处理指令列表
+/// @param[in] persistent_ops_size 持久化数据处理指令列表长度
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_builder_set_persistent_ops(
+    builder: qiniu_ng_upload_policy_builder_t,
+    persistent_ops: *const *const qiniu_ng_char_t,
+    persistent_ops_size: size_t,
+) {
+    let mut builder = Option::<Box<UploadPolicyBuilder>>::from(builder).unwrap();
+    builder.persistent_ops(&Vec::<String>::with_capacity(persistent_ops_size).tap(|ops| {
+        for i in 0..persistent_ops_size {
+            ops.push(unsafe { ucstr::from_ptr(*persistent_ops.add(i)) }.to_string().unwrap());
+        }
+    }));
+    let _ = qiniu_ng_upload_policy_builder_t::from(builder);
+}
+
+/// @brief 持久化数据处理完成后，七牛云向业务服务器发送处理结果的 URL
+/// @param[in] builder 客户端配置生成器实例
+/// @param[in] persistent_notify_url 接收处理结果的 URL
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_builder_set_persistent_notify_url(
+    builder: qiniu_ng_upload_policy_builder_t,
+    persistent_notify_url: *const qiniu_ng_char_t,
+) {
+    let mut builder = Option::<Box<UploadPolicyBuilder>>::from(builder).unwrap();
+    builder.persistent_notify_url(unsafe { ucstr::from_ptr(persistent_notify_url) }.to_string().unwrap());
+    let _ = qiniu_ng_upload_policy_builder_t::from(builder);
+}
+
+/// @brief 指定持久化数据处理使用的私有队列名称
+/// @param[in] builder 客户端配置生成器实例
+/// @param[in] persistent_pipeline 私有队列名称
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_builder_set_persistent_pipeline(
+    builder: qiniu_ng_upload_policy_builder_t,
+    persistent_pipeline: *const qiniu_ng_char_t,
+) {
+    let mut builder = Option::<Box<UploadPolicyBuilder>>::from(builder).unwrap();
+    builder.persistent_pipeline(unsafe { ucstr::from_ptr(persistent_pipeline) }.to_string().unwrap());
+    let _ = qiniu_ng_upload_policy_builder_t::from(builder);
+}
+
+/// @brief 指定上传用户的唯一标识
+/// @details 该值将出现在回调请求中，可以用于业务服务器识别上传用户
+/// @param[in] builder 客户端配置生成器实例
+/// @param[in] end_user 上传用户的唯一标识
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_builder_set_end_user(
+    builder: qiniu_ng_upload_policy_builder_t,
+    end_user: *const qiniu_ng_char_t,
+) {
+    let mut builder = Option::<Box<UploadPolicyBuilder>>::from(builder).unwrap();
+    builder.end_user(unsafe { ucstr::from_ptr(end_user) }.to_string().unwrap());
+    let _ = qiniu_ng_upload_policy_builder_t::from(builder);
+}
+
 /// @brief 设置自定义对象名称
 /// @details
 ///     支持[魔法变量](https://developer.qiniu.com/kodo/manual/1235/vars#magicvar)和[自定义变量](https://developer.qiniu.com/kodo/manual/1235/vars#xvar)
@@ -722,6 +782,73 @@ pub extern "C" fn qiniu_ng_upload_policy_get_callback_body_type(
     })
 }
 
+/// @brief 上传成功后，触发执行的持久化数据处理指令列表
+/// @param[in] upload_policy 上传策略实例
+/// @retval qiniu_ng_str_list_t 返回持久化数据处理指令列表
+/// @note 这里返回的 `qiniu_ng_str_list_t` 有可能封装的是 `NULL`，请调用 `qiniu_ng_str_list_is_null()` 进行判断
+/// @warning 当 `qiniu_ng_str_list_t` 使用完毕后，请务必调用 `qiniu_ng_str_list_free()` 方法释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_get_persistent_ops(
+    upload_policy: qiniu_ng_upload_policy_t,
+) -> qiniu_ng_str_list_t {
+    let upload_policy = Option::<Box<UploadPolicy>>::from(upload_policy).unwrap();
+    unsafe {
+        qiniu_ng_str_list_t::from_optional_str_slice_unchecked(
+            upload_policy
+                .persistent_ops()
+                .map(|ops| ops.collect::<Box<[&str]>>())
+                .as_ref()
+                .map(|ops| ops.as_ref()),
+        )
+    }
+    .tap(|_| {
+        let _ = qiniu_ng_upload_policy_t::from(upload_policy);
+    })
+}
+
+/// @brief 持久化数据处理完成后，七牛云向业务服务器发送处理结果的 URL
+/// @param[in] upload_policy 上传策略实例
+/// @retval qiniu_ng_str_t 返回接收处理结果的 URL
+/// @note 这里返回的 `qiniu_ng_str_t` 有可能封装的是 `NULL`，请调用 `qiniu_ng_str_is_null()` 进行判断
+/// @warning 当 `qiniu_ng_str_t` 使用完毕后，请务必调用 `qiniu_ng_str_free()` 方法释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_get_persistent_notify_url(
+    upload_policy: qiniu_ng_upload_policy_t,
+) -> qiniu_ng_str_t {
+    let upload_policy = Option::<Box<UploadPolicy>>::from(upload_policy).unwrap();
+    unsafe { qiniu_ng_str_t::from_optional_str_unchecked(upload_policy.persistent_notify_url()) }.tap(|_| {
+        let _ = qiniu_ng_upload_policy_t::from(upload_policy);
+    })
+}
+
+/// @brief 持久化数据处理使用的私有队列名称
+/// @param[in] upload_policy 上传策略实例
+/// @retval qiniu_ng_str_t 返回私有队列名称
+/// @note 这里返回的 `qiniu_ng_str_t` 有可能封装的是 `NULL`，请调用 `qiniu_ng_str_is_null()` 进行判断
+/// @warning 当 `qiniu_ng_str_t` 使用完毕后，请务必调用 `qiniu_ng_str_free()` 方法释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_get_persistent_pipeline(
+    upload_policy: qiniu_ng_upload_policy_t,
+) -> qiniu_ng_str_t {
+    let upload_policy = Option::<Box<UploadPolicy>>::from(upload_policy).unwrap();
+    unsafe { qiniu_ng_str_t::from_optional_str_unchecked(upload_policy.persistent_pipeline()) }.tap(|_| {
+        let _ = qiniu_ng_upload_policy_t::from(upload_policy);
+    })
+}
+
+/// @brief 上传用户的唯一标识
+/// @param[in] upload_policy 上传策略实例
+/// @retval qiniu_ng_str_t 返回上传用户的唯一标识
+/// @note 这里返回的 `qiniu_ng_str_t` 有可能封装的是 `NULL`，请调用 `qiniu_ng_str_is_null()` 进行判断
+/// @warning 当 `qiniu_ng_str_t` 使用完毕后，请务必调用 `qiniu_ng_str_free()` 方法释放内存
+#[no_mangle]
+pub extern "C" fn qiniu_ng_upload_policy_get_end_user(upload_policy: qiniu_ng_upload_policy_t) -> qiniu_ng_str_t {
+    let upload_policy = Option::<Box<UploadPolicy>>::from(upload_policy).unwrap();
+    unsafe { qiniu_ng_str_t::from_optional_str_unchecked(upload_policy.end_user()) }.tap(|_| {
+        let _ = qiniu_ng_upload_policy_t::from(upload_policy);
+    })
+}
+
 /// @brief 获取上传策略中的自定义对象名称
 /// @param[in] upload_policy 上传策略实例
 /// @retval qiniu_ng_str_t 返回自定义对象名称
@@ -24,6 +24,7 @@ int main(void) {
     RUN_TEST(test_qiniu_ng_etag_from_buffer);
     RUN_TEST(test_qiniu_ng_etag_from_large_buffer);
     RUN_TEST(test_qiniu_ng_etag_from_unexisted_file_path);
+    RUN_TEST(test_qiniu_ng_cdn_sign_url);
     RUN_TEST(test_qiniu_ng_config_new_default);
     RUN_TEST(test_qiniu_ng_config_new);
     RUN_TEST(test_qiniu_ng_config_new2);
@@ -33,6 +33,7 @@ void test_qiniu_ng_etag_from_file_path(void);
 void test_qiniu_ng_etag_from_buffer(void);
 void test_qiniu_ng_etag_from_unexisted_file_path(void);
 void test_qiniu_ng_etag_from_large_buffer(void);
+void test_qiniu_ng_cdn_sign_url(void);
 void test_qiniu_ng_config_new_default(void);
 void test_qiniu_ng_config_new(void);
 void test_qiniu_ng_config_new2(void);
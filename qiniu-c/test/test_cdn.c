@@ -0,0 +1,26 @@
+#include "unity.h"
+#include "libqiniu_ng.h"
+#include "test.h"
+
+void test_qiniu_ng_cdn_sign_url(void) {
+    qiniu_ng_str_t signed_url = qiniu_ng_cdn_sign_url_with_deadline(
+        QINIU_NG_CHARS("http://www.qiniu.com/DIR1/dir2/vodfile.mp4?v=1"),
+        QINIU_NG_CHARS("ki0jk3da7xp4ohmsyc7yigdmpbokhnqjnyqyi9nw"),
+        0x55bb9b80);
+    TEST_ASSERT_FALSE_MESSAGE(
+        qiniu_ng_str_is_null(signed_url),
+        "qiniu_ng_str_is_null(signed_url) != false");
+    TEST_ASSERT_EQUAL_STRING_MESSAGE(
+        qiniu_ng_str_get_ptr(signed_url),
+        QINIU_NG_CHARS("http://www.qiniu.com/DIR1/dir2/vodfile.mp4?v=1&sign=1cfed5e1b9743a989395008d26e7d76f&t=55bb9b80"),
+        "qiniu_ng_str_get_ptr(signed_url) != expected");
+    qiniu_ng_str_free(&signed_url);
+
+    signed_url = qiniu_ng_cdn_sign_url_with_lifetime(
+        QINIU_NG_CHARS("/DIR1/dir2/vodfile.mp4"),
+        QINIU_NG_CHARS("ki0jk3da7xp4ohmsyc7yigdmpbokhnqjnyqyi9nw"),
+        3600);
+    TEST_ASSERT_TRUE_MESSAGE(
+        qiniu_ng_str_is_null(signed_url),
+        "qiniu_ng_str_is_null(signed_url) != true");
+}
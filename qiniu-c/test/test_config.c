@@ -25,6 +25,9 @@ void test_qiniu_ng_config_new(void) {
     TEST_ASSERT_EQUAL_INT_MESSAGE(
         qiniu_ng_config_get_batch_max_operation_size(config), 1000,
         "qiniu_ng_config_get_batch_max_operation_size() returns unexpected value");
+    TEST_ASSERT_EQUAL_INT_MESSAGE(
+        qiniu_ng_config_get_download_url_lifetime(config), 60 * 60,
+        "qiniu_ng_config_get_download_url_lifetime() returns unexpected value");
     TEST_ASSERT_EQUAL_INT_MESSAGE(
         qiniu_ng_config_get_upload_threshold(config), 1 << 22,
         "qiniu_ng_config_get_upload_threshold() returns unexpected value");
@@ -91,6 +94,7 @@ void test_qiniu_ng_config_new2(void) {
     qiniu_ng_config_builder_set_appended_user_agent(builder, QINIU_NG_CHARS("test-user-agent"));
     qiniu_ng_config_builder_use_https(builder, false);
     qiniu_ng_config_builder_batch_max_operation_size(builder, 10000);
+    qiniu_ng_config_builder_download_url_lifetime(builder, 60 * 60 * 2);
     qiniu_ng_config_builder_upload_threshold(builder, 1 << 23);
     qiniu_ng_config_builder_uc_host(builder, QINIU_NG_CHARS("uc.qiniu.com"));
     qiniu_ng_config_builder_disable_uplog(builder);
@@ -124,6 +128,9 @@ void test_qiniu_ng_config_new2(void) {
     TEST_ASSERT_EQUAL_INT_MESSAGE(
         qiniu_ng_config_get_batch_max_operation_size(config), 10000,
         "qiniu_ng_config_get_batch_max_operation_size() returns unexpected value");
+    TEST_ASSERT_EQUAL_INT_MESSAGE(
+        qiniu_ng_config_get_download_url_lifetime(config), 60 * 60 * 2,
+        "qiniu_ng_config_get_download_url_lifetime() returns unexpected value");
     TEST_ASSERT_EQUAL_INT_MESSAGE(
         qiniu_ng_config_get_upload_threshold(config), 1 << 23,
         "qiniu_ng_config_get_upload_threshold() returns unexpected value");
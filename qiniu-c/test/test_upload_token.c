@@ -11,6 +11,10 @@ void test_qiniu_ng_make_upload_token(void) {
         QINIU_NG_CHARS("https://apin1.qiniu.com/callback"),
         QINIU_NG_CHARS("https://apin2.qiniu.com/callback")
     };
+    const qiniu_ng_char_t *PERSISTENT_OPS[2] = {
+        QINIU_NG_CHARS("avthumb/mp4"),
+        QINIU_NG_CHARS("vframe/jpg/offset/7")
+    };
     uint64_t deadline = (unsigned long long) time(NULL) + 3600;
 
     qiniu_ng_upload_policy_builder_t builder = qiniu_ng_upload_policy_builder_new_for_bucket(QINIU_NG_CHARS("test-bucket"), config);
@@ -19,6 +23,8 @@ void test_qiniu_ng_make_upload_token(void) {
         qiniu_ng_upload_policy_builder_set_overwritable(builder),
         "qiniu_ng_upload_policy_builder_set_overwritable(builder) should return false");
     qiniu_ng_upload_policy_builder_set_callback(builder, (const qiniu_ng_char_t *const *) &CALLBACK_URLS[0], 2, NULL, QINIU_NG_CHARS("key=$(key)"), NULL);
+    qiniu_ng_upload_policy_builder_set_persistent_ops(builder, (const qiniu_ng_char_t *const *) &PERSISTENT_OPS[0], 2);
+    qiniu_ng_upload_policy_builder_set_persistent_pipeline(builder, QINIU_NG_CHARS("test-pipeline"));
     qiniu_ng_upload_policy_t upload_policy = qiniu_ng_upload_policy_build(builder);
     TEST_ASSERT_FALSE_MESSAGE(
         qiniu_ng_upload_policy_builder_is_freed(builder),
@@ -85,6 +91,27 @@ void test_qiniu_ng_make_upload_token(void) {
         "qiniu_ng_str_is_null(callback_body_type) != true");
     qiniu_ng_str_free(&callback_body_type);
 
+    qiniu_ng_str_list_t persistent_ops = qiniu_ng_upload_policy_get_persistent_ops(upload_policy);
+    TEST_ASSERT_EQUAL_INT_MESSAGE(
+        qiniu_ng_str_list_len(persistent_ops), 2,
+        "qiniu_ng_str_list_len(persistent_ops) != 2");
+    TEST_ASSERT_EQUAL_STRING_MESSAGE(
+        qiniu_ng_str_list_get(persistent_ops, 1), QINIU_NG_CHARS("vframe/jpg/offset/7"),
+        "qiniu_ng_str_list_get(persistent_ops, 1) != \"vframe/jpg/offset/7\"");
+    qiniu_ng_str_list_free(&persistent_ops);
+
+    qiniu_ng_str_t persistent_pipeline = qiniu_ng_upload_policy_get_persistent_pipeline(upload_policy);
+    TEST_ASSERT_EQUAL_STRING_MESSAGE(
+        qiniu_ng_str_get_ptr(persistent_pipeline), QINIU_NG_CHARS("test-pipeline"),
+        "qiniu_ng_str_get_ptr(persistent_pipeline) != \"test-pipeline\"");
+    qiniu_ng_str_free(&persistent_pipeline);
+
+    qiniu_ng_str_t persistent_notify_url = qiniu_ng_upload_policy_get_persistent_notify_url(upload_policy);
+    TEST_ASSERT_TRUE_MESSAGE(
+        qiniu_ng_str_is_null(persistent_notify_url),
+        "qiniu_ng_str_is_null(persistent_notify_url) != true");
+    qiniu_ng_str_free(&persistent_notify_url);
+
     qiniu_ng_upload_token_t upload_token = qiniu_ng_upload_token_new_from_policy(upload_policy, GETENV(QINIU_NG_CHARS("access_key")), GETENV(QINIU_NG_CHARS("secret_key")));
     qiniu_ng_upload_policy_free(&upload_policy);
 
@@ -6,6 +6,7 @@ pub enum Method {
     HEAD,
     POST,
     PUT,
+    DELETE,
 }
 
 impl Method {
@@ -15,6 +16,7 @@ impl Method {
             Method::HEAD => "HEAD",
             Method::POST => "POST",
             Method::PUT => "PUT",
+            Method::DELETE => "DELETE",
         }
     }
 
@@ -24,6 +26,7 @@ impl Method {
             Method::HEAD => b"HEAD",
             Method::POST => b"POST",
             Method::PUT => b"PUT",
+            Method::DELETE => b"DELETE",
         }
     }
 }
@@ -37,6 +40,7 @@ impl TryFrom<&str> for Method {
             "HEAD" => Ok(Method::HEAD),
             "POST" => Ok(Method::POST),
             "PUT" => Ok(Method::PUT),
+            "DELETE" => Ok(Method::DELETE),
             _ => Err(InvalidMethod),
         }
     }
@@ -51,6 +55,7 @@ impl TryFrom<&[u8]> for Method {
             b"HEAD" => Ok(Method::HEAD),
             b"POST" => Ok(Method::POST),
             b"PUT" => Ok(Method::PUT),
+            b"DELETE" => Ok(Method::DELETE),
             _ => Err(InvalidMethod),
         }
     }
@@ -65,6 +70,7 @@ impl FromStr for Method {
             "HEAD" => Ok(Method::HEAD),
             "POST" => Ok(Method::POST),
             "PUT" => Ok(Method::PUT),
+            "DELETE" => Ok(Method::DELETE),
             _ => Err(InvalidMethod),
         }
     }
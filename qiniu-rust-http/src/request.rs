@@ -1,6 +1,15 @@
 use super::{HeaderName, HeaderValue, Headers, Method};
 use getset::{CopyGetters, Getters, MutGetters};
-use std::{borrow::Cow, ffi::c_void, fmt, net::SocketAddr, ptr::null_mut, time::Duration};
+use std::{
+    borrow::Cow,
+    cmp::min,
+    ffi::c_void,
+    fmt,
+    io::{Cursor, Read, Result as IOResult, Seek, SeekFrom},
+    net::SocketAddr,
+    ptr::null_mut,
+    time::Duration,
+};
 
 pub type URL<'b> = Cow<'b, str>;
 pub type Body<'b> = Cow<'b, [u8]>;
@@ -11,6 +20,12 @@ pub enum ProgressCallback<'b> {
     Fn(fn(u64, u64)),
 }
 
+#[derive(Copy, Clone)]
+pub enum ReadingBodyCallback<'b> {
+    Closure(&'b dyn Fn(usize)),
+    Fn(fn(usize)),
+}
+
 #[derive(Getters, CopyGetters, MutGetters)]
 pub struct Request<'b> {
     #[get_mut = "pub"]
@@ -51,6 +66,10 @@ pub struct Request<'b> {
     #[get_mut = "pub"]
     on_downloading_progress: Option<ProgressCallback<'b>>,
 
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    on_reading_body: Option<ReadingBodyCallback<'b>>,
+
     #[get_copy = "pub"]
     #[get_mut = "pub"]
     connect_timeout: Duration,
@@ -84,6 +103,55 @@ impl<'b> Request<'b> {
     pub fn user_agent(&self) -> Option<&str> {
         self.user_agent.as_ref().map(|user_agent| user_agent.as_ref())
     }
+
+    /// 获取请求体读取器
+    ///
+    /// 以流的形式读取请求体，每次读取前都将调用 `on_reading_body` 回调，回调可以通过阻塞来控制请求体的发送速度。
+    /// 因此 HTTP 调用器应当通过该读取器逐块读取并发送请求体，而不是一次性发送 `body()` 的全部内容。
+    /// 每次发送请求（包括重试）时都应该获取新的读取器
+    pub fn body_reader(&self) -> Option<BodyReader<'_>> {
+        self.body.as_ref().map(|body| BodyReader {
+            body: Cursor::new(body.as_ref()),
+            on_reading_body: self.on_reading_body,
+        })
+    }
+}
+
+/// 请求体读取器
+pub struct BodyReader<'r> {
+    body: Cursor<&'r [u8]>,
+    on_reading_body: Option<ReadingBodyCallback<'r>>,
+}
+
+impl BodyReader<'_> {
+    /// 获取请求体的总长度
+    pub fn len(&self) -> usize {
+        self.body.get_ref().len()
+    }
+
+    /// 请求体是否为空
+    pub fn is_empty(&self) -> bool {
+        self.body.get_ref().is_empty()
+    }
+}
+
+impl Read for BodyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
+        let rest = self.len().saturating_sub(self.body.position() as usize);
+        let size = min(buf.len(), rest);
+        if size > 0 {
+            if let Some(on_reading_body) = self.on_reading_body {
+                on_reading_body.call(size);
+            }
+        }
+        self.body.read(&mut buf[..size])
+    }
+}
+
+impl Seek for BodyReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> IOResult<u64> {
+        self.body.seek(pos)
+    }
 }
 
 pub struct RequestBuilder<'r> {
@@ -151,6 +219,11 @@ impl<'r> RequestBuilder<'r> {
         self
     }
 
+    pub fn on_reading_body(mut self, callback: impl Into<ReadingBodyCallback<'r>>) -> RequestBuilder<'r> {
+        self.request.on_reading_body = Some(callback.into());
+        self
+    }
+
     pub fn connect_timeout(mut self, timeout: Duration) -> RequestBuilder<'r> {
         self.request.connect_timeout = timeout;
         self
@@ -198,6 +271,7 @@ impl Default for Request<'_> {
             resolved_socket_addrs: Cow::Borrowed(&[]),
             on_uploading_progress: None,
             on_downloading_progress: None,
+            on_reading_body: None,
             custom_data: null_mut(),
             connect_timeout: Duration::from_secs(5),
             request_timeout: Duration::from_secs(300),
@@ -235,6 +309,14 @@ impl fmt::Debug for Request<'_> {
                     &"Not Installed"
                 },
             )
+            .field(
+                "on_reading_body",
+                if self.on_reading_body.is_some() {
+                    &"Installed"
+                } else {
+                    &"Not Installed"
+                },
+            )
             .field("connect_timeout", &self.connect_timeout)
             .field("request_timeout", &self.request_timeout)
             .field("tcp_keepalive_idle_timeout", &self.tcp_keepalive_idle_timeout)
@@ -265,3 +347,58 @@ impl From<fn(u64, u64)> for ProgressCallback<'_> {
         ProgressCallback::Fn(f)
     }
 }
+
+impl ReadingBodyCallback<'_> {
+    pub fn call(&self, size: usize) {
+        match self {
+            ReadingBodyCallback::Closure(closure) => (closure)(size),
+            ReadingBodyCallback::Fn(f) => (f)(size),
+        }
+    }
+}
+
+impl<'a> From<&'a dyn Fn(usize)> for ReadingBodyCallback<'a> {
+    fn from(f: &'a dyn Fn(usize)) -> Self {
+        ReadingBodyCallback::Closure(f)
+    }
+}
+
+impl From<fn(usize)> for ReadingBodyCallback<'_> {
+    fn from(f: fn(usize)) -> Self {
+        ReadingBodyCallback::Fn(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{boxed::Box, cell::RefCell, error::Error, result::Result};
+
+    #[test]
+    fn test_body_reader() -> Result<(), Box<dyn Error>> {
+        let read_sizes = RefCell::new(Vec::new());
+        let on_reading_body = |size: usize| read_sizes.borrow_mut().push(size);
+        let request = RequestBuilder::default()
+            .body(&b"0123456789"[..])
+            .on_reading_body(&on_reading_body as &dyn Fn(usize))
+            .build();
+        let mut reader = request.body_reader().unwrap();
+        assert_eq!(reader.len(), 10);
+
+        let mut buf = [0u8; 4];
+        assert_eq!(reader.read(&mut buf)?, 4);
+        assert_eq!(&buf, b"0123");
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest)?;
+        assert_eq!(rest, b"456789");
+        assert_eq!(*read_sizes.borrow(), vec![4, 6]);
+
+        // 重新读取时同样需要调用回调
+        reader.seek(SeekFrom::Start(0))?;
+        let mut all = Vec::new();
+        reader.read_to_end(&mut all)?;
+        assert_eq!(all, b"0123456789");
+        assert_eq!(*read_sizes.borrow(), vec![4, 6, 10]);
+        Ok(())
+    }
+}
@@ -25,6 +25,17 @@ pub fn fake_req_id() -> String {
     base64::encode_config(&buf, base64::URL_SAFE)
 }
 
+pub fn json_response<T: Serialize>(status_code: StatusCode, body: T) -> Response {
+    let mut headers = Headers::with_capacity(2);
+    headers.insert("Content-Type".into(), "application/json".into());
+    headers.insert("X-Reqid".into(), fake_req_id().into());
+    ResponseBuilder::default()
+        .status_code(status_code)
+        .headers(headers)
+        .bytes_as_body(serde_json::to_string(&body).unwrap())
+        .build()
+}
+
 pub struct JSONCallMock<T: Serialize + Send + Sync> {
     status_code: StatusCode,
     response_headers: Headers<'static>,
@@ -6,15 +6,15 @@ use derive_builder::Builder;
 use lazy_static::lazy_static;
 use object_pool::Pool;
 use qiniu_http::{
-    Error, ErrorKind, HTTPCaller, HTTPCallerErrorKind, Headers, Method, ProgressCallback, Request, Response,
-    ResponseBuilder, Result, StatusCode,
+    Error, ErrorKind, HTTPCaller, HTTPCallerErrorKind, Headers, Method, ProgressCallback, Request, RequestBodyReader,
+    Response, ResponseBuilder, Result, StatusCode,
 };
 use std::{
     convert::TryInto,
     default::Default,
     env,
     fs::File,
-    io::{Cursor, Read, Seek, SeekFrom, Write},
+    io::{Read, Seek, SeekFrom, Write},
     mem::{size_of, transmute, transmute_copy},
     net::IpAddr,
     path::{Path, PathBuf},
@@ -130,9 +130,9 @@ impl CurlClient {
         context.upload_progress = request.on_uploading_progress();
         context.download_progress = request.on_downloading_progress();
 
-        if let Some(request_body) = request.body().as_ref().map(|body| body.as_ref()) {
+        if let Some(request_body) = request.body_reader() {
             if !request_body.is_empty() {
-                context.request_body = Some(Cursor::new(request_body));
+                context.request_body = Some(request_body);
             }
         }
 
@@ -150,6 +150,7 @@ impl CurlClient {
             Method::HEAD => easy.nobody(true),
             Method::POST => easy.post(true),
             Method::PUT => easy.upload(true),
+            Method::DELETE => easy.custom_request("DELETE"),
         };
         Self::handle_if_err(result, request)
     }
@@ -327,7 +328,7 @@ enum ProgressStatus {
 }
 
 struct Context<'r> {
-    request_body: Option<Cursor<&'r [u8]>>,
+    request_body: Option<RequestBodyReader<'r>>,
     response_body: Option<ResponseBody>,
     response_headers: Option<Headers<'static>>,
     buffer_size: usize,
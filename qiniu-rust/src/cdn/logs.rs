@@ -0,0 +1,45 @@
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+#[derive(Serialize)]
+pub(super) struct LogListRequest<'a> {
+    pub(super) day: &'a str,
+    pub(super) domains: &'a str,
+}
+
+#[derive(Deserialize)]
+pub(super) struct LogListResult {
+    #[serde(default)]
+    pub(super) data: HashMap<String, Option<Vec<LogFile>>>,
+}
+
+/// CDN 日志文件
+///
+/// 通过 `CDNManager::log_files` 获取
+#[derive(Deserialize, Getters, CopyGetters, Debug, Clone)]
+pub struct LogFile {
+    /// 日志文件名称
+    #[get = "pub"]
+    name: String,
+
+    /// 日志文件尺寸，单位为字节
+    #[get_copy = "pub"]
+    size: u64,
+
+    mtime: u64,
+
+    /// 日志文件下载地址
+    #[get = "pub"]
+    url: String,
+}
+
+impl LogFile {
+    /// 日志文件最后修改时间
+    pub fn modified(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.mtime)
+    }
+}
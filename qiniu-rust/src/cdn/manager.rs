@@ -0,0 +1,382 @@
+use super::{
+    logs::{LogFile, LogListRequest, LogListResult},
+    refresh::{PrefetchRequest, PrefetchResult, RefreshQuota, RefreshRequest, RefreshResult},
+    statistics::{Granularity, TrafficRequest, TrafficStatistics},
+};
+use crate::{
+    config::Config,
+    credential::Credential,
+    http::{Client, Error as HTTPError, TokenVersion},
+};
+use assert_impl::assert_impl;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{borrow::Borrow, collections::HashMap, result::Result};
+use thiserror::Error;
+
+/// CDN 管理器
+///
+/// 通过 `Client::cdn` 获取，封装 CDN 相关管理功能
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config, cdn::Granularity};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let result = client.cdn().refresh(&["http://www.example.com/1.html"], &["http://www.example.com/images/"])?;
+/// println!("request_id: {}, invalid_urls: {:?}", result.request_id(), result.invalid_urls());
+/// let flux = client.cdn().flux(&["www.example.com"], "2020-01-01", "2020-01-02", Granularity::Day)?;
+/// println!("time: {:?}, flux: {:?}", flux.time(), flux.domain("www.example.com"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CDNManager {
+    http_client: Client,
+    credential: Credential,
+    fusion_url: Box<str>,
+}
+
+#[derive(Deserialize)]
+struct FusionResponse<T> {
+    code: i64,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(flatten)]
+    body: T,
+}
+
+impl CDNManager {
+    pub(crate) fn new(credential: Credential, config: Config) -> CDNManager {
+        CDNManager {
+            fusion_url: config.fusion_url().into(),
+            credential,
+            http_client: Client::new(config),
+        }
+    }
+
+    /// 刷新 URL 和目录的 CDN 缓存
+    ///
+    /// 目录必须以 `/` 结尾，每次提交的 URL 和目录数量均受到每日刷新额度的限制
+    pub fn refresh(&self, urls: &[&str], dirs: &[&str]) -> CDNResult<RefreshResult> {
+        self.post("/v2/tune/refresh", &RefreshRequest { urls, dirs })
+    }
+
+    /// 刷新 URL 的 CDN 缓存
+    pub fn refresh_urls(&self, urls: &[&str]) -> CDNResult<RefreshResult> {
+        self.refresh(urls, &[])
+    }
+
+    /// 刷新目录的 CDN 缓存
+    ///
+    /// 目录必须以 `/` 结尾
+    pub fn refresh_dirs(&self, dirs: &[&str]) -> CDNResult<RefreshResult> {
+        self.refresh(&[], dirs)
+    }
+
+    /// 查询当日的缓存刷新额度
+    ///
+    /// 通过提交空的刷新请求获取，不会消耗刷新额度
+    pub fn refresh_quota(&self) -> CDNResult<RefreshQuota> {
+        Ok(self.refresh(&[], &[])?.quota())
+    }
+
+    /// 预取 URL 到 CDN 节点
+    pub fn prefetch(&self, urls: &[&str]) -> CDNResult<PrefetchResult> {
+        self.post("/v2/tune/prefetch", &PrefetchRequest { urls })
+    }
+
+    /// 查询域名在指定日期范围内的流量
+    ///
+    /// `start_date` 和 `end_date` 的格式均为 `YYYY-MM-DD`，流量的单位为字节
+    pub fn flux(
+        &self,
+        domains: &[&str],
+        start_date: impl AsRef<str>,
+        end_date: impl AsRef<str>,
+        granularity: Granularity,
+    ) -> CDNResult<TrafficStatistics> {
+        self.traffic(
+            "/v2/tune/flux",
+            domains,
+            start_date.as_ref(),
+            end_date.as_ref(),
+            granularity,
+        )
+    }
+
+    /// 查询域名在指定日期范围内的带宽
+    ///
+    /// `start_date` 和 `end_date` 的格式均为 `YYYY-MM-DD`，带宽的单位为 bps
+    pub fn bandwidth(
+        &self,
+        domains: &[&str],
+        start_date: impl AsRef<str>,
+        end_date: impl AsRef<str>,
+        granularity: Granularity,
+    ) -> CDNResult<TrafficStatistics> {
+        self.traffic(
+            "/v2/tune/bandwidth",
+            domains,
+            start_date.as_ref(),
+            end_date.as_ref(),
+            granularity,
+        )
+    }
+
+    /// 列出域名在指定日期的日志文件
+    ///
+    /// `day` 的格式为 `YYYY-MM-DD`，返回域名与其日志文件列表的映射
+    pub fn log_files(&self, domains: &[&str], day: impl AsRef<str>) -> CDNResult<HashMap<String, Vec<LogFile>>> {
+        let result: LogListResult = self.post(
+            "/v2/tune/log/list",
+            &LogListRequest {
+                day: day.as_ref(),
+                domains: &domains.join(";"),
+            },
+        )?;
+        Ok(result
+            .data
+            .into_iter()
+            .map(|(domain, files)| (domain, files.unwrap_or_default()))
+            .collect())
+    }
+
+    fn traffic(
+        &self,
+        path: &str,
+        domains: &[&str],
+        start_date: &str,
+        end_date: &str,
+        granularity: Granularity,
+    ) -> CDNResult<TrafficStatistics> {
+        self.post(
+            path,
+            &TrafficRequest {
+                start_date,
+                end_date,
+                granularity,
+                domains: &domains.join(";"),
+            },
+        )
+    }
+
+    fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> CDNResult<T> {
+        let response: FusionResponse<T> = self
+            .http_client
+            .post(path, &[&self.fusion_url])
+            .token(TokenVersion::V1, self.credential.borrow().into())
+            .accept_json()
+            .json_body(body)
+            .unwrap()
+            .send()?
+            .parse_json()?;
+        if response.code != 200 {
+            return Err(CDNError::ResponseError {
+                code: response.code,
+                message: response.error.unwrap_or_default(),
+            });
+        }
+        Ok(response.body)
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+/// CDN 管理错误
+#[derive(Error, Debug)]
+pub enum CDNError {
+    /// 七牛 API 调用错误
+    #[error("Qiniu API call error: {0}")]
+    HTTPError(#[from] HTTPError),
+    /// CDN 服务返回错误
+    #[error("CDN response error: code = {code}, message = {message}")]
+    ResponseError {
+        /// 错误代码
+        code: i64,
+        /// 错误信息
+        message: String,
+    },
+}
+
+/// CDN 管理结果
+pub type CDNResult<T> = Result<T, CDNError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ConfigBuilder,
+        http::{DomainsManagerBuilder, Method},
+    };
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock};
+    use serde_json::{json, Value};
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_cdn_manager_refresh_and_prefetch() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                r"^https://fusion\.qiniuapi\.com/v2/tune/refresh$",
+                |request, _| {
+                    assert!(request
+                        .headers()
+                        .get(&"Authorization".into())
+                        .unwrap()
+                        .starts_with("QBox abcdefghklmnopq:"));
+                    let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                    assert_eq!(
+                        body,
+                        json!({"urls": ["http://www.qiniu.com/1.html", "http://www.qiniu.com/2.html"], "dirs": []})
+                    );
+                    Ok(json_response(
+                        200,
+                        json!({
+                            "code": 200,
+                            "error": "success",
+                            "requestId": "5e12d2f5d8b9d1a9b3c6b4c0",
+                            "invalidUrls": ["http://www.qiniu.com/2.html"],
+                            "invalidDirs": null,
+                            "urlQuotaDay": 10000,
+                            "urlSurplusDay": 9998,
+                            "dirQuotaDay": 100,
+                            "dirSurplusDay": 100,
+                        }),
+                    ))
+                },
+            )
+            .install(
+                Method::POST,
+                r"^https://fusion\.qiniuapi\.com/v2/tune/prefetch$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!({
+                            "code": 400032,
+                            "error": "invalid host",
+                            "requestId": "",
+                            "invalidUrls": ["http://www.example.com/1.html"],
+                        }),
+                    ))
+                },
+            ),
+        );
+        let cdn = get_cdn_manager(mock.clone());
+        let result = cdn.refresh_urls(&["http://www.qiniu.com/1.html", "http://www.qiniu.com/2.html"])?;
+        assert_eq!(result.request_id(), "5e12d2f5d8b9d1a9b3c6b4c0");
+        assert_eq!(result.invalid_urls(), &["http://www.qiniu.com/2.html".to_owned()]);
+        assert!(result.invalid_dirs().is_empty());
+        assert_eq!(result.quota().url_surplus_day(), 9998);
+        assert_eq!(result.quota().dir_quota_day(), 100);
+        match cdn.prefetch(&["http://www.example.com/1.html"]) {
+            Err(CDNError::ResponseError { code, message }) => {
+                assert_eq!(code, 400032);
+                assert_eq!(message, "invalid host");
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cdn_manager_traffic_and_logs() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                r"^https://fusion\.qiniuapi\.com/v2/tune/flux$",
+                |request, _| {
+                    let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                    assert_eq!(
+                        body,
+                        json!({
+                            "startDate": "2020-01-01",
+                            "endDate": "2020-01-02",
+                            "granularity": "day",
+                            "domains": "a.qiniu.com;b.qiniu.com",
+                        })
+                    );
+                    Ok(json_response(
+                        200,
+                        json!({
+                            "code": 200,
+                            "error": "",
+                            "time": ["2020-01-01 00:00:00", "2020-01-02 00:00:00"],
+                            "data": {
+                                "a.qiniu.com": {"china": [1024, 2048], "oversea": [0, 512]},
+                                "b.qiniu.com": null,
+                            },
+                        }),
+                    ))
+                },
+            )
+            .install(
+                Method::POST,
+                r"^https://fusion\.qiniuapi\.com/v2/tune/log/list$",
+                |request, _| {
+                    let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                    assert_eq!(body, json!({"day": "2020-01-01", "domains": "a.qiniu.com"}));
+                    Ok(json_response(
+                        200,
+                        json!({
+                            "code": 200,
+                            "error": "",
+                            "data": {
+                                "a.qiniu.com": [{
+                                    "name": "a.qiniu.com_2020-01-01-00_part-00000.gz",
+                                    "size": 1024,
+                                    "mtime": 1_577_840_400,
+                                    "url": "http://log.qiniu.com/a.qiniu.com_2020-01-01-00_part-00000.gz",
+                                }],
+                            },
+                        }),
+                    ))
+                },
+            ),
+        );
+        let cdn = get_cdn_manager(mock.clone());
+        let flux = cdn.flux(
+            &["a.qiniu.com", "b.qiniu.com"],
+            "2020-01-01",
+            "2020-01-02",
+            Granularity::Day,
+        )?;
+        assert_eq!(flux.time().len(), 2);
+        assert_eq!(flux.domain("a.qiniu.com").unwrap().china(), &[1024, 2048]);
+        assert_eq!(flux.domain("a.qiniu.com").unwrap().oversea(), &[0, 512]);
+        assert!(flux.domain("b.qiniu.com").is_none());
+        assert_eq!(flux.domains().collect::<Vec<_>>(), vec!["a.qiniu.com"]);
+
+        let log_files = cdn.log_files(&["a.qiniu.com"], "2020-01-01")?;
+        let files = log_files.get("a.qiniu.com").unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].size(), 1024);
+        assert_eq!(
+            files[0].url(),
+            "http://log.qiniu.com/a.qiniu.com_2020-01-01-00_part-00000.gz"
+        );
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+
+    fn get_cdn_manager(mock: CounterCallMock<CallHandlers>) -> CDNManager {
+        CDNManager::new(
+            Credential::new("abcdefghklmnopq", "1234567890"),
+            ConfigBuilder::default()
+                .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                .http_request_handler(mock)
+                .build(),
+        )
+    }
+}
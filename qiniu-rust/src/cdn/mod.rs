@@ -0,0 +1,15 @@
+//! CDN 模块
+//!
+//! 提供 CDN 缓存刷新，文件预取，流量带宽查询，日志列举，时间戳防盗链签名等 CDN 相关功能
+
+mod logs;
+mod manager;
+mod refresh;
+mod statistics;
+mod timestamp_signer;
+
+pub use logs::LogFile;
+pub use manager::{CDNError, CDNManager, CDNResult};
+pub use refresh::{PrefetchResult, RefreshQuota, RefreshResult};
+pub use statistics::{Granularity, TrafficData, TrafficStatistics};
+pub use timestamp_signer::TimestampSigner;
@@ -0,0 +1,110 @@
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub(super) struct RefreshRequest<'a> {
+    pub(super) urls: &'a [&'a str],
+    pub(super) dirs: &'a [&'a str],
+}
+
+#[derive(Serialize)]
+pub(super) struct PrefetchRequest<'a> {
+    pub(super) urls: &'a [&'a str],
+}
+
+/// 缓存刷新额度
+///
+/// 额度按天计算，每天零点重置
+#[derive(Deserialize, CopyGetters, Debug, Copy, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshQuota {
+    /// 每日 URL 刷新额度
+    #[serde(default)]
+    #[get_copy = "pub"]
+    url_quota_day: u64,
+
+    /// 当日剩余的 URL 刷新额度
+    #[serde(default)]
+    #[get_copy = "pub"]
+    url_surplus_day: u64,
+
+    /// 每日目录刷新额度
+    #[serde(default)]
+    #[get_copy = "pub"]
+    dir_quota_day: u64,
+
+    /// 当日剩余的目录刷新额度
+    #[serde(default)]
+    #[get_copy = "pub"]
+    dir_surplus_day: u64,
+}
+
+/// 缓存刷新结果
+///
+/// 通过 `CDNManager::refresh` 获取
+#[derive(Deserialize, Getters, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshResult {
+    /// 刷新请求 ID，可以用于查询刷新进度
+    #[serde(default)]
+    #[get = "pub"]
+    request_id: String,
+
+    #[serde(default)]
+    invalid_urls: Option<Vec<String>>,
+
+    #[serde(default)]
+    invalid_dirs: Option<Vec<String>>,
+
+    #[serde(flatten)]
+    quota: RefreshQuota,
+}
+
+impl RefreshResult {
+    /// 无效的 URL 列表
+    pub fn invalid_urls(&self) -> &[String] {
+        self.invalid_urls.as_ref().map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 无效的目录列表
+    pub fn invalid_dirs(&self) -> &[String] {
+        self.invalid_dirs.as_ref().map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 提交刷新后的刷新额度
+    pub fn quota(&self) -> RefreshQuota {
+        self.quota
+    }
+}
+
+/// 文件预取结果
+///
+/// 通过 `CDNManager::prefetch` 获取
+#[derive(Deserialize, Getters, CopyGetters, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchResult {
+    /// 预取请求 ID，可以用于查询预取进度
+    #[serde(default)]
+    #[get = "pub"]
+    request_id: String,
+
+    #[serde(default)]
+    invalid_urls: Option<Vec<String>>,
+
+    /// 每日预取额度
+    #[serde(default)]
+    #[get_copy = "pub"]
+    quota_day: u64,
+
+    /// 当日剩余的预取额度
+    #[serde(default)]
+    #[get_copy = "pub"]
+    surplus_day: u64,
+}
+
+impl PrefetchResult {
+    /// 无效的 URL 列表
+    pub fn invalid_urls(&self) -> &[String] {
+        self.invalid_urls.as_ref().map(Vec::as_slice).unwrap_or(&[])
+    }
+}
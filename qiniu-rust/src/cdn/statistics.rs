@@ -0,0 +1,73 @@
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 统计数据的时间粒度
+#[derive(Serialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Granularity {
+    /// 5 分钟
+    #[serde(rename = "5min")]
+    FiveMinutes,
+    /// 1 小时
+    #[serde(rename = "hour")]
+    Hour,
+    /// 1 天
+    #[serde(rename = "day")]
+    Day,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TrafficRequest<'a> {
+    pub(super) start_date: &'a str,
+    pub(super) end_date: &'a str,
+    pub(super) granularity: Granularity,
+    pub(super) domains: &'a str,
+}
+
+/// 单个域名的统计数据
+///
+/// 每个元素与 `TrafficStatistics::time` 中的时间点一一对应
+#[derive(Deserialize, Getters, Debug, Clone, Default)]
+pub struct TrafficData {
+    /// 国内的统计数据
+    #[serde(default)]
+    #[get = "pub"]
+    china: Vec<u64>,
+
+    /// 海外的统计数据
+    #[serde(default)]
+    #[get = "pub"]
+    oversea: Vec<u64>,
+}
+
+/// 流量或带宽统计结果
+///
+/// 流量的单位为字节，带宽的单位为 bps
+#[derive(Deserialize, Getters, Debug, Clone)]
+pub struct TrafficStatistics {
+    /// 统计数据的时间点列表
+    #[serde(default)]
+    #[get = "pub"]
+    time: Vec<String>,
+
+    #[serde(default)]
+    data: HashMap<String, Option<TrafficData>>,
+}
+
+impl TrafficStatistics {
+    /// 获取指定域名的统计数据
+    ///
+    /// 如果该域名在查询时间范围内没有数据，将返回 `None`
+    pub fn domain(&self, domain: impl AsRef<str>) -> Option<&TrafficData> {
+        self.data.get(domain.as_ref()).and_then(|data| data.as_ref())
+    }
+
+    /// 获取所有有数据的域名
+    pub fn domains(&self) -> impl Iterator<Item = &str> {
+        self.data
+            .iter()
+            .filter(|(_, data)| data.is_some())
+            .map(|(domain, _)| domain.as_str())
+    }
+}
@@ -0,0 +1,98 @@
+use std::{
+    borrow::Cow,
+    result::Result,
+    time::{Duration, SystemTime},
+};
+use url::{ParseError as URLParseError, Url};
+
+/// CDN 时间戳防盗链签名器
+///
+/// 用于为开启了时间戳防盗链的 CDN 域名生成带有有效期的访问地址。
+/// 签名算法为 `sign = md5(key + path + t)`，其中 `path` 为经过百分号编码的 URL 路径，`t` 为十六进制表示的过期时间戳。
+/// 原 URL 中的查询字符串将被保留，签名参数将被追加在查询字符串末尾
+///
+/// 可以点击[这里](https://developer.qiniu.com/fusion/kb/1670/timestamp-hotlinking-prevention)了解时间戳防盗链
+///
+/// ```rust
+/// use qiniu_ng::cdn::TimestampSigner;
+/// use std::time::{Duration, SystemTime};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let signer = TimestampSigner::new("[Anti-leech Key]");
+/// let deadline = SystemTime::UNIX_EPOCH + Duration::from_secs(1_438_358_400);
+/// let signed_url = signer.sign_with_deadline("http://www.example.com/test.jpg?imageInfo", deadline)?;
+/// assert!(signed_url.starts_with("http://www.example.com/test.jpg?imageInfo&sign="));
+/// assert!(signed_url.ends_with("&t=55bb9b80"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimestampSigner<'a> {
+    key: Cow<'a, str>,
+}
+
+impl<'a> TimestampSigner<'a> {
+    /// 使用 CDN 域名配置的防盗链密钥创建签名器
+    pub fn new(key: impl Into<Cow<'a, str>>) -> Self {
+        TimestampSigner { key: key.into() }
+    }
+
+    /// 生成指定过期时间的访问地址
+    pub fn sign_with_deadline(&self, url: impl AsRef<str>, deadline: SystemTime) -> Result<String, URLParseError> {
+        let mut url = Url::parse(url.as_ref())?;
+        let timestamp = format!(
+            "{:x}",
+            deadline
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|t| t.as_secs())
+                .unwrap_or(0)
+        );
+        let sign = {
+            let mut to_sign = String::with_capacity(self.key.len() + url.path().len() + timestamp.len());
+            to_sign.push_str(&self.key);
+            to_sign.push_str(url.path());
+            to_sign.push_str(&timestamp);
+            format!("{:x}", md5::compute(to_sign.as_bytes()))
+        };
+        url.query_pairs_mut()
+            .append_pair("sign", &sign)
+            .append_pair("t", &timestamp);
+        Ok(url.as_str().to_owned())
+    }
+
+    /// 生成指定有效期的访问地址
+    pub fn sign_with_lifetime(&self, url: impl AsRef<str>, lifetime: Duration) -> Result<String, URLParseError> {
+        self.sign_with_deadline(url, SystemTime::now() + lifetime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_cdn_timestamp_signer() -> Result<(), Box<dyn Error>> {
+        let signer = TimestampSigner::new("ki0jk3da7xp4ohmsyc7yigdmpbokhnqjnyqyi9nw");
+        let deadline = SystemTime::UNIX_EPOCH + Duration::from_secs(0x55bb_9b80);
+        assert_eq!(
+            signer.sign_with_deadline("http://www.qiniu.com/DIR1/dir2/vodfile.mp4", deadline)?,
+            format!(
+                "http://www.qiniu.com/DIR1/dir2/vodfile.mp4?sign={:x}&t=55bb9b80",
+                md5::compute("ki0jk3da7xp4ohmsyc7yigdmpbokhnqjnyqyi9nw/DIR1/dir2/vodfile.mp455bb9b80")
+            )
+        );
+        assert_eq!(
+            signer.sign_with_deadline("http://www.qiniu.com/中文 目录/file.mp4?v=1&u=2", deadline)?,
+            format!(
+                "http://www.qiniu.com/%E4%B8%AD%E6%96%87%20%E7%9B%AE%E5%BD%95/file.mp4?v=1&u=2&sign={:x}&t=55bb9b80",
+                md5::compute(
+                    "ki0jk3da7xp4ohmsyc7yigdmpbokhnqjnyqyi9nw/%E4%B8%AD%E6%96%87%20%E7%9B%AE%E5%BD%95/file.mp455bb9b80"
+                )
+            )
+        );
+        assert!(signer.sign_with_deadline("/relative/path", deadline).is_err());
+        Ok(())
+    }
+}
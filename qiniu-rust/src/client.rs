@@ -1,5 +1,6 @@
 //! 七牛客户端模块
 use super::{
+    cdn::CDNManager,
     config::Config,
     credential::Credential,
     storage::{manager::StorageManager, uploader::UploadManager},
@@ -19,6 +20,9 @@ pub struct Client {
 
     #[get]
     upload_manager: UploadManager,
+
+    #[get]
+    cdn_manager: CDNManager,
 }
 
 impl Client {
@@ -44,6 +48,7 @@ impl Client {
         let credential = Credential::new(access_key, secret_key);
         Client {
             upload_manager: UploadManager::new(config.clone()),
+            cdn_manager: CDNManager::new(credential.clone(), config.clone()),
             storage_manager: StorageManager::new(credential, config),
         }
     }
@@ -60,6 +65,12 @@ impl Client {
         self.upload_manager()
     }
 
+    /// 获取 CDN 管理器
+    #[inline]
+    pub fn cdn(&self) -> &CDNManager {
+        self.cdn_manager()
+    }
+
     /// 获取客户端配置
     #[inline]
     pub fn config(&self) -> &Config {
@@ -1,7 +1,10 @@
 //! 七牛客户端配置模块
 use crate::{
     http::{DomainsManager, HTTPAfterAction, HTTPBeforeAction, HTTPCaller},
-    storage::uploader::{UploadLogger, UploadLoggerBuilder, UploadRecorder},
+    storage::{
+        download::DownloadRecorder,
+        uploader::{UploadLogger, UploadLoggerBuilder, UploadRecorder},
+    },
 };
 use assert_impl::assert_impl;
 use derive_builder::Builder;
@@ -74,6 +77,13 @@ pub struct ConfigInner {
     #[builder(default = "default::uplog_host()", setter(into))]
     uplog_host: Cow<'static, str>,
 
+    /// Fusion 服务器地址（仅需要指定主机地址和端口，无需包含协议）
+    ///
+    /// 用于 CDN 相关功能，默认将会使用七牛公有云的 Fusion 服务器地址，仅在使用私有云时才需要配置
+    #[get = "pub"]
+    #[builder(default = "default::fusion_host()", setter(into))]
+    fusion_host: Cow<'static, str>,
+
     /// 上传凭证有效期
     ///
     /// 默认为 1 小时
@@ -81,6 +91,13 @@ pub struct ConfigInner {
     #[builder(default = "default::upload_token_lifetime()")]
     upload_token_lifetime: Duration,
 
+    /// 私有空间下载地址有效期
+    ///
+    /// 默认为 1 小时
+    #[get_copy = "pub"]
+    #[builder(default = "default::download_url_lifetime()")]
+    download_url_lifetime: Duration,
+
     /// 最大批量操作数
     ///
     /// 默认为 1000
@@ -118,6 +135,13 @@ pub struct ConfigInner {
     #[builder(default)]
     upload_recorder: UploadRecorder,
 
+    /// 下载进度记录仪
+    ///
+    /// 用于记录文件分片下载进度，如果文件在下载期间发生错误，将可以在重试时避免再次下载已经成功下载的文件分片，实现断点续传
+    #[get = "pub"]
+    #[builder(default)]
+    download_recorder: DownloadRecorder,
+
     /// HTTP 请求连接超时时长
     ///
     /// 对七牛 Rust SDK 所有发出的 HTTP 请求均有效
@@ -284,11 +308,21 @@ mod default {
         Cow::Borrowed("uplog.qbox.me")
     }
 
+    #[inline]
+    pub const fn fusion_host() -> Cow<'static, str> {
+        Cow::Borrowed("fusion.qiniuapi.com")
+    }
+
     #[inline]
     pub const fn upload_token_lifetime() -> Duration {
         Duration::from_secs(60 * 60)
     }
 
+    #[inline]
+    pub const fn download_url_lifetime() -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
     #[inline]
     pub const fn batch_max_operation_size() -> usize {
         1000
@@ -372,11 +406,14 @@ impl fmt::Debug for ConfigInner {
             .field("rsf_host", &self.rsf_host)
             .field("api_host", &self.api_host)
             .field("uplog_host", &self.uplog_host)
+            .field("fusion_host", &self.fusion_host)
             .field("upload_token_lifetime", &self.upload_token_lifetime)
+            .field("download_url_lifetime", &self.download_url_lifetime)
             .field("batch_max_operation_size", &self.batch_max_operation_size)
             .field("upload_threshold", &self.upload_threshold)
             .field("upload_block_size", &self.upload_block_size)
             .field("upload_recorder", &self.upload_recorder)
+            .field("download_recorder", &self.download_recorder)
             .field("upload_logger", &self.upload_logger)
             .field("http_request_retries", &self.http_request_retries)
             .field("http_request_retry_delay", &self.http_request_retry_delay)
@@ -437,6 +474,15 @@ impl ConfigInner {
             "http://".to_owned() + self.uplog_host.as_ref()
         }
     }
+
+    /// Fusion 服务器 URL
+    pub fn fusion_url(&self) -> String {
+        if self.use_https {
+            "https://".to_owned() + self.fusion_host.as_ref()
+        } else {
+            "http://".to_owned() + self.fusion_host.as_ref()
+        }
+    }
 }
 
 /// 七牛客户端配置
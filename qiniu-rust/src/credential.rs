@@ -208,7 +208,6 @@ impl Credential {
         self.sign_with_data(upload_policy.as_json().as_bytes())
     }
 
-    #[allow(dead_code)]
     pub(crate) fn sign_download_url_with_deadline(
         &self,
         url: Url,
@@ -26,6 +26,10 @@ impl Client {
         self.request_builder(Method::PUT, path, hosts)
     }
 
+    pub(crate) fn delete<'a>(&self, path: &'a str, hosts: &'a [&'a str]) -> RequestBuilder<'a> {
+        self.request_builder(Method::DELETE, path, hosts)
+    }
+
     pub(crate) fn head<'a>(&self, path: &'a str, hosts: &'a [&'a str]) -> RequestBuilder<'a> {
         self.request_builder(Method::HEAD, path, hosts)
     }
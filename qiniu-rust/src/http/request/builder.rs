@@ -32,8 +32,11 @@ impl<'a> Builder<'a> {
                 follow_redirection: false,
                 on_uploading_progress: None,
                 on_downloading_progress: None,
+                on_reading_body: None,
                 on_response: None,
                 on_error: None,
+                on_attempt: None,
+                on_host_frozen: None,
             },
         }
     }
@@ -95,6 +98,11 @@ impl<'a> Builder<'a> {
         self
     }
 
+    pub(crate) fn on_reading_body(mut self, callback: &'a dyn Fn(usize)) -> Builder<'a> {
+        self.parts.on_reading_body = Some(callback);
+        self
+    }
+
     pub(crate) fn on_response(
         mut self,
         callback: &'a dyn Fn(&mut Response, Duration) -> HTTPResult<()>,
@@ -108,6 +116,17 @@ impl<'a> Builder<'a> {
         self
     }
 
+    // 每次尝试发送请求前回调，第一个参数为本次尝试使用的域名，如果本次尝试是一次重试，第二个参数为上一次尝试的错误
+    pub(crate) fn on_attempt(mut self, callback: &'a dyn Fn(&str, Option<&HTTPError>)) -> Builder<'a> {
+        self.parts.on_attempt = Some(callback);
+        self
+    }
+
+    pub(crate) fn on_host_frozen(mut self, callback: &'a dyn Fn(&str, &HTTPError)) -> Builder<'a> {
+        self.parts.on_host_frozen = Some(callback);
+        self
+    }
+
     pub(crate) fn accept_json(mut self) -> Builder<'a> {
         self = self.header("Accept", "application/json");
         self.parts.read_body = true;
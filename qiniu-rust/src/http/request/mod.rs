@@ -44,13 +44,16 @@ impl<'a> Request<'a> {
         for choice in choices {
             let base_url = choice.base_url;
             let timer = Instant::now();
-            match self.try_choice(choice) {
+            match self.try_choice(choice, prev_err.as_ref()) {
                 Ok(resp) => {
                     return Ok(resp);
                 }
                 Err(err) => match err.retry_kind() {
                     HTTPRetryKind::RetryableError | HTTPRetryKind::HostUnretryableError if self.is_retry_safe(&err) => {
                         self.domains_manager.freeze_url(base_url).unwrap();
+                        if let Some(on_host_frozen) = &self.parts.on_host_frozen {
+                            (on_host_frozen)(base_url, &err);
+                        }
                         if let Some(on_error) = &self.parts.on_error {
                             (on_error)(Some(base_url), &err, timer.elapsed());
                         }
@@ -69,7 +72,7 @@ impl<'a> Request<'a> {
         Err(prev_err.unwrap())
     }
 
-    fn try_choice(&self, choice: Choice<'a>) -> HTTPResult<Response<'a>> {
+    fn try_choice(&self, choice: Choice<'a>, prev_host_err: Option<&HTTPError>) -> HTTPResult<Response<'a>> {
         let mut request = {
             let mut builder = RequestBuilder::default()
                 .method(self.parts.method)
@@ -91,6 +94,9 @@ impl<'a> Request<'a> {
             if let Some(on_downloading_progress) = self.parts.on_downloading_progress {
                 builder = builder.on_downloading_progress(on_downloading_progress);
             }
+            if let Some(on_reading_body) = self.parts.on_reading_body {
+                builder = builder.on_reading_body(on_reading_body);
+            }
             if let Some(headers) = &self.parts.headers {
                 builder = builder.headers(headers.to_owned());
             }
@@ -107,6 +113,9 @@ impl<'a> Request<'a> {
         let retries = self.parts.config.http_request_retries();
         assert!(retries > 0);
         for _ in 0..=retries {
+            if let Some(on_attempt) = &self.parts.on_attempt {
+                (on_attempt)(choice.base_url, prev_err.as_ref().or(prev_host_err));
+            }
             let timer = Instant::now();
             match self
                 .do_request(&mut request)
@@ -179,7 +188,7 @@ impl<'a> Request<'a> {
 
     fn is_retry_safe(&self, err: &HTTPError) -> bool {
         match self.parts.method {
-            Method::GET | Method::PUT | Method::HEAD => true,
+            Method::GET | Method::PUT | Method::HEAD | Method::DELETE => true,
             _ => self.parts.idempotent || err.is_retry_safe(),
         }
     }
@@ -804,6 +813,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_on_attempt_and_on_host_frozen() -> StdResult<(), Box<dyn StdError>> {
+        let mock = CounterCallMock::new(HTTPRetryer {
+            retry_kind: HTTPRetryKind::RetryableError,
+            is_retry_safe: true,
+        });
+        let config: Config = ConfigBuilder::default()
+            .http_request_retries(RETRIES)
+            .http_request_retry_delay(Duration::from_millis(1))
+            .http_request_handler(mock.clone())
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let (first_attempts, retries, frozen_hosts) = (AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0));
+        assert!(Builder::new(
+            config,
+            Method::GET,
+            "/test_call",
+            &["http://z1h1.com:1111", "http://z1h2.com:2222"],
+        )
+        .token(TokenVersion::V2, get_credential().into())
+        .on_attempt(&|host, prev_err| {
+            if prev_err.is_some() {
+                retries.fetch_add(1, Relaxed);
+            } else {
+                assert_eq!(host, "http://z1h1.com:1111");
+                first_attempts.fetch_add(1, Relaxed);
+            }
+        })
+        .on_host_frozen(&|_, _| {
+            frozen_hosts.fetch_add(1, Relaxed);
+        })
+        .raw_body("application/json", b"{\"test\":123}".as_ref())
+        .send()
+        .is_err());
+
+        assert_eq!(mock.call_called(), 2 * (RETRIES + 1));
+        assert_eq!(first_attempts.load(Relaxed), 1);
+        assert_eq!(retries.load(Relaxed), 2 * (RETRIES + 1) - 1);
+        assert_eq!(frozen_hosts.load(Relaxed), 2);
+        Ok(())
+    }
+
     fn get_credential() -> Credential {
         Credential::new("abcdefghklmnopq", "1234567890")
     }
@@ -19,8 +19,13 @@ pub(crate) struct Parts<'a> {
     pub(super) follow_redirection: bool,
     pub(super) on_uploading_progress: Option<&'a dyn Fn(u64, u64)>,
     pub(super) on_downloading_progress: Option<&'a dyn Fn(u64, u64)>,
+    pub(super) on_reading_body: Option<&'a dyn Fn(usize)>,
     pub(super) on_response: Option<&'a dyn Fn(&mut Response, Duration) -> HTTPResult<()>>,
     pub(super) on_error: Option<&'a dyn Fn(Option<&str>, &HTTPError, Duration)>,
+    #[allow(clippy::type_complexity)]
+    pub(super) on_attempt: Option<&'a dyn Fn(&str, Option<&HTTPError>)>,
+    #[allow(clippy::type_complexity)]
+    pub(super) on_host_frozen: Option<&'a dyn Fn(&str, &HTTPError)>,
 }
 
 impl fmt::Debug for Parts<'_> {
@@ -53,6 +58,14 @@ impl fmt::Debug for Parts<'_> {
                     &"Not Installed"
                 },
             )
+            .field(
+                "on_reading_body",
+                if self.on_reading_body.is_some() {
+                    &"Installed"
+                } else {
+                    &"Not Installed"
+                },
+            )
             .field(
                 "on_response",
                 if self.on_response.is_some() {
@@ -69,6 +82,22 @@ impl fmt::Debug for Parts<'_> {
                     &"Not Installed"
                 },
             )
+            .field(
+                "on_attempt",
+                if self.on_attempt.is_some() {
+                    &"Installed"
+                } else {
+                    &"Not Installed"
+                },
+            )
+            .field(
+                "on_host_frozen",
+                if self.on_host_frozen.is_some() {
+                    &"Installed"
+                } else {
+                    &"Not Installed"
+                },
+            )
             .finish()
     }
 }
@@ -9,10 +9,10 @@
 //! Rust SDK 属于七牛服务端 SDK 之一，主要有如下功能：
 //! 1. 提供生成客户端上传所需的上传凭证的功能
 //! 2. 提供文件从服务端直接上传七牛的功能
-//! 3. 提供文件从七牛直接下载到本地的功能 【开发中】
+//! 3. 提供文件从七牛直接下载到本地的功能
 //! 4. 提供对七牛空间中文件进行管理的功能 【开发中】
 //! 5. 提供对七牛空间中文件进行处理的功能 【开发中】
-//! 6. 提供七牛 CDN 相关的刷新，预取，日志功能 【开发中】
+//! 6. 提供七牛 CDN 相关的刷新，预取，日志功能
 //!
 //! # 鉴权
 //!
@@ -531,6 +531,7 @@ pub use client::Client;
 mod credential;
 pub use credential::Credential;
 
+pub mod cdn;
 pub mod config;
 pub use config::{Config, ConfigBuilder};
 
@@ -0,0 +1,506 @@
+//! 批量操作模块
+//!
+//! 提供对象批量操作功能
+
+use super::object::{path, FileType, ObjectInfo};
+use crate::{
+    credential::Credential,
+    http::{Client, Error as HTTPError, TokenVersion},
+};
+use assert_impl::assert_impl;
+use serde::Deserialize;
+use serde_json::Value;
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// 批量操作生成器
+///
+/// 用于累积对象批量操作，在执行时，SDK 将根据 `Config::batch_max_operation_size` 自动将操作拆分为多个请求发送，
+/// 并按照操作的添加顺序返回每个操作的结果
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let bucket = client.storage().bucket("[Bucket name]").build();
+/// let mut batch = bucket.batch();
+/// for key in &["[Key 1]", "[Key 2]", "[Key 3]"] {
+///     batch.delete(key);
+/// }
+/// for result in batch.execute()? {
+///     if !result.is_success() {
+///         println!("code: {}, error: {:?}", result.code(), result.error());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Batch<'b> {
+    bucket: Cow<'b, str>,
+    credential: Cow<'b, Credential>,
+    rs_urls: Vec<Cow<'static, str>>,
+    operations: Vec<String>,
+    http_client: Client,
+}
+
+/// 批量操作中单个操作的结果
+#[derive(Deserialize, Debug, Clone)]
+pub struct BatchOperationResult {
+    code: u16,
+    #[serde(default)]
+    data: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct BatchOperationError {
+    error: String,
+}
+
+impl<'b> Batch<'b> {
+    pub(crate) fn new(
+        bucket: Cow<'b, str>,
+        credential: Cow<'b, Credential>,
+        rs_urls: Vec<Cow<'static, str>>,
+        http_client: Client,
+    ) -> Batch<'b> {
+        Batch {
+            bucket,
+            credential,
+            rs_urls,
+            http_client,
+            operations: Vec::new(),
+        }
+    }
+
+    /// 获取对象元信息
+    pub fn stat(&mut self, key: impl AsRef<str>) -> &mut Self {
+        self.operations.push(path::stat(&self.bucket, key.as_ref()));
+        self
+    }
+
+    /// 删除对象
+    pub fn delete(&mut self, key: impl AsRef<str>) -> &mut Self {
+        self.operations.push(path::delete(&self.bucket, key.as_ref()));
+        self
+    }
+
+    /// 复制对象到目标存储空间
+    pub fn copy_to(
+        &mut self,
+        key: impl AsRef<str>,
+        dest_bucket: impl AsRef<str>,
+        dest_key: impl AsRef<str>,
+        force: bool,
+    ) -> &mut Self {
+        self.operations.push(path::copy_to(
+            &self.bucket,
+            key.as_ref(),
+            dest_bucket.as_ref(),
+            dest_key.as_ref(),
+            force,
+        ));
+        self
+    }
+
+    /// 移动对象到目标存储空间
+    pub fn move_to(
+        &mut self,
+        key: impl AsRef<str>,
+        dest_bucket: impl AsRef<str>,
+        dest_key: impl AsRef<str>,
+        force: bool,
+    ) -> &mut Self {
+        self.operations.push(path::move_to(
+            &self.bucket,
+            key.as_ref(),
+            dest_bucket.as_ref(),
+            dest_key.as_ref(),
+            force,
+        ));
+        self
+    }
+
+    /// 修改对象 MIME 类型
+    pub fn change_mime_type(&mut self, key: impl AsRef<str>, mime_type: impl AsRef<str>) -> &mut Self {
+        self.operations
+            .push(path::change_mime_type(&self.bucket, key.as_ref(), mime_type.as_ref()));
+        self
+    }
+
+    /// 修改对象存储类型
+    pub fn change_file_type(&mut self, key: impl AsRef<str>, file_type: FileType) -> &mut Self {
+        self.operations
+            .push(path::change_file_type(&self.bucket, key.as_ref(), file_type));
+        self
+    }
+
+    /// 修改对象 MIME 类型和自定义元数据
+    ///
+    /// `mime_type` 为 `None` 时不修改 MIME 类型，元数据名称可以省略 `x-qn-meta-` 前缀
+    pub fn change_metadata<K: AsRef<str>, V: AsRef<str>>(
+        &mut self,
+        key: impl AsRef<str>,
+        mime_type: Option<&str>,
+        metadata: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut Self {
+        let metadata = metadata.into_iter().collect::<Vec<_>>();
+        let metadata = metadata
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_ref()))
+            .collect::<Vec<_>>();
+        self.operations
+            .push(path::change_metadata(&self.bucket, key.as_ref(), mime_type, &metadata));
+        self
+    }
+
+    /// 启用对象
+    pub fn enable(&mut self, key: impl AsRef<str>) -> &mut Self {
+        self.operations
+            .push(path::change_status(&self.bucket, key.as_ref(), false));
+        self
+    }
+
+    /// 禁用对象
+    pub fn disable(&mut self, key: impl AsRef<str>) -> &mut Self {
+        self.operations
+            .push(path::change_status(&self.bucket, key.as_ref(), true));
+        self
+    }
+
+    /// 设置对象在多少天后被删除
+    ///
+    /// `days` 为 0 表示取消对象的过期删除
+    pub fn delete_after_days(&mut self, key: impl AsRef<str>, days: u32) -> &mut Self {
+        self.operations
+            .push(path::delete_after_days(&self.bucket, key.as_ref(), days));
+        self
+    }
+
+    /// 解冻归档存储对象
+    ///
+    /// 解冻后的对象将在 `freeze_after_days` 天后重新冻结，取值范围为 1 到 7
+    pub fn restore_archive(&mut self, key: impl AsRef<str>, freeze_after_days: u32) -> &mut Self {
+        self.operations
+            .push(path::restore_archive(&self.bucket, key.as_ref(), freeze_after_days));
+        self
+    }
+
+    /// 已经添加的操作数量
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// 是否尚未添加任何操作
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// 清空所有已经添加的操作
+    ///
+    /// 清空后生成器可以被复用
+    pub fn clear(&mut self) -> &mut Self {
+        self.operations.clear();
+        self
+    }
+
+    /// 执行批量操作
+    ///
+    /// 如果操作数超过 `Config::batch_max_operation_size`，将被拆分为多个请求依次发送。
+    /// 返回的结果与操作的添加顺序一致。
+    /// 注意，单个操作失败不会导致该方法返回错误，只有请求本身失败或返回的结果数量与操作数量不符才会返回错误，
+    /// 此时可以通过 `BatchError::completed` 获取此前已经执行完毕的操作结果
+    pub fn execute(&self) -> BatchResult<Vec<BatchOperationResult>> {
+        let rs_urls = self.rs_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        let batch_size = self.http_client.config().batch_max_operation_size().max(1);
+        let mut results = Vec::with_capacity(self.operations.len());
+        for operations in self.operations.chunks(batch_size) {
+            match self.execute_chunk(operations, &rs_urls) {
+                Ok(mut chunk_results) => results.append(&mut chunk_results),
+                Err(kind) => {
+                    return Err(BatchError {
+                        kind,
+                        completed: results,
+                    })
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    fn execute_chunk(
+        &self,
+        operations: &[String],
+        rs_urls: &[&str],
+    ) -> Result<Vec<BatchOperationResult>, BatchErrorKind> {
+        let body = serde_urlencoded::to_string(
+            operations
+                .iter()
+                .map(|operation| ("op", operation.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let results: Vec<BatchOperationResult> = self
+            .http_client
+            .post("/batch", rs_urls)
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .accept_json()
+            .raw_body("application/x-www-form-urlencoded", body)
+            .send()?
+            .parse_json()?;
+        if results.len() != operations.len() {
+            return Err(BatchErrorKind::UnexpectedResultsCount {
+                expected: operations.len(),
+                actual: results.len(),
+            });
+        }
+        Ok(results)
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+impl BatchOperationResult {
+    /// 操作结果的状态码
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// 操作是否成功
+    pub fn is_success(&self) -> bool {
+        self.code == 200
+    }
+
+    /// 操作返回的数据
+    pub fn data(&self) -> Option<&Value> {
+        self.data.as_ref()
+    }
+
+    /// 操作失败时的错误信息
+    pub fn error(&self) -> Option<String> {
+        if self.is_success() {
+            return None;
+        }
+        self.data
+            .as_ref()
+            .and_then(|data| serde_json::from_value::<BatchOperationError>(data.to_owned()).ok())
+            .map(|data| data.error)
+    }
+
+    /// 对于成功的 `stat` 操作，返回对象元信息
+    pub fn object_info(&self) -> Option<ObjectInfo> {
+        if !self.is_success() {
+            return None;
+        }
+        self.data
+            .as_ref()
+            .and_then(|data| serde_json::from_value(data.to_owned()).ok())
+    }
+}
+
+/// 批量操作错误
+///
+/// 拆分后的请求是依次发送的，当某个请求出错时，此前的请求中的操作已经在服务器端执行完毕，
+/// 这些操作的结果可以通过 `completed` 获取，它们与最先添加的若干个操作一一对应。
+/// 出错的请求中的操作可能已经执行，也可能没有执行，之后的操作则都没有执行
+#[derive(Error, Debug)]
+#[error("{kind}")]
+pub struct BatchError {
+    kind: BatchErrorKind,
+    completed: Vec<BatchOperationResult>,
+}
+
+/// 批量操作错误类型
+#[derive(Error, Debug)]
+pub enum BatchErrorKind {
+    /// 七牛 API 调用错误
+    #[error("Qiniu API call error: {0}")]
+    HTTPError(#[from] HTTPError),
+    /// 返回的结果数量与请求中的操作数量不符
+    #[error("Unexpected batch results count: expected = {expected}, actual = {actual}")]
+    UnexpectedResultsCount {
+        /// 请求中的操作数量
+        expected: usize,
+        /// 返回的结果数量
+        actual: usize,
+    },
+}
+
+impl BatchError {
+    /// 错误类型
+    pub fn kind(&self) -> &BatchErrorKind {
+        &self.kind
+    }
+
+    /// 出错前已经执行完毕的操作结果
+    pub fn completed(&self) -> &[BatchOperationResult] {
+        &self.completed
+    }
+
+    /// 获取出错前已经执行完毕的操作结果的所有权
+    pub fn into_completed(self) -> Vec<BatchOperationResult> {
+        self.completed
+    }
+}
+
+/// 批量操作结果
+pub type BatchResult<T> = Result<T, BatchError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ConfigBuilder,
+        http::{DomainsManagerBuilder, ErrorKind as HTTPErrorKind, Method},
+    };
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock};
+    use serde_json::json;
+    use std::{error::Error, result::Result};
+
+    #[test]
+    fn test_storage_batch_execute() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, "/batch$", |request, _| {
+                let body = String::from_utf8(request.body().as_ref().unwrap().to_vec()).unwrap();
+                let operations = serde_urlencoded::from_str::<Vec<(String, String)>>(&body).unwrap();
+                assert!(operations.len() <= 2);
+                let results = operations
+                    .into_iter()
+                    .map(|(name, operation)| {
+                        assert_eq!(name, "op");
+                        if operation.starts_with("/stat/") {
+                            json!({"code": 200, "data": {"fsize": 1024, "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk", "mimeType": "text/plain", "putTime": 0}})
+                        } else if operation == path::delete("test-bucket", "test-key-2") {
+                            json!({"code": 612, "data": {"error": "no such file or directory"}})
+                        } else {
+                            json!({"code": 200})
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                Ok(json_response(298, results))
+            }),
+        );
+        let mut batch = Batch::new(
+            "test-bucket".into(),
+            Credential::new("abcdefghklmnopq", "1234567890").into(),
+            vec!["https://rs.qbox.me".into()],
+            Client::new(
+                ConfigBuilder::default()
+                    .batch_max_operation_size(2)
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        );
+        batch
+            .stat("test-key-1")
+            .delete("test-key-2")
+            .copy_to("test-key-1", "test-bucket-2", "test-key-1", true)
+            .move_to("test-key-3", "test-bucket", "test-key-4", false)
+            .change_mime_type("test-key-4", "text/html");
+        assert_eq!(batch.len(), 5);
+        batch
+            .change_metadata("test-key-4", None, vec![("author", "qiniu")])
+            .disable("test-key-4")
+            .delete_after_days("test-key-4", 30)
+            .restore_archive("test-key-5", 1);
+        assert_eq!(batch.len(), 9);
+        let results = batch.execute()?;
+        assert_eq!(mock.call_called(), 5);
+        assert_eq!(results.len(), 9);
+        assert_eq!(results[0].object_info().unwrap().size(), 1024);
+        assert!(!results[1].is_success());
+        assert_eq!(results[1].code(), 612);
+        assert_eq!(results[1].error(), Some("no such file or directory".to_owned()));
+        assert!(results[2..].iter().all(|result| result.is_success()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_batch_execute_with_http_error() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, "/batch$", |_, called| match called {
+                1 => Ok(json_response(298, json!([{"code": 200}, {"code": 200}]))),
+                _ => Ok(json_response(612, json!({"error": "no such bucket"}))),
+            }),
+        );
+        let mut batch = new_batch(mock.clone());
+        batch
+            .delete("test-key-1")
+            .delete("test-key-2")
+            .delete("test-key-3")
+            .delete("test-key-4")
+            .delete("test-key-5");
+        let err = batch.execute().unwrap_err();
+        assert_eq!(mock.call_called(), 2);
+        match err.kind() {
+            BatchErrorKind::HTTPError(err) => match err.error_kind() {
+                HTTPErrorKind::ResponseStatusCodeError(status_code, _) => assert_eq!(*status_code, 612),
+                kind => panic!("Unexpected HTTP error kind: {}", kind),
+            },
+            kind => panic!("Unexpected error kind: {}", kind),
+        }
+        let completed = err.into_completed();
+        assert_eq!(completed.len(), 2);
+        assert!(completed.iter().all(|result| result.is_success()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_batch_execute_with_unexpected_results_count() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, "/batch$", |_, called| match called {
+                1 => Ok(json_response(298, json!([{"code": 200}, {"code": 612}]))),
+                _ => Ok(json_response(200, json!([{"code": 200}]))),
+            }),
+        );
+        let mut batch = new_batch(mock.clone());
+        batch
+            .delete("test-key-1")
+            .delete("test-key-2")
+            .delete("test-key-3")
+            .delete("test-key-4");
+        let err = batch.execute().unwrap_err();
+        assert_eq!(mock.call_called(), 2);
+        match err.kind() {
+            BatchErrorKind::UnexpectedResultsCount { expected, actual } => {
+                assert_eq!(*expected, 2);
+                assert_eq!(*actual, 1);
+            }
+            kind => panic!("Unexpected error kind: {}", kind),
+        }
+        assert_eq!(err.completed().len(), 2);
+        assert!(err.completed()[0].is_success());
+        assert_eq!(err.completed()[1].code(), 612);
+        Ok(())
+    }
+
+    fn new_batch(mock: CounterCallMock<CallHandlers>) -> Batch<'static> {
+        Batch::new(
+            "test-bucket".into(),
+            Credential::new("abcdefghklmnopq", "1234567890").into(),
+            vec!["https://rs.qbox.me".into()],
+            Client::new(
+                ConfigBuilder::default()
+                    .batch_max_operation_size(2)
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock)
+                    .build(),
+            ),
+        )
+    }
+}
@@ -1,12 +1,20 @@
 //! 存储空间模块
 
 use super::{
+    batch::Batch,
+    download::{DownloadUrlBuilder, Downloader},
+    fetch::{AsyncFetchBuilder, AsyncFetchJob, FetchResult},
+    list::ListBuilder,
+    object::{path, FileType, ObjectInfo},
+    processing::{PersistentStatus, PfopResult},
     region::{Region, RegionId},
     uploader::{BucketUploaderBuilder, UploadManager},
 };
 use crate::{
+    config::Config,
     credential::Credential,
-    http::{Client, Result},
+    http::{Client, Result, TokenVersion},
+    utils::base64,
 };
 use assert_impl::assert_impl;
 use once_cell::sync::OnceCell;
@@ -280,7 +288,345 @@ impl<'r> Bucket<'r> {
         self.upload_manager.for_bucket(self)
     }
 
-    fn rs_urls(&self) -> Vec<Cow<'static, str>> {
+    /// 获取对象元信息
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use qiniu_ng::{Client, Config};
+    /// # use std::{result::Result, error::Error};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+    /// let bucket = client.storage().bucket("[Bucket name]").build();
+    /// let info = bucket.stat("[Key]")?;
+    /// println!("size: {}, hash: {}", info.size(), info.hash());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stat(&self, key: impl AsRef<str>) -> Result<ObjectInfo> {
+        let path = path::stat(self.name(), key.as_ref());
+        let rs_urls = self.rs_urls();
+        let rs_urls = rs_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.http_client
+            .post(&path, &rs_urls)
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .idempotent()
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()
+    }
+
+    /// 删除对象
+    pub fn delete(&self, key: impl AsRef<str>) -> Result<()> {
+        self.rs_call(&path::delete(self.name(), key.as_ref()))
+    }
+
+    /// 复制对象
+    ///
+    /// 将当前存储空间中的对象复制到目标存储空间 `dest_bucket` 中，命名为 `dest_key`。
+    /// 如果目标对象已经存在，仅当 `force` 为 `true` 时才会覆盖目标对象，否则将返回错误
+    pub fn copy_to(
+        &self,
+        key: impl AsRef<str>,
+        dest_bucket: impl AsRef<str>,
+        dest_key: impl AsRef<str>,
+        force: bool,
+    ) -> Result<()> {
+        self.rs_call(&path::copy_to(
+            self.name(),
+            key.as_ref(),
+            dest_bucket.as_ref(),
+            dest_key.as_ref(),
+            force,
+        ))
+    }
+
+    /// 移动对象
+    ///
+    /// 将当前存储空间中的对象移动到目标存储空间 `dest_bucket` 中，命名为 `dest_key`。
+    /// 如果目标对象已经存在，仅当 `force` 为 `true` 时才会覆盖目标对象，否则将返回错误
+    pub fn move_to(
+        &self,
+        key: impl AsRef<str>,
+        dest_bucket: impl AsRef<str>,
+        dest_key: impl AsRef<str>,
+        force: bool,
+    ) -> Result<()> {
+        self.rs_call(&path::move_to(
+            self.name(),
+            key.as_ref(),
+            dest_bucket.as_ref(),
+            dest_key.as_ref(),
+            force,
+        ))
+    }
+
+    /// 修改对象 MIME 类型
+    pub fn change_mime_type(&self, key: impl AsRef<str>, mime_type: impl AsRef<str>) -> Result<()> {
+        self.rs_call(&path::change_mime_type(self.name(), key.as_ref(), mime_type.as_ref()))
+    }
+
+    /// 修改对象 MIME 类型和自定义元数据
+    ///
+    /// `mime_type` 为 `None` 时不修改 MIME 类型。
+    /// 元数据名称可以省略 `x-qn-meta-` 前缀，下载对象时将作为 `x-qn-meta-*` 响应头返回
+    ///
+    /// ```rust,no_run
+    /// use qiniu_ng::{Client, Config};
+    /// # use std::{result::Result, error::Error};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+    /// let bucket = client.storage().bucket("[Bucket name]").build();
+    /// bucket.change_metadata("[Key]", Some("text/plain"), vec![("author", "qiniu")])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn change_metadata<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        key: impl AsRef<str>,
+        mime_type: Option<&str>,
+        metadata: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<()> {
+        let metadata = metadata.into_iter().collect::<Vec<_>>();
+        let metadata = metadata
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_ref()))
+            .collect::<Vec<_>>();
+        self.rs_call(&path::change_metadata(self.name(), key.as_ref(), mime_type, &metadata))
+    }
+
+    /// 修改对象存储类型
+    pub fn change_file_type(&self, key: impl AsRef<str>, file_type: FileType) -> Result<()> {
+        self.rs_call(&path::change_file_type(self.name(), key.as_ref(), file_type))
+    }
+
+    /// 启用对象
+    pub fn enable(&self, key: impl AsRef<str>) -> Result<()> {
+        self.rs_call(&path::change_status(self.name(), key.as_ref(), false))
+    }
+
+    /// 禁用对象
+    ///
+    /// 禁用后的对象将无法被下载
+    pub fn disable(&self, key: impl AsRef<str>) -> Result<()> {
+        self.rs_call(&path::change_status(self.name(), key.as_ref(), true))
+    }
+
+    /// 设置对象在多少天后被删除
+    ///
+    /// `days` 为 0 表示取消对象的过期删除
+    pub fn delete_after_days(&self, key: impl AsRef<str>, days: u32) -> Result<()> {
+        self.rs_call(&path::delete_after_days(self.name(), key.as_ref(), days))
+    }
+
+    /// 解冻归档存储对象
+    ///
+    /// 解冻后的对象将在 `freeze_after_days` 天后重新冻结，取值范围为 1 到 7
+    pub fn restore_archive(&self, key: impl AsRef<str>, freeze_after_days: u32) -> Result<()> {
+        self.rs_call(&path::restore_archive(self.name(), key.as_ref(), freeze_after_days))
+    }
+
+    /// 镜像回源预取
+    ///
+    /// 对于设置了镜像源的存储空间，从镜像源拉取指定对象的最新内容并覆盖存储空间中的对象。
+    /// 该方法将使用存储空间所在区域的 IO 服务器
+    pub fn prefetch(&self, key: impl AsRef<str>) -> Result<()> {
+        let path = path::prefetch(self.name(), key.as_ref());
+        let io_urls = self.io_urls()?;
+        let io_urls = io_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.http_client
+            .post(&path, &io_urls)
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 同步抓取
+    ///
+    /// 从指定 URL 抓取资源并存储为当前存储空间中的 `key` 对象，抓取完毕后才会返回。
+    /// 该方法将使用存储空间所在区域的 IO 服务器，适合抓取较小的资源，对于较大的资源，建议使用 `async_fetch`
+    pub fn fetch(&self, url: impl AsRef<str>, key: impl AsRef<str>) -> Result<FetchResult> {
+        let path = "/fetch/".to_owned()
+            + &base64::urlsafe(url.as_ref().as_bytes())
+            + "/to/"
+            + &path::encode_entry(self.name(), key.as_ref());
+        let io_urls = self.io_urls()?;
+        let io_urls = io_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.http_client
+            .post(&path, &io_urls)
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()
+    }
+
+    /// 创建异步抓取任务
+    ///
+    /// 异步抓取任务将在七牛服务器上排队执行，可以通过 `async_fetch_status` 查询任务状态
+    pub fn async_fetch<'a>(&'a self, url: impl Into<Cow<'a, str>>) -> AsyncFetchBuilder<'a, 'r> {
+        AsyncFetchBuilder::new(self, url.into())
+    }
+
+    /// 查询异步抓取任务状态
+    pub fn async_fetch_status(&self, id: impl AsRef<str>) -> Result<AsyncFetchJob> {
+        let api_urls = self.api_urls();
+        let api_urls = api_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.http_client
+            .get("/sisyphus/fetch", &api_urls)
+            .query("id", id.as_ref())
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()
+    }
+
+    /// 获取批量操作生成器
+    ///
+    /// 批量操作将针对当前存储空间中的对象
+    pub fn batch(&self) -> Batch<'_> {
+        Batch::new(
+            Cow::Borrowed(self.name()),
+            Cow::Borrowed(self.credential.as_ref()),
+            self.rs_urls(),
+            self.http_client.to_owned(),
+        )
+    }
+
+    /// 获取对象列举生成器
+    pub fn list(&self) -> ListBuilder<'_> {
+        ListBuilder::new(
+            Cow::Borrowed(self.name()),
+            Cow::Borrowed(self.credential.as_ref()),
+            self.rsf_urls(),
+            self.http_client.to_owned(),
+        )
+    }
+
+    /// 获取下载地址生成器
+    pub fn download_url<'a>(&'a self, key: impl Into<Cow<'a, str>>) -> DownloadUrlBuilder<'a, 'r> {
+        DownloadUrlBuilder::new(self, key.into())
+    }
+
+    /// 获取下载器
+    ///
+    /// 如果需要指定下载域名或数据处理指令，可以通过 `download_url` 获取下载地址生成器后调用 `DownloadUrlBuilder::downloader`
+    pub fn downloader<'a>(&'a self, key: impl Into<Cow<'a, str>>) -> Downloader<'a, 'r> {
+        self.download_url(key).downloader()
+    }
+
+    /// 对指定对象发起持久化数据处理
+    ///
+    /// 返回持久化处理 ID，可以通过 `prefop` 方法查询处理状态
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - 需要处理的对象名称
+    /// * `fops` - 数据处理指令列表，多个指令将并行处理，可以使用 `processing::Pipeline` 生成
+    /// * `pipeline` - 私有队列名称，如果不指定，将使用公共队列
+    /// * `notify_url` - 处理完成后七牛服务器将向该地址发送处理结果
+    /// * `force` - 如果处理结果对象已经存在，是否强制覆盖
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use qiniu_ng::{Client, Config};
+    /// # use std::{result::Result, error::Error};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+    /// let bucket = client.storage().bucket("[Bucket name]").build();
+    /// let persistent_id = bucket.pfop("[Key]", &["avthumb/mp4"], None, None, false)?;
+    /// let status = bucket.prefop(&persistent_id)?;
+    /// println!("code: {:?}, desc: {}", status.code(), status.desc());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pfop(
+        &self,
+        key: impl AsRef<str>,
+        fops: &[impl AsRef<str>],
+        pipeline: Option<&str>,
+        notify_url: Option<&str>,
+        force: bool,
+    ) -> Result<String> {
+        let fops = fops.iter().map(|fop| fop.as_ref()).collect::<Vec<_>>().join(";");
+        let mut params = vec![("bucket", self.name()), ("key", key.as_ref()), ("fops", &fops)];
+        if let Some(pipeline) = pipeline {
+            params.push(("pipeline", pipeline));
+        }
+        if let Some(notify_url) = notify_url {
+            params.push(("notifyURL", notify_url));
+        }
+        if force {
+            params.push(("force", "1"));
+        }
+        let api_urls = self.api_urls();
+        let api_urls = api_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        let result: PfopResult = self
+            .http_client
+            .post("/pfop/", &api_urls)
+            .token(TokenVersion::V1, self.credential.as_ref().into())
+            .accept_json()
+            .raw_body(
+                "application/x-www-form-urlencoded",
+                serde_urlencoded::to_string(params).unwrap(),
+            )
+            .send()?
+            .parse_json()?;
+        Ok(result.persistent_id)
+    }
+
+    /// 查询持久化数据处理状态
+    pub fn prefop(&self, persistent_id: impl AsRef<str>) -> Result<PersistentStatus> {
+        let api_urls = self.api_urls();
+        let api_urls = api_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.http_client
+            .get("/status/get/prefop", &api_urls)
+            .query("id", persistent_id.as_ref())
+            .token(TokenVersion::V1, self.credential.as_ref().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()
+    }
+
+    pub(crate) fn credential(&self) -> &Credential {
+        self.credential.as_ref()
+    }
+
+    pub(crate) fn config(&self) -> &Config {
+        self.upload_manager.config()
+    }
+
+    pub(crate) fn http_client(&self) -> &Client {
+        &self.http_client
+    }
+
+    fn rs_call(&self, path: &str) -> Result<()> {
+        let rs_urls = self.rs_urls();
+        let rs_urls = rs_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.http_client
+            .post(path, &rs_urls)
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    fn io_urls(&self) -> Result<Vec<Cow<'static, str>>> {
+        Ok(self.region()?.io_urls_owned(self.upload_manager.config().use_https()))
+    }
+
+    pub(super) fn rs_urls(&self) -> Vec<Cow<'static, str>> {
         let mut rs_urls = self
             .region()
             .map(|region| region.rs_urls_owned(self.upload_manager.config().use_https()))
@@ -289,6 +635,24 @@ impl<'r> Bucket<'r> {
         rs_urls
     }
 
+    fn rsf_urls(&self) -> Vec<Cow<'static, str>> {
+        let mut rsf_urls = self
+            .region()
+            .map(|region| region.rsf_urls_owned(self.upload_manager.config().use_https()))
+            .unwrap_or_else(|_| Vec::new());
+        rsf_urls.push(Cow::Owned(self.upload_manager.config().rsf_url()));
+        rsf_urls
+    }
+
+    pub(super) fn api_urls(&self) -> Vec<Cow<'static, str>> {
+        let mut api_urls = self
+            .region()
+            .map(|region| region.api_urls_owned(self.upload_manager.config().use_https()))
+            .unwrap_or_else(|_| Vec::new());
+        api_urls.push(Cow::Owned(self.upload_manager.config().api_url()));
+        api_urls
+    }
+
     #[allow(dead_code)]
     fn ignore() {
         assert_impl!(Send: Self);
@@ -340,17 +704,24 @@ impl<'a, 'r: 'a> Iterator for BucketRegionIter<'a, 'r> {
 #[cfg(test)]
 mod tests {
     use super::{
-        super::{region::RegionId, uploader::UploadManager},
+        super::{
+            processing::{Avthumb, PersistentStatusCode, Pipeline, Vframe},
+            region::RegionId,
+            uploader::UploadManager,
+        },
         *,
     };
     use crate::{
         config::ConfigBuilder,
         credential::Credential,
-        http::{DomainsManagerBuilder, Headers, PanickedHTTPCaller},
+        http::{DomainsManagerBuilder, ErrorKind as HTTPErrorKind, Headers, Method, PanickedHTTPCaller},
+    };
+    use qiniu_http::ResponseBuilder;
+    use qiniu_test_utils::http_call_mock::{
+        fake_req_id, json_response, CallHandlers, CounterCallMock, ErrorResponseMock, JSONCallMock,
     };
-    use qiniu_test_utils::http_call_mock::{CounterCallMock, JSONCallMock};
     use serde_json::json;
-    use std::{boxed::Box, error::Error, result::Result, sync::Arc, thread};
+    use std::{boxed::Box, error::Error, result::Result, sync::Arc, thread, time::Duration};
 
     #[test]
     fn test_storage_bucket_set_region() -> Result<(), Box<dyn Error>> {
@@ -654,6 +1025,379 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_storage_bucket_stat() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                "/stat/".to_owned() + &regex::escape(&path::encode_entry("test-bucket", "test-key")) + "$",
+                |_, _| {
+                    let mut headers = Headers::new();
+                    headers.insert("Content-Type".into(), "application/json".into());
+                    headers.insert("X-Reqid".into(), fake_req_id().into());
+                    Ok(ResponseBuilder::default()
+                        .status_code(200u16)
+                        .headers(headers)
+                        .bytes_as_body(
+                            json!({
+                                "fsize": 1024,
+                                "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk",
+                                "mimeType": "text/plain",
+                                "putTime": 15_735_614_000_000_000u64,
+                                "type": 0,
+                            })
+                            .to_string(),
+                        )
+                        .build())
+                },
+            ),
+        );
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        let info = bucket.stat("test-key")?;
+        assert_eq!(info.size(), 1024);
+        assert_eq!(info.hash(), "FhOPGUW8AaIdsdUGjnj-8jDTYqtk");
+        assert_eq!(info.mime_type(), "text/plain");
+        assert_eq!(info.md5(), None);
+        assert_eq!(mock.call_called(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_delete_non_existed_object() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(ErrorResponseMock::new(612, "no such file or directory"));
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        match bucket.delete("test-key").unwrap_err().error_kind() {
+            HTTPErrorKind::ResponseStatusCodeError(612, message) => {
+                assert_eq!(message.as_ref(), "no such file or directory");
+            }
+            err => panic!("Unexpected error: {:?}", err),
+        }
+        assert_eq!(mock.call_called(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_copy_and_move() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                "/copy/".to_owned()
+                    + &regex::escape(&path::encode_entry("test-bucket", "test-key"))
+                    + "/"
+                    + &regex::escape(&path::encode_entry("test-bucket-2", "test-key-2"))
+                    + "/force/true$",
+                |_, _| Ok(ResponseBuilder::default().status_code(200u16).build()),
+            )
+            .install(
+                Method::POST,
+                "/move/".to_owned()
+                    + &regex::escape(&path::encode_entry("test-bucket", "test-key"))
+                    + "/"
+                    + &regex::escape(&path::encode_entry("test-bucket-2", "test-key-2"))
+                    + "$",
+                |_, _| Ok(ResponseBuilder::default().status_code(200u16).build()),
+            ),
+        );
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        bucket.copy_to("test-key", "test-bucket-2", "test-key-2", true)?;
+        bucket.move_to("test-key", "test-bucket-2", "test-key-2", false)?;
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_prefetch() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                "^https://iovip\\.qbox\\.me/prefetch/".to_owned()
+                    + &regex::escape(&path::encode_entry("test-bucket", "test-key"))
+                    + "$",
+                |_, _| Ok(ResponseBuilder::default().status_code(200u16).build()),
+            ),
+        );
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        bucket.prefetch("test-key")?;
+        assert_eq!(mock.call_called(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_change_object() -> Result<(), Box<dyn Error>> {
+        let expected_paths = [
+            path::change_metadata("test-bucket", "test-key", Some("text/plain"), &[("author", "qiniu")]),
+            path::change_file_type("test-bucket", "test-key", FileType::Archive),
+            path::restore_archive("test-bucket", "test-key", 3),
+            path::change_status("test-bucket", "test-key", true),
+            path::change_status("test-bucket", "test-key", false),
+            path::delete_after_days("test-bucket", "test-key", 7),
+        ];
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, "^https://rs\\.qbox\\.me/", move |request, called| {
+                assert_eq!(
+                    request.url(),
+                    "https://rs.qbox.me".to_owned() + &expected_paths[called - 1]
+                );
+                Ok(ResponseBuilder::default().status_code(200u16).build())
+            }),
+        );
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        bucket.change_metadata("test-key", Some("text/plain"), vec![("author", "qiniu")])?;
+        bucket.change_file_type("test-key", FileType::Archive)?;
+        bucket.restore_archive("test-key", 3)?;
+        bucket.disable("test-key")?;
+        bucket.enable("test-key")?;
+        bucket.delete_after_days("test-key", 7)?;
+        assert_eq!(mock.call_called(), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_fetch() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                "^https://iovip\\.qbox\\.me/fetch/".to_owned()
+                    + &regex::escape(&base64::urlsafe(b"https://www.example.com/1.png"))
+                    + "/to/"
+                    + &regex::escape(&path::encode_entry("test-bucket", "test-key"))
+                    + "$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!({
+                            "key": "test-key",
+                            "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk",
+                            "mimeType": "image/png",
+                            "fsize": 1024,
+                        }),
+                    ))
+                },
+            )
+            .install(
+                Method::POST,
+                r"^https://api\.qiniu\.com/sisyphus/fetch$",
+                |request, _| {
+                    let body: serde_json::Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                    assert_eq!(
+                        body,
+                        json!({
+                            "url": "https://www.example.com/2.mp4",
+                            "bucket": "test-bucket",
+                            "key": "test-key-2",
+                            "md5": "0cc175b9c0f1b6a831c399e269772661",
+                            "callbackurl": "https://www.example.com/callback",
+                            "callbackbody": "key=$(key)",
+                            "file_type": 1,
+                            "ignore_same_key": true,
+                        })
+                    );
+                    Ok(json_response(200, json!({"id": "test-job-id", "wait": 3})))
+                },
+            )
+            .install(
+                Method::GET,
+                r"^https://api\.qiniu\.com/sisyphus/fetch\?id=test-job-id$",
+                |_, _| Ok(json_response(200, json!({"id": "test-job-id", "wait": -1}))),
+            ),
+        );
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        let result = bucket.fetch("https://www.example.com/1.png", "test-key")?;
+        assert_eq!(result.key(), "test-key");
+        assert_eq!(result.mime_type(), "image/png");
+        assert_eq!(result.size(), 1024);
+
+        let job = bucket
+            .async_fetch("https://www.example.com/2.mp4")
+            .key("test-key-2")
+            .md5("0cc175b9c0f1b6a831c399e269772661")
+            .callback_url("https://www.example.com/callback")
+            .callback_body("key=$(key)")
+            .file_type(FileType::InfrequentAccess)
+            .ignore_same_key()
+            .send()?;
+        assert_eq!(job.id(), "test-job-id");
+        assert_eq!(job.queued_tasks(), Some(3));
+        assert!(!job.is_processed());
+
+        let status = bucket.async_fetch_status(job.id())?;
+        assert_eq!(status.queued_tasks(), None);
+        assert!(status.is_processed());
+        assert_eq!(mock.call_called(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_pfop_and_prefop() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, r"^https://api\.qiniu\.com/pfop/$", |request, _| {
+                assert!(request
+                    .headers()
+                    .get(&"Authorization".into())
+                    .unwrap()
+                    .starts_with("QBox abcdefghklmnopq:"));
+                let body = String::from_utf8(request.body().as_ref().unwrap().to_vec()).unwrap();
+                let params = serde_urlencoded::from_str::<Vec<(String, String)>>(&body).unwrap();
+                assert_eq!(
+                    params,
+                    vec![
+                        ("bucket".to_owned(), "test-bucket".to_owned()),
+                        ("key".to_owned(), "test-key.mp4".to_owned()),
+                        ("fops".to_owned(), "avthumb/mp4;vframe/jpg/offset/7".to_owned()),
+                        ("pipeline".to_owned(), "test-pipeline".to_owned()),
+                        ("force".to_owned(), "1".to_owned()),
+                    ]
+                );
+                let mut headers = Headers::new();
+                headers.insert("Content-Type".into(), "application/json".into());
+                headers.insert("X-Reqid".into(), fake_req_id().into());
+                Ok(ResponseBuilder::default()
+                    .status_code(200u16)
+                    .headers(headers)
+                    .bytes_as_body(json!({"persistentId": "z0.5e0aee6e3d2ab7e0ed8d4d3d"}).to_string())
+                    .build())
+            })
+            .install(
+                Method::GET,
+                r"^https://api\.qiniu\.com/status/get/prefop\?id=z0\.5e0aee6e3d2ab7e0ed8d4d3d$",
+                |_, _| {
+                    let mut headers = Headers::new();
+                    headers.insert("Content-Type".into(), "application/json".into());
+                    headers.insert("X-Reqid".into(), fake_req_id().into());
+                    Ok(ResponseBuilder::default()
+                        .status_code(200u16)
+                        .headers(headers)
+                        .bytes_as_body(
+                            json!({
+                                "code": 2,
+                                "desc": "The fop is processing now",
+                                "id": "z0.5e0aee6e3d2ab7e0ed8d4d3d",
+                                "inputBucket": "test-bucket",
+                                "inputKey": "test-key.mp4",
+                                "pipeline": "test-pipeline",
+                                "items": [{"cmd": "avthumb/mp4", "code": 2, "desc": "The fop is processing now"}],
+                            })
+                            .to_string(),
+                        )
+                        .build())
+                },
+            ),
+        );
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(mock.clone())
+                    .build(),
+            ),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        let persistent_id = bucket.pfop(
+            "test-key.mp4",
+            &[
+                Pipeline::from(Avthumb::new("mp4")),
+                Pipeline::from(Vframe::new("jpg", Duration::from_secs(7))),
+            ],
+            Some("test-pipeline"),
+            None,
+            true,
+        )?;
+        assert_eq!(persistent_id, "z0.5e0aee6e3d2ab7e0ed8d4d3d");
+        let status = bucket.prefop(&persistent_id)?;
+        assert_eq!(status.code(), PersistentStatusCode::Processing);
+        assert!(!status.code().is_finished());
+        assert_eq!(status.items()[0].cmd(), "avthumb/mp4");
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+
     fn get_credential() -> Credential {
         Credential::new("abcdefghklmnopq", "1234567890")
     }
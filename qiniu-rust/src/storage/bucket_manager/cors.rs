@@ -0,0 +1,113 @@
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// 跨域规则
+///
+/// 用于指定浏览器跨域访问存储空间中的文件时允许的来源，方法和头信息。
+/// 通过 `CorsRuleBuilder` 创建，或通过 `BucketManager::cors_rules` 获取
+///
+/// ```rust
+/// use qiniu_ng::storage::bucket_manager::CorsRuleBuilder;
+/// use std::time::Duration;
+///
+/// let rule = CorsRuleBuilder::new(&["https://www.example.com"], &["GET", "HEAD"])
+///     .allowed_headers(&["Range"])
+///     .exposed_headers(&["ETag"])
+///     .max_age(Duration::from_secs(600))
+///     .build();
+/// assert_eq!(rule.max_age(), Some(Duration::from_secs(600)));
+/// ```
+#[derive(Serialize, Deserialize, Getters, Debug, Clone, Eq, PartialEq)]
+pub struct CorsRule {
+    /// 允许的来源列表，可以使用 `*` 作为通配符
+    #[serde(rename = "allowed_origin", default)]
+    #[get = "pub"]
+    allowed_origins: Vec<String>,
+
+    /// 允许的 HTTP 方法列表
+    #[serde(rename = "allowed_method", default)]
+    #[get = "pub"]
+    allowed_methods: Vec<String>,
+
+    /// 允许的请求头列表
+    #[serde(rename = "allowed_header", default, skip_serializing_if = "Vec::is_empty")]
+    #[get = "pub"]
+    allowed_headers: Vec<String>,
+
+    /// 允许浏览器访问的响应头列表
+    #[serde(rename = "exposed_header", default, skip_serializing_if = "Vec::is_empty")]
+    #[get = "pub"]
+    exposed_headers: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "is_zero")]
+    max_age: u64,
+}
+
+fn is_zero(max_age: &u64) -> bool {
+    *max_age == 0
+}
+
+impl CorsRule {
+    /// 浏览器缓存预检请求结果的时间
+    ///
+    /// 如果返回 `None`，表示未指定
+    pub fn max_age(&self) -> Option<Duration> {
+        if self.max_age > 0 {
+            Some(Duration::from_secs(self.max_age))
+        } else {
+            None
+        }
+    }
+}
+
+/// 跨域规则生成器
+pub struct CorsRuleBuilder {
+    inner: CorsRule,
+}
+
+impl CorsRuleBuilder {
+    /// 创建跨域规则生成器
+    ///
+    /// 需要指定允许的来源列表和 HTTP 方法列表
+    pub fn new(allowed_origins: &[impl AsRef<str>], allowed_methods: &[impl AsRef<str>]) -> CorsRuleBuilder {
+        CorsRuleBuilder {
+            inner: CorsRule {
+                allowed_origins: to_strings(allowed_origins),
+                allowed_methods: to_strings(allowed_methods),
+                allowed_headers: Vec::new(),
+                exposed_headers: Vec::new(),
+                max_age: 0,
+            },
+        }
+    }
+
+    /// 指定允许的请求头列表
+    pub fn allowed_headers(mut self, allowed_headers: &[impl AsRef<str>]) -> Self {
+        self.inner.allowed_headers = to_strings(allowed_headers);
+        self
+    }
+
+    /// 指定允许浏览器访问的响应头列表
+    pub fn exposed_headers(mut self, exposed_headers: &[impl AsRef<str>]) -> Self {
+        self.inner.exposed_headers = to_strings(exposed_headers);
+        self
+    }
+
+    /// 指定浏览器缓存预检请求结果的时间
+    ///
+    /// 时间精确到秒
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.inner.max_age = max_age.as_secs();
+        self
+    }
+
+    /// 生成跨域规则
+    pub fn build(self) -> CorsRule {
+        self.inner
+    }
+}
+
+fn to_strings(values: &[impl AsRef<str>]) -> Vec<String> {
+    values.iter().map(|value| value.as_ref().to_owned()).collect()
+}
@@ -0,0 +1,184 @@
+use getset::Getters;
+use serde::Deserialize;
+use std::borrow::Cow;
+
+/// 事件类型
+#[derive(Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum EventType {
+    /// 简单上传
+    Put,
+    /// 分片上传完成
+    Mkfile,
+    /// 删除文件
+    Delete,
+    /// 复制文件
+    Copy,
+    /// 移动文件
+    Move,
+    /// 追加上传
+    Append,
+    /// 禁用文件
+    Disable,
+    /// 启用文件
+    Enable,
+    /// 删除标记创建
+    DeleteMarkerCreate,
+}
+
+impl EventType {
+    /// 事件类型名称
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventType::Put => "put",
+            EventType::Mkfile => "mkfile",
+            EventType::Delete => "delete",
+            EventType::Copy => "copy",
+            EventType::Move => "move",
+            EventType::Append => "append",
+            EventType::Disable => "disable",
+            EventType::Enable => "enable",
+            EventType::DeleteMarkerCreate => "deleteMarkerCreate",
+        }
+    }
+}
+
+/// 事件通知规则
+///
+/// 用于指定存储空间中匹配前缀和后缀的文件发生指定事件时，七牛将回调通知指定的回调地址。
+/// 通过 `EventRuleBuilder` 创建，或通过 `BucketManager::event_rules` 获取
+///
+/// ```rust
+/// use qiniu_ng::storage::bucket_manager::{EventRuleBuilder, EventType};
+///
+/// let rule = EventRuleBuilder::new("images", &[EventType::Put, EventType::Delete], &["https://www.example.com/callback"])
+///     .prefix("images/")
+///     .suffix(".jpg")
+///     .build();
+/// assert_eq!(rule.events(), &[EventType::Put, EventType::Delete]);
+/// ```
+#[derive(Deserialize, Getters, Debug, Clone, Eq, PartialEq)]
+pub struct EventRule {
+    /// 规则名称
+    #[get = "pub"]
+    name: String,
+
+    /// 规则匹配的文件名称前缀，为空表示匹配所有文件
+    #[serde(default)]
+    #[get = "pub"]
+    prefix: String,
+
+    /// 规则匹配的文件名称后缀，为空表示匹配所有文件
+    #[serde(default)]
+    #[get = "pub"]
+    suffix: String,
+
+    /// 触发通知的事件类型
+    #[serde(default, rename = "event")]
+    #[get = "pub"]
+    events: Vec<EventType>,
+
+    /// 回调地址列表
+    #[serde(default)]
+    #[get = "pub"]
+    callback_urls: Vec<String>,
+
+    #[serde(default)]
+    access_key: Option<String>,
+
+    #[serde(default)]
+    host: Option<String>,
+}
+
+impl EventRule {
+    /// 用于签名回调请求的 Access Key
+    pub fn access_key(&self) -> Option<&str> {
+        self.access_key.as_ref().map(String::as_str)
+    }
+
+    /// 回调请求的 Host 头
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_ref().map(String::as_str)
+    }
+
+    pub(super) fn to_params<'a>(&'a self, bucket: &'a str) -> Vec<(&'static str, Cow<'a, str>)> {
+        let mut params = vec![
+            ("bucket", Cow::Borrowed(bucket)),
+            ("name", Cow::Borrowed(self.name.as_str())),
+            ("prefix", Cow::Borrowed(self.prefix.as_str())),
+            ("suffix", Cow::Borrowed(self.suffix.as_str())),
+        ];
+        params.extend(self.events.iter().map(|event| ("event", Cow::Borrowed(event.as_str()))));
+        params.extend(
+            self.callback_urls
+                .iter()
+                .map(|url| ("callbackURL", Cow::Borrowed(url.as_str()))),
+        );
+        if let Some(access_key) = &self.access_key {
+            params.push(("access_key", Cow::Borrowed(access_key)));
+        }
+        if let Some(host) = &self.host {
+            params.push(("host", Cow::Borrowed(host)));
+        }
+        params
+    }
+}
+
+/// 事件通知规则生成器
+pub struct EventRuleBuilder {
+    inner: EventRule,
+}
+
+impl EventRuleBuilder {
+    /// 创建事件通知规则生成器
+    ///
+    /// 规则名称在同一个存储空间中必须唯一，至少需要指定一个事件类型和一个回调地址
+    pub fn new(name: impl Into<String>, events: &[EventType], callback_urls: &[impl AsRef<str>]) -> EventRuleBuilder {
+        EventRuleBuilder {
+            inner: EventRule {
+                name: name.into(),
+                prefix: String::new(),
+                suffix: String::new(),
+                events: events.to_vec(),
+                callback_urls: callback_urls.iter().map(|url| url.as_ref().to_owned()).collect(),
+                access_key: None,
+                host: None,
+            },
+        }
+    }
+
+    /// 指定规则匹配的文件名称前缀
+    ///
+    /// 默认匹配所有文件
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.inner.prefix = prefix.into();
+        self
+    }
+
+    /// 指定规则匹配的文件名称后缀
+    ///
+    /// 默认匹配所有文件
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.inner.suffix = suffix.into();
+        self
+    }
+
+    /// 指定用于签名回调请求的 Access Key
+    ///
+    /// 七牛将使用该 Access Key 对应的 Secret Key 对回调请求签名
+    pub fn access_key(mut self, access_key: impl Into<String>) -> Self {
+        self.inner.access_key = Some(access_key.into());
+        self
+    }
+
+    /// 指定回调请求的 Host 头
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.inner.host = Some(host.into());
+        self
+    }
+
+    /// 生成事件通知规则
+    pub fn build(self) -> EventRule {
+        self.inner
+    }
+}
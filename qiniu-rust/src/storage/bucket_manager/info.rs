@@ -0,0 +1,103 @@
+use getset::Getters;
+use serde::Deserialize;
+use std::{convert::TryFrom, time::Duration};
+
+/// 存储空间信息
+///
+/// 通过 `BucketManager::info` 获取
+#[derive(Deserialize, Getters, Debug, Clone)]
+pub struct BucketInfo {
+    /// 存储空间所在区域 ID
+    #[serde(default)]
+    #[get = "pub"]
+    region: String,
+
+    #[serde(default)]
+    private: u8,
+
+    #[serde(default)]
+    max_age: u64,
+
+    #[serde(default)]
+    source: String,
+
+    #[serde(default)]
+    host: String,
+
+    /// 存储空间的下载域名列表
+    #[serde(skip)]
+    #[get = "pub"]
+    pub(super) domains: Vec<String>,
+}
+
+impl BucketInfo {
+    /// 是否是私有空间
+    pub fn is_private(&self) -> bool {
+        self.private != 0
+    }
+
+    /// 存储空间中文件被访问时返回的 `Cache-Control: max-age` 缓存时间
+    ///
+    /// 如果返回 `None`，表示使用七牛默认值
+    pub fn max_age(&self) -> Option<Duration> {
+        if self.max_age > 0 {
+            Some(Duration::from_secs(self.max_age))
+        } else {
+            None
+        }
+    }
+
+    /// 存储空间的镜像源
+    ///
+    /// 如果返回 `None`，表示存储空间没有设置镜像源
+    pub fn mirror_source(&self) -> Option<&str> {
+        non_empty(&self.source)
+    }
+
+    /// 镜像回源请求的 Host 头
+    ///
+    /// 如果返回 `None`，表示使用镜像源的域名
+    pub fn mirror_host(&self) -> Option<&str> {
+        non_empty(&self.host)
+    }
+}
+
+fn non_empty(s: &str) -> Option<&str> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+/// 存储空间配额
+///
+/// 通过 `BucketManager::quota` 获取
+#[derive(Deserialize, Debug, Copy, Clone)]
+pub struct BucketQuota {
+    #[serde(default = "unlimited")]
+    size: i64,
+
+    #[serde(default = "unlimited")]
+    count: i64,
+}
+
+fn unlimited() -> i64 {
+    -1
+}
+
+impl BucketQuota {
+    /// 存储空间最大存储量，单位为字节
+    ///
+    /// 如果返回 `None`，表示不限制
+    pub fn size(&self) -> Option<u64> {
+        u64::try_from(self.size).ok()
+    }
+
+    /// 存储空间最大文件数量
+    ///
+    /// 如果返回 `None`，表示不限制
+    pub fn count(&self) -> Option<u64> {
+        u64::try_from(self.count).ok()
+    }
+}
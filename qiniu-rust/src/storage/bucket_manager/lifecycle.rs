@@ -0,0 +1,142 @@
+use getset::Getters;
+use serde::Deserialize;
+use std::borrow::Cow;
+
+/// 生命周期规则
+///
+/// 用于指定存储空间中匹配前缀的文件在上传若干天后转为低频存储，归档存储或是被删除。
+/// 通过 `LifecycleRuleBuilder` 创建，或通过 `BucketManager::lifecycle_rules` 获取
+///
+/// ```rust
+/// use qiniu_ng::storage::bucket_manager::LifecycleRuleBuilder;
+///
+/// let rule = LifecycleRuleBuilder::new("logs")
+///     .prefix("logs/")
+///     .to_infrequent_after_days(30)
+///     .to_archive_after_days(90)
+///     .delete_after_days(365)
+///     .build();
+/// assert_eq!(rule.name(), "logs");
+/// assert_eq!(rule.delete_after_days(), Some(365));
+/// ```
+#[derive(Deserialize, Getters, Debug, Clone, Eq, PartialEq)]
+pub struct LifecycleRule {
+    /// 规则名称
+    #[get = "pub"]
+    name: String,
+
+    /// 规则匹配的文件名称前缀，为空表示匹配所有文件
+    #[serde(default)]
+    #[get = "pub"]
+    prefix: String,
+
+    #[serde(default)]
+    delete_after_days: u32,
+
+    #[serde(default, rename = "to_line_after_days")]
+    to_infrequent_after_days: u32,
+
+    #[serde(default)]
+    to_archive_after_days: u32,
+}
+
+impl LifecycleRule {
+    /// 文件上传多少天后被删除
+    ///
+    /// 如果返回 `None`，表示不删除
+    pub fn delete_after_days(&self) -> Option<u32> {
+        non_zero(self.delete_after_days)
+    }
+
+    /// 文件上传多少天后转为低频存储
+    ///
+    /// 如果返回 `None`，表示不转为低频存储
+    pub fn to_infrequent_after_days(&self) -> Option<u32> {
+        non_zero(self.to_infrequent_after_days)
+    }
+
+    /// 文件上传多少天后转为归档存储
+    ///
+    /// 如果返回 `None`，表示不转为归档存储
+    pub fn to_archive_after_days(&self) -> Option<u32> {
+        non_zero(self.to_archive_after_days)
+    }
+
+    pub(super) fn to_params<'a>(&'a self, bucket: &'a str) -> Vec<(&'static str, Cow<'a, str>)> {
+        vec![
+            ("bucket", Cow::Borrowed(bucket)),
+            ("name", Cow::Borrowed(&self.name)),
+            ("prefix", Cow::Borrowed(&self.prefix)),
+            ("delete_after_days", Cow::Owned(self.delete_after_days.to_string())),
+            (
+                "to_line_after_days",
+                Cow::Owned(self.to_infrequent_after_days.to_string()),
+            ),
+            (
+                "to_archive_after_days",
+                Cow::Owned(self.to_archive_after_days.to_string()),
+            ),
+        ]
+    }
+}
+
+fn non_zero(days: u32) -> Option<u32> {
+    if days > 0 {
+        Some(days)
+    } else {
+        None
+    }
+}
+
+/// 生命周期规则生成器
+pub struct LifecycleRuleBuilder {
+    inner: LifecycleRule,
+}
+
+impl LifecycleRuleBuilder {
+    /// 创建生命周期规则生成器
+    ///
+    /// 规则名称在同一个存储空间中必须唯一
+    pub fn new(name: impl Into<String>) -> LifecycleRuleBuilder {
+        LifecycleRuleBuilder {
+            inner: LifecycleRule {
+                name: name.into(),
+                prefix: String::new(),
+                delete_after_days: 0,
+                to_infrequent_after_days: 0,
+                to_archive_after_days: 0,
+            },
+        }
+    }
+
+    /// 指定规则匹配的文件名称前缀
+    ///
+    /// 默认匹配所有文件
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.inner.prefix = prefix.into();
+        self
+    }
+
+    /// 指定文件上传多少天后被删除
+    pub fn delete_after_days(mut self, days: u32) -> Self {
+        self.inner.delete_after_days = days;
+        self
+    }
+
+    /// 指定文件上传多少天后转为低频存储
+    pub fn to_infrequent_after_days(mut self, days: u32) -> Self {
+        self.inner.to_infrequent_after_days = days;
+        self
+    }
+
+    /// 指定文件上传多少天后转为归档存储
+    pub fn to_archive_after_days(mut self, days: u32) -> Self {
+        self.inner.to_archive_after_days = days;
+        self
+    }
+
+    /// 生成生命周期规则
+    pub fn build(self) -> LifecycleRule {
+        self.inner
+    }
+}
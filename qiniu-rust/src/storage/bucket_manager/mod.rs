@@ -0,0 +1,747 @@
+//! 存储空间管理模块
+//!
+//! 封装存储空间的配置管理功能，例如访问权限，配额，缓存时间，标签，生命周期规则，事件通知规则，跨域规则，防盗链等
+
+mod cors;
+mod event;
+mod info;
+mod lifecycle;
+mod referer;
+
+pub use cors::{CorsRule, CorsRuleBuilder};
+pub use event::{EventRule, EventRuleBuilder, EventType};
+pub use info::{BucketInfo, BucketQuota};
+pub use lifecycle::{LifecycleRule, LifecycleRuleBuilder};
+pub use referer::{RefererAntiLeech, RefererAntiLeechBuilder, RefererAntiLeechMode};
+
+use referer::RefererAntiLeechInfo;
+
+use super::manager::StorageManager;
+use crate::http::{Result as HTTPResult, TokenVersion};
+use assert_impl::assert_impl;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    borrow::{Borrow, Cow},
+    collections::HashMap,
+    time::Duration,
+};
+
+/// 存储空间管理器
+///
+/// 通过 `StorageManager::bucket_manager` 获取，用于读取和修改七牛服务器上存储空间的配置
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// use std::time::Duration;
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let bucket_manager = client.storage().bucket_manager("[Bucket name]");
+/// bucket_manager.set_private(true)?;
+/// bucket_manager.set_max_age(Duration::from_secs(86400))?;
+/// bucket_manager.set_tags(vec![("env", "production"), ("team", "media")])?;
+/// let info = bucket_manager.info()?;
+/// assert!(info.is_private());
+/// # Ok(())
+/// # }
+/// ```
+pub struct BucketManager<'m> {
+    storage_manager: &'m StorageManager,
+    bucket: Cow<'m, str>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct BucketTagging {
+    #[serde(rename = "Tags", default)]
+    tags: Vec<BucketTag>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BucketTag {
+    #[serde(rename = "Key")]
+    key: String,
+    #[serde(rename = "Value")]
+    value: String,
+}
+
+impl<'m> BucketManager<'m> {
+    pub(super) fn new(storage_manager: &'m StorageManager, bucket: Cow<'m, str>) -> BucketManager<'m> {
+        BucketManager {
+            storage_manager,
+            bucket,
+        }
+    }
+
+    /// 存储空间名称
+    pub fn name(&self) -> &str {
+        self.bucket.as_ref()
+    }
+
+    /// 获取存储空间信息
+    ///
+    /// 包括存储空间所在区域，访问权限，缓存时间以及下载域名
+    pub fn info(&self) -> HTTPResult<BucketInfo> {
+        let mut info: BucketInfo = self
+            .storage_manager
+            .http_client
+            .post("/v2/bucketInfo", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()?;
+        info.domains = self
+            .storage_manager
+            .http_client
+            .get("/v6/domain/list", &[&self.storage_manager.api_url])
+            .query("tbl", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()?;
+        Ok(info)
+    }
+
+    /// 设置存储空间访问权限
+    ///
+    /// 传入 `true` 将存储空间设置为私有空间，传入 `false` 则设置为公开空间
+    pub fn set_private(&self, private: bool) -> HTTPResult<()> {
+        self.storage_manager
+            .http_client
+            .post("/private", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .query("private", if private { "1" } else { "0" })
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 设置存储空间中文件被访问时返回的 `Cache-Control: max-age` 响应头
+    ///
+    /// 缓存时间精确到秒，传入 0 表示使用七牛默认值
+    pub fn set_max_age(&self, max_age: Duration) -> HTTPResult<()> {
+        self.storage_manager
+            .http_client
+            .post("/maxAge", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .query("maxAge", max_age.as_secs().to_string())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 获取存储空间配额
+    pub fn quota(&self) -> HTTPResult<BucketQuota> {
+        self.storage_manager
+            .http_client
+            .post(
+                &("/getbucketquota/".to_owned() + self.bucket.as_ref()),
+                &[&self.storage_manager.api_url],
+            )
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()
+    }
+
+    /// 设置存储空间配额
+    ///
+    /// `size` 为存储空间最大存储量，单位为字节，`count` 为存储空间最大文件数量，传入 `None` 表示不限制
+    pub fn set_quota(&self, size: Option<u64>, count: Option<u64>) -> HTTPResult<()> {
+        let to_param = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_else(|| "-1".to_owned());
+        self.storage_manager
+            .http_client
+            .post(
+                &("/setbucketquota/".to_owned()
+                    + self.bucket.as_ref()
+                    + "/size/"
+                    + &to_param(size)
+                    + "/count/"
+                    + &to_param(count)),
+                &[&self.storage_manager.api_url],
+            )
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 获取存储空间标签
+    pub fn tags(&self) -> HTTPResult<HashMap<String, String>> {
+        let tagging: BucketTagging = self
+            .storage_manager
+            .http_client
+            .get("/bucketTagging", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()?;
+        Ok(tagging.tags.into_iter().map(|tag| (tag.key, tag.value)).collect())
+    }
+
+    /// 设置存储空间标签
+    ///
+    /// 存储空间原有的标签将被全部替换
+    pub fn set_tags<K: Into<String>, V: Into<String>>(&self, tags: impl IntoIterator<Item = (K, V)>) -> HTTPResult<()> {
+        let tagging = BucketTagging {
+            tags: tags
+                .into_iter()
+                .map(|(key, value)| BucketTag {
+                    key: key.into(),
+                    value: value.into(),
+                })
+                .collect(),
+        };
+        self.storage_manager
+            .http_client
+            .put("/bucketTagging", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .json_body(&tagging)
+            .unwrap()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 删除存储空间的全部标签
+    pub fn delete_tags(&self) -> HTTPResult<()> {
+        self.storage_manager
+            .http_client
+            .delete("/bucketTagging", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 列出存储空间的全部生命周期规则
+    pub fn lifecycle_rules(&self) -> HTTPResult<Vec<LifecycleRule>> {
+        self.get_rules("/rules/get")
+    }
+
+    /// 添加生命周期规则
+    pub fn add_lifecycle_rule(&self, rule: &LifecycleRule) -> HTTPResult<()> {
+        self.post_form("/rules/add", rule.to_params(&self.bucket))
+    }
+
+    /// 更新生命周期规则
+    ///
+    /// 将根据规则名称替换已有的同名规则
+    pub fn update_lifecycle_rule(&self, rule: &LifecycleRule) -> HTTPResult<()> {
+        self.post_form("/rules/update", rule.to_params(&self.bucket))
+    }
+
+    /// 删除指定名称的生命周期规则
+    pub fn delete_lifecycle_rule(&self, name: impl AsRef<str>) -> HTTPResult<()> {
+        self.post_form(
+            "/rules/delete",
+            vec![("bucket", self.bucket.as_ref().into()), ("name", name.as_ref().into())],
+        )
+    }
+
+    /// 列出存储空间的全部事件通知规则
+    pub fn event_rules(&self) -> HTTPResult<Vec<EventRule>> {
+        self.get_rules("/events/get")
+    }
+
+    /// 添加事件通知规则
+    pub fn add_event_rule(&self, rule: &EventRule) -> HTTPResult<()> {
+        self.post_form("/events/add", rule.to_params(&self.bucket))
+    }
+
+    /// 更新事件通知规则
+    ///
+    /// 将根据规则名称替换已有的同名规则
+    pub fn update_event_rule(&self, rule: &EventRule) -> HTTPResult<()> {
+        self.post_form("/events/update", rule.to_params(&self.bucket))
+    }
+
+    /// 删除指定名称的事件通知规则
+    pub fn delete_event_rule(&self, name: impl AsRef<str>) -> HTTPResult<()> {
+        self.post_form(
+            "/events/delete",
+            vec![("bucket", self.bucket.as_ref().into()), ("name", name.as_ref().into())],
+        )
+    }
+
+    /// 获取存储空间的跨域规则
+    pub fn cors_rules(&self) -> HTTPResult<Vec<CorsRule>> {
+        let rules: Option<Vec<CorsRule>> = self
+            .storage_manager
+            .http_client
+            .get(
+                &("/corsRules/get/".to_owned() + self.bucket.as_ref()),
+                &[&self.storage_manager.uc_url],
+            )
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()?;
+        Ok(rules.unwrap_or_default())
+    }
+
+    /// 设置存储空间的跨域规则
+    ///
+    /// 存储空间原有的跨域规则将被全部替换，传入空列表表示清除全部跨域规则
+    pub fn set_cors_rules(&self, rules: &[CorsRule]) -> HTTPResult<()> {
+        self.storage_manager
+            .http_client
+            .post(
+                &("/corsRules/set/".to_owned() + self.bucket.as_ref()),
+                &[&self.storage_manager.uc_url],
+            )
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .idempotent()
+            .json_body(&rules)
+            .unwrap()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 获取存储空间的 Referer 防盗链配置
+    pub fn referer_anti_leech(&self) -> HTTPResult<RefererAntiLeech> {
+        let info: RefererAntiLeechInfo = self
+            .storage_manager
+            .http_client
+            .post("/v2/bucketInfo", &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()?;
+        Ok(info.into())
+    }
+
+    /// 设置存储空间的 Referer 防盗链配置
+    ///
+    /// 存储空间原有的 Referer 防盗链配置将被全部替换
+    pub fn set_referer_anti_leech(&self, referer_anti_leech: &RefererAntiLeech) -> HTTPResult<()> {
+        referer_anti_leech
+            .to_query_pairs()
+            .into_iter()
+            .fold(
+                self.storage_manager
+                    .http_client
+                    .post("/referAntiLeech", &[&self.storage_manager.uc_url])
+                    .query("bucket", self.bucket.as_ref()),
+                |builder, (key, value)| builder.query(key, value),
+            )
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    fn get_rules<T: DeserializeOwned>(&self, path: &str) -> HTTPResult<Vec<T>> {
+        let rules: Option<Vec<T>> = self
+            .storage_manager
+            .http_client
+            .get(path, &[&self.storage_manager.uc_url])
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .accept_json()
+            .no_body()
+            .send()?
+            .parse_json()?;
+        Ok(rules.unwrap_or_default())
+    }
+
+    fn post_form(&self, path: &str, params: Vec<(&str, Cow<str>)>) -> HTTPResult<()> {
+        self.storage_manager
+            .http_client
+            .post(path, &[&self.storage_manager.uc_url])
+            .token(TokenVersion::V2, self.storage_manager.credential.borrow().into())
+            .raw_body(
+                "application/x-www-form-urlencoded",
+                serde_urlencoded::to_string(params).unwrap(),
+            )
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ConfigBuilder,
+        credential::Credential,
+        http::{DomainsManagerBuilder, Method},
+    };
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock};
+    use serde_json::{json, Value};
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_storage_bucket_manager_info_and_settings() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                r"^https://uc\.qbox\.me/v2/bucketInfo\?bucket=test-bucket$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!({
+                            "region": "z1",
+                            "private": 1,
+                            "max_age": 86400,
+                            "no_index_page": 0,
+                            "source": "https://origin.example.com",
+                            "host": "",
+                        }),
+                    ))
+                },
+            )
+            .install(
+                Method::GET,
+                r"^https://api\.qiniu\.com/v6/domain/list\?tbl=test-bucket$",
+                |_, _| Ok(json_response(200, json!(["abc.com", "def.com"]))),
+            )
+            .install(Method::POST, r"^https://uc\.qbox\.me/private\?", |request, _| {
+                assert!(request.url().contains("bucket=test-bucket"));
+                assert!(request.url().contains("private=0"));
+                Ok(json_response(200, json!({})))
+            })
+            .install(Method::POST, r"^https://uc\.qbox\.me/maxAge\?", |request, _| {
+                assert!(request.url().contains("bucket=test-bucket"));
+                assert!(request.url().contains("maxAge=3600"));
+                Ok(json_response(200, json!({})))
+            })
+            .install(
+                Method::POST,
+                r"^https://api\.qiniu\.com/setbucketquota/test-bucket/size/1048576/count/-1$",
+                |_, _| Ok(json_response(200, json!({}))),
+            )
+            .install(
+                Method::POST,
+                r"^https://api\.qiniu\.com/getbucketquota/test-bucket$",
+                |_, _| Ok(json_response(200, json!({"size": 1_048_576, "count": -1}))),
+            ),
+        );
+        let storage_manager = get_storage_manager(mock.clone());
+        let bucket_manager = storage_manager.bucket_manager("test-bucket");
+        let info = bucket_manager.info()?;
+        assert_eq!(info.region(), "z1");
+        assert!(info.is_private());
+        assert_eq!(info.max_age(), Some(Duration::from_secs(86400)));
+        assert_eq!(info.domains(), &["abc.com".to_owned(), "def.com".to_owned()]);
+        assert_eq!(info.mirror_source(), Some("https://origin.example.com"));
+        assert_eq!(info.mirror_host(), None);
+
+        bucket_manager.set_private(false)?;
+        bucket_manager.set_max_age(Duration::from_secs(3600))?;
+        bucket_manager.set_quota(Some(1 << 20), None)?;
+        let quota = bucket_manager.quota()?;
+        assert_eq!(quota.size(), Some(1 << 20));
+        assert_eq!(quota.count(), None);
+        assert_eq!(mock.call_called(), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_manager_tags() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::PUT,
+                r"^https://uc\.qbox\.me/bucketTagging\?bucket=test-bucket$",
+                |request, _| {
+                    let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                    assert_eq!(
+                        body,
+                        json!({"Tags": [{"Key": "env", "Value": "production"}, {"Key": "team", "Value": "media"}]})
+                    );
+                    Ok(json_response(200, json!({})))
+                },
+            )
+            .install(
+                Method::GET,
+                r"^https://uc\.qbox\.me/bucketTagging\?bucket=test-bucket$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!({"Tags": [{"Key": "env", "Value": "production"}, {"Key": "team", "Value": "media"}]}),
+                    ))
+                },
+            )
+            .install(
+                Method::DELETE,
+                r"^https://uc\.qbox\.me/bucketTagging\?bucket=test-bucket$",
+                |_, _| Ok(json_response(200, json!({}))),
+            ),
+        );
+        let storage_manager = get_storage_manager(mock.clone());
+        let bucket_manager = storage_manager.bucket_manager("test-bucket");
+        bucket_manager.set_tags(vec![("env", "production"), ("team", "media")])?;
+        let tags = bucket_manager.tags()?;
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags.get("env").map(|v| v.as_str()), Some("production"));
+        assert_eq!(tags.get("team").map(|v| v.as_str()), Some("media"));
+        bucket_manager.delete_tags()?;
+        assert_eq!(mock.call_called(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_manager_lifecycle_rules() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, r"^https://uc\.qbox\.me/rules/add$", |request, _| {
+                let params =
+                    serde_urlencoded::from_bytes::<Vec<(String, String)>>(request.body().as_ref().unwrap()).unwrap();
+                assert_eq!(
+                    params,
+                    vec![
+                        ("bucket".to_owned(), "test-bucket".to_owned()),
+                        ("name".to_owned(), "logs".to_owned()),
+                        ("prefix".to_owned(), "logs/".to_owned()),
+                        ("delete_after_days".to_owned(), "365".to_owned()),
+                        ("to_line_after_days".to_owned(), "30".to_owned()),
+                        ("to_archive_after_days".to_owned(), "0".to_owned()),
+                    ]
+                );
+                Ok(json_response(200, json!({})))
+            })
+            .install(
+                Method::GET,
+                r"^https://uc\.qbox\.me/rules/get\?bucket=test-bucket$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!([{
+                            "name": "logs",
+                            "prefix": "logs/",
+                            "delete_after_days": 365,
+                            "to_line_after_days": 30,
+                            "to_archive_after_days": 0,
+                            "ctime": "2020-01-01T00:00:00.000+08:00",
+                        }]),
+                    ))
+                },
+            )
+            .install(Method::POST, r"^https://uc\.qbox\.me/rules/delete$", |request, _| {
+                assert_eq!(
+                    request.body().as_ref().unwrap().as_ref(),
+                    b"bucket=test-bucket&name=logs"
+                );
+                Ok(json_response(200, json!({})))
+            }),
+        );
+        let storage_manager = get_storage_manager(mock.clone());
+        let bucket_manager = storage_manager.bucket_manager("test-bucket");
+        let rule = LifecycleRuleBuilder::new("logs")
+            .prefix("logs/")
+            .to_infrequent_after_days(30)
+            .delete_after_days(365)
+            .build();
+        bucket_manager.add_lifecycle_rule(&rule)?;
+        let rules = bucket_manager.lifecycle_rules()?;
+        assert_eq!(rules, vec![rule]);
+        assert_eq!(rules[0].to_infrequent_after_days(), Some(30));
+        assert_eq!(rules[0].to_archive_after_days(), None);
+        bucket_manager.delete_lifecycle_rule("logs")?;
+        assert_eq!(mock.call_called(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_manager_event_rules() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, r"^https://uc\.qbox\.me/events/update$", |request, _| {
+                let params =
+                    serde_urlencoded::from_bytes::<Vec<(String, String)>>(request.body().as_ref().unwrap()).unwrap();
+                assert_eq!(
+                    params,
+                    vec![
+                        ("bucket".to_owned(), "test-bucket".to_owned()),
+                        ("name".to_owned(), "images".to_owned()),
+                        ("prefix".to_owned(), "images/".to_owned()),
+                        ("suffix".to_owned(), ".jpg".to_owned()),
+                        ("event".to_owned(), "put".to_owned()),
+                        ("event".to_owned(), "copy".to_owned()),
+                        ("event".to_owned(), "deleteMarkerCreate".to_owned()),
+                        ("callbackURL".to_owned(), "https://a.example.com/callback".to_owned()),
+                        ("callbackURL".to_owned(), "https://b.example.com/callback".to_owned()),
+                        ("host".to_owned(), "callback.example.com".to_owned()),
+                    ]
+                );
+                Ok(json_response(200, json!({})))
+            })
+            .install(
+                Method::GET,
+                r"^https://uc\.qbox\.me/events/get\?bucket=test-bucket$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!([{
+                            "name": "images",
+                            "prefix": "images/",
+                            "suffix": ".jpg",
+                            "event": ["put", "delete"],
+                            "callback_urls": ["https://a.example.com/callback"],
+                            "access_key": "abcdefghklmnopq",
+                        }]),
+                    ))
+                },
+            ),
+        );
+        let storage_manager = get_storage_manager(mock.clone());
+        let bucket_manager = storage_manager.bucket_manager("test-bucket");
+        bucket_manager.update_event_rule(
+            &EventRuleBuilder::new(
+                "images",
+                &[EventType::Put, EventType::Copy, EventType::DeleteMarkerCreate],
+                &["https://a.example.com/callback", "https://b.example.com/callback"],
+            )
+            .prefix("images/")
+            .suffix(".jpg")
+            .host("callback.example.com")
+            .build(),
+        )?;
+        let rules = bucket_manager.event_rules()?;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name(), "images");
+        assert_eq!(rules[0].events(), &[EventType::Put, EventType::Delete]);
+        assert_eq!(rules[0].callback_urls(), &["https://a.example.com/callback".to_owned()]);
+        assert_eq!(rules[0].access_key(), Some("abcdefghklmnopq"));
+        assert_eq!(rules[0].host(), None);
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_bucket_manager_cors_and_referer() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                r"^https://uc\.qbox\.me/corsRules/set/test-bucket$",
+                |request, _| {
+                    let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                    assert_eq!(
+                        body,
+                        json!([{
+                            "allowed_origin": ["https://www.example.com"],
+                            "allowed_method": ["GET", "HEAD"],
+                            "exposed_header": ["ETag"],
+                            "max_age": 600,
+                        }])
+                    );
+                    Ok(json_response(200, json!({})))
+                },
+            )
+            .install(
+                Method::GET,
+                r"^https://uc\.qbox\.me/corsRules/get/test-bucket$",
+                |_, _| {
+                    Ok(json_response(
+                        200,
+                        json!([{
+                            "allowed_origin": ["https://www.example.com"],
+                            "allowed_method": ["GET", "HEAD"],
+                            "exposed_header": ["ETag"],
+                            "max_age": 600,
+                        }]),
+                    ))
+                },
+            )
+            .install(Method::POST, r"^https://uc\.qbox\.me/referAntiLeech\?", |request, _| {
+                let url = url::Url::parse(request.url()).unwrap();
+                let mut pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+                pairs.sort();
+                assert_eq!(
+                    pairs,
+                    vec![
+                        ("bucket".to_owned(), "test-bucket".to_owned()),
+                        ("mode".to_owned(), "2".to_owned()),
+                        ("norefer".to_owned(), "1".to_owned()),
+                        ("pattern".to_owned(), "www.example.com;*.example.org".to_owned()),
+                        ("source_enabled".to_owned(), "0".to_owned()),
+                    ]
+                );
+                Ok(json_response(200, json!({})))
+            })
+            .install(Method::POST, r"^https://uc\.qbox\.me/v2/bucketInfo\?", |_, _| {
+                Ok(json_response(
+                    200,
+                    json!({
+                        "region": "z0",
+                        "anti_leech_mode": 2,
+                        "refer_wl": null,
+                        "refer_bl": ["www.example.com", "*.example.org"],
+                        "no_refer": true,
+                        "source_enabled": false,
+                    }),
+                ))
+            }),
+        );
+        let storage_manager = get_storage_manager(mock.clone());
+        let bucket_manager = storage_manager.bucket_manager("test-bucket");
+        let rules = vec![CorsRuleBuilder::new(&["https://www.example.com"], &["GET", "HEAD"])
+            .exposed_headers(&["ETag"])
+            .max_age(Duration::from_secs(600))
+            .build()];
+        bucket_manager.set_cors_rules(&rules)?;
+        assert_eq!(bucket_manager.cors_rules()?, rules);
+
+        let referer_anti_leech = RefererAntiLeechBuilder::new(RefererAntiLeechMode::Blacklist)
+            .patterns(&["www.example.com", "*.example.org"])
+            .allow_empty_referer(true)
+            .build();
+        bucket_manager.set_referer_anti_leech(&referer_anti_leech)?;
+        assert_eq!(bucket_manager.referer_anti_leech()?, referer_anti_leech);
+        assert_eq!(mock.call_called(), 4);
+        Ok(())
+    }
+
+    fn get_storage_manager(mock: CounterCallMock<CallHandlers>) -> StorageManager {
+        StorageManager::new(
+            Credential::new("abcdefghklmnopq", "1234567890"),
+            ConfigBuilder::default()
+                .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                .http_request_handler(mock)
+                .build(),
+        )
+    }
+}
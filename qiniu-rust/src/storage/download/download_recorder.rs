@@ -0,0 +1,202 @@
+use super::super::recorder::{default_id_generator, FileSystemRecorder, RecordMedium, Recorder};
+use assert_impl::assert_impl;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Error, ErrorKind, Result},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// 下载进度记录仪
+///
+/// 用于记录文件分片下载进度，如果文件在下载期间发生错误，将可以在重试时避免再次下载已经成功下载的文件分片，实现断点续传
+#[derive(Builder, Clone)]
+#[builder(pattern = "mutable", public, build_fn(name = "inner_build", private))]
+pub struct DownloadRecorder {
+    /// 设置记录仪
+    #[builder(default = "default::recorder()")]
+    recorder: Arc<dyn Recorder>,
+
+    /// 记录仪 ID 生成器
+    ///
+    /// 默认将使用基于 SHA1 的策略生成 ID
+    #[builder(default = "default::id_generator")]
+    id_generator: fn(name: &str, path: &Path, key: Option<&str>) -> String,
+
+    /// 始终刷新
+    ///
+    /// 当记录下载进度后，是否始终刷新 IO 确保数据已经被持久化，默认为否
+    #[builder(default = "default::always_flush_records()")]
+    always_flush_records: bool,
+}
+
+pub(super) struct DownloadRecordMedium {
+    medium: Arc<Mutex<dyn RecordMedium>>,
+    always_flush_records: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub(super) struct DownloadRecordMediumMetadata {
+    pub(super) file_size: u64,
+    pub(super) etag: Box<str>,
+    pub(super) range_size: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct SerializableDownloadRecordMediumMetadata<'a> {
+    file_size: u64,
+    etag: &'a str,
+    range_size: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(super) struct DownloadRecordMediumRangeItem {
+    pub(super) range_number: usize,
+}
+
+impl DownloadRecorderBuilder {
+    pub fn build(&self) -> DownloadRecorder {
+        self.inner_build().unwrap()
+    }
+}
+
+impl DownloadRecorder {
+    pub(super) fn open_and_write_metadata(
+        &self,
+        path: &Path,
+        key: Option<&str>,
+        file_size: u64,
+        etag: &str,
+        range_size: u64,
+    ) -> Result<DownloadRecordMedium> {
+        let metadata = SerializableDownloadRecordMediumMetadata {
+            file_size,
+            etag,
+            range_size,
+        };
+        let medium = self.recorder.open(&self.generate_key(path, key), true)?;
+        {
+            let mut medium = medium.lock().unwrap();
+            let mut metadata = serde_json::to_string(&metadata).map_err(|err| Error::new(ErrorKind::Other, err))?;
+            metadata.push('\n');
+            medium.write_all(metadata.as_bytes())?;
+            if self.always_flush_records {
+                medium.flush()?;
+            }
+        }
+        Ok(DownloadRecordMedium {
+            medium,
+            always_flush_records: self.always_flush_records,
+        })
+    }
+
+    pub(super) fn open_for_appending(&self, path: &Path, key: Option<&str>) -> Result<DownloadRecordMedium> {
+        Ok(DownloadRecordMedium {
+            medium: self.recorder.open(&self.generate_key(path, key), false)?,
+            always_flush_records: self.always_flush_records,
+        })
+    }
+
+    pub(super) fn drop(&self, path: &Path, key: Option<&str>) -> Result<()> {
+        self.recorder.delete(&self.generate_key(path, key))
+    }
+
+    pub(super) fn load(
+        &self,
+        path: &Path,
+        key: Option<&str>,
+    ) -> Result<Option<(DownloadRecordMediumMetadata, Box<[DownloadRecordMediumRangeItem]>)>> {
+        let medium = self.recorder.open(&self.generate_key(path, key), false)?;
+        let mut lock = medium.lock().unwrap();
+        let mut reader = BufReader::new(&mut *lock);
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let metadata: DownloadRecordMediumMetadata =
+            serde_json::from_str(&line).map_err(|err| Error::new(ErrorKind::Other, err))?;
+        let mut range_items = Vec::<DownloadRecordMediumRangeItem>::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(Some((metadata, range_items.into())));
+            }
+            let range_item: DownloadRecordMediumRangeItem =
+                serde_json::from_str(&line).map_err(|err| Error::new(ErrorKind::Other, err))?;
+            range_items.push(range_item);
+        }
+    }
+
+    fn generate_key(&self, path: &Path, key: Option<&str>) -> String {
+        (self.id_generator)("download", path, key)
+    }
+
+    /// 获取记录仪的引用
+    pub fn recorder(&self) -> &dyn Recorder {
+        self.recorder.as_ref()
+    }
+
+    /// 是否总是刷新
+    pub fn always_flush_records(&self) -> bool {
+        self.always_flush_records
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+impl fmt::Debug for DownloadRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DownloadRecorder")
+            .field("recorder", &self.recorder)
+            .field("always_flush_records", &self.always_flush_records)
+            .finish()
+    }
+}
+
+impl Default for DownloadRecorder {
+    fn default() -> Self {
+        DownloadRecorderBuilder::default().build()
+    }
+}
+
+mod default {
+    use super::*;
+
+    #[inline]
+    pub fn recorder() -> Arc<dyn Recorder> {
+        FileSystemRecorder::default()
+    }
+
+    #[inline]
+    pub const fn always_flush_records() -> bool {
+        false
+    }
+
+    pub(super) use super::default_id_generator as id_generator;
+}
+
+impl DownloadRecordMedium {
+    pub(super) fn append(&self, range_number: usize) -> Result<()> {
+        let mut item = serde_json::to_string(&DownloadRecordMediumRangeItem { range_number })
+            .map_err(|err| Error::new(ErrorKind::Other, err))?;
+        item.push('\n');
+        let mut medium = self.medium.lock().unwrap();
+        medium.write_all(item.as_bytes())?;
+        if self.always_flush_records {
+            medium.flush()?;
+        }
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
@@ -0,0 +1,915 @@
+use super::{DownloadUrlBuilder, DownloadUrlError};
+use crate::{
+    http::{Error as HTTPError, ErrorKind as HTTPErrorKind, Method, RetryKind as HTTPRetryKind},
+    utils::{etag, global_thread_pool, rob::Rob, ron::Ron},
+};
+use assert_impl::assert_impl;
+use digest::{FixedOutput, Input};
+use qiniu_http::ResponseBody as HTTPResponseBody;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::{
+    cell::Cell,
+    ffi::OsString,
+    fs::{remove_file, rename, OpenOptions},
+    io::{
+        copy as io_copy, empty as io_empty, sink as io_sink, Cursor, Error as IOError, ErrorKind as IOErrorKind, Read,
+        Result as IOResult, Seek, SeekFrom, Write,
+    },
+    path::{Path, PathBuf},
+    result::Result,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+        Mutex,
+    },
+};
+use thiserror::Error;
+use url::{Position, Url};
+
+const PART_FILE_SUFFIX: &str = ".part";
+const BUFFER_SIZE: usize = 1 << 16;
+const DEFAULT_RANGE_SIZE: u64 = 1 << 22;
+
+/// 下载器
+///
+/// 通过 `Bucket::downloader` 或 `DownloadUrlBuilder::downloader` 获取。
+/// 下载器通过 HTTP `Range` 请求下载对象，在下载过程中发生错误时，将从已经下载的位置继续下载，
+/// 并在多个下载域名之间自动切换。下载完成后，将使用对象的 Etag 对下载内容进行校验。
+/// 对于大文件，可以调用 `download_to_file_concurrently` 在线程池中并发下载多个分片
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let bucket = client.storage().bucket("[Bucket name]").build();
+/// bucket
+///     .downloader("[Key]")
+///     .private()
+///     .on_progress(|downloaded, total| println!("{} / {:?}", downloaded, total))
+///     .download_to_file("/tmp/file")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Downloader<'a, 'r: 'a> {
+    url_builder: DownloadUrlBuilder<'a, 'r>,
+    checksum_enabled: bool,
+    range_size: u64,
+    thread_pool: Option<Ron<'a, ThreadPool>>,
+    max_concurrency: usize,
+    #[allow(clippy::type_complexity)]
+    on_downloading_progress: Option<Rob<'a, dyn Fn(u64, Option<u64>) + Send + Sync>>,
+}
+
+impl<'a, 'r: 'a> Downloader<'a, 'r> {
+    pub(super) fn new(url_builder: DownloadUrlBuilder<'a, 'r>) -> Downloader<'a, 'r> {
+        Downloader {
+            url_builder,
+            checksum_enabled: true,
+            range_size: DEFAULT_RANGE_SIZE,
+            thread_pool: None,
+            max_concurrency: 0,
+            on_downloading_progress: None,
+        }
+    }
+
+    /// 下载私有空间中的对象
+    ///
+    /// 下载地址将被签名，有效期为 `Config::download_url_lifetime`
+    pub fn private(mut self) -> Self {
+        self.url_builder = self.url_builder.private();
+        self
+    }
+
+    /// 禁用文件校验
+    ///
+    /// 在任何场景下都不推荐禁用文件校验
+    pub fn disable_checksum(mut self) -> Self {
+        self.checksum_enabled = false;
+        self
+    }
+
+    /// 启用文件校验
+    ///
+    /// 默认总是启用。
+    /// 注意，仅当下载的是对象原始内容（未指定数据处理指令或样式），且对象的 Etag 为七牛 Etag V1 格式时，才会进行校验
+    pub fn enable_checksum(mut self) -> Self {
+        self.checksum_enabled = true;
+        self
+    }
+
+    /// 为分片下载指定线程池
+    ///
+    /// 默认情况下，分片下载将使用 SDK 内置全局线程池，该线程池默认仅有一个线程，
+    /// 可以调用 `utils::thread_pool::recreate_thread_pool` 调整其线程数量，或调用该方法指定专用线程池
+    pub fn thread_pool(mut self, thread_pool: impl Into<Ron<'a, ThreadPool>>) -> Self {
+        self.thread_pool = Some(thread_pool.into());
+        self
+    }
+
+    /// 为分片下载创建专用线程池指定线程池大小
+    pub fn thread_pool_size(self, num_threads: usize) -> Self {
+        self.thread_pool(
+            ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .thread_name(move |index| format!("downloader_thread_{}_{}", num_threads, index))
+                .build()
+                .unwrap(),
+        )
+    }
+
+    /// 分片下载最大并发度
+    ///
+    /// 默认情况下，分片下载的最大并发度等于下载器所用线程池的大小。
+    /// 调用该方法可以修改最大并发度
+    ///
+    /// `concurrency` 必须大于 0
+    pub fn max_concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0);
+        self.max_concurrency = concurrency;
+        self
+    }
+
+    /// 分片下载时每个分片的大小
+    ///
+    /// 默认为 4 MB
+    ///
+    /// `range_size` 必须大于 0
+    pub fn range_size(mut self, range_size: u64) -> Self {
+        assert!(range_size > 0);
+        self.range_size = range_size;
+        self
+    }
+
+    /// 下载进度回调
+    ///
+    /// 将在下载期间反复回调指定的闭包，以获取下载进度。
+    /// 下载进度闭包的第一个参数为已经下载的数据量，
+    /// 第二个参数为数据总量，如果为 `None` 表示数据总量不可预知，
+    /// 单位均为字节
+    pub fn on_progress_ref(mut self, callback: &'a (dyn Fn(u64, Option<u64>) + Send + Sync)) -> Self {
+        self.on_downloading_progress = Some(callback.into());
+        self
+    }
+
+    /// 下载进度回调
+    ///
+    /// 将在下载期间反复回调指定的闭包，以获取下载进度。
+    /// 下载进度闭包的第一个参数为已经下载的数据量，
+    /// 第二个参数为数据总量，如果为 `None` 表示数据总量不可预知，
+    /// 单位均为字节
+    pub fn on_progress(mut self, callback: impl Fn(u64, Option<u64>) + Send + Sync + 'static) -> Self {
+        self.on_downloading_progress = Some(Rob::Owned(Box::new(callback)));
+        self
+    }
+
+    /// 下载对象到数据流
+    ///
+    /// 返回下载的数据量，单位为字节
+    pub fn download_to_writer(self, writer: impl Write) -> DownloadResult<u64> {
+        let mut writer = EtagWriter {
+            inner: writer,
+            etag: etag::new(),
+        };
+        let (size, response_etag) = self.download(&mut writer, 0)?;
+        if let Some(expected) = self.expected_etag(response_etag)? {
+            let actual = String::from_utf8(writer.etag.fixed_result().to_vec()).unwrap();
+            if actual != expected {
+                return Err(DownloadError::EtagMismatch { expected, actual });
+            }
+        }
+        Ok(size)
+    }
+
+    /// 下载对象到指定路径
+    ///
+    /// 下载期间数据将被写入到 `<file_path>.part` 临时文件中，下载并校验成功后再移动到指定路径。
+    /// 如果临时文件已经存在，将从临时文件的末尾继续下载；如果继续下载的文件校验失败，将重新下载整个文件。
+    /// 返回文件大小，单位为字节
+    pub fn download_to_file(self, file_path: impl AsRef<Path>) -> DownloadResult<u64> {
+        let file_path = file_path.as_ref();
+        let part_file_path = Self::part_file_path(file_path);
+        let resumed = part_file_path.exists();
+        let size = match self.download_to_part_file(&part_file_path) {
+            Err(DownloadError::EtagMismatch { .. }) if resumed => self.download_to_part_file(&part_file_path)?,
+            result => result?,
+        };
+        rename(&part_file_path, file_path)?;
+        Ok(size)
+    }
+
+    /// 分片并发下载对象到指定路径
+    ///
+    /// 适用于大文件下载。SDK 将首先获取对象大小，然后在线程池中并发下载各个分片，并直接写入到预先分配空间的 `<file_path>.part` 临时文件中。
+    /// 已经下载完成的分片将被记录在 `Config::download_recorder` 中，如果下载中断，再次调用时将仅下载尚未完成的分片。
+    /// 下载并校验成功后临时文件将被移动到指定路径。
+    /// 如果无法获取对象大小，将退化为 `download_to_file` 的行为。
+    /// 返回文件大小，单位为字节
+    pub fn download_to_file_concurrently(self, file_path: impl AsRef<Path>) -> DownloadResult<u64> {
+        let file_path = file_path.as_ref();
+        let (file_size, response_etag) = match self.stat_remote()? {
+            (Some(file_size), response_etag) => (file_size, response_etag),
+            (None, _) => return self.download_to_file(file_path),
+        };
+        let part_file_path = Self::part_file_path(file_path);
+        let recorder = self.url_builder.bucket().config().download_recorder();
+        let key = Some(self.url_builder.key());
+        let expected = self.expected_etag(response_etag.to_owned())?;
+        loop {
+            let resumed = self.download_ranges(
+                file_path,
+                &part_file_path,
+                file_size,
+                response_etag.as_ref().map(String::as_str).unwrap_or_default(),
+            )?;
+            if let Some(expected) = &expected {
+                let actual = etag::from_file(&part_file_path)?;
+                if &actual != expected {
+                    remove_file(&part_file_path)?;
+                    let _ = recorder.drop(file_path, key);
+                    if resumed {
+                        continue;
+                    }
+                    return Err(DownloadError::EtagMismatch {
+                        expected: expected.to_owned(),
+                        actual,
+                    });
+                }
+            }
+            break;
+        }
+        rename(&part_file_path, file_path)?;
+        let _ = recorder.drop(file_path, key);
+        Ok(file_size)
+    }
+
+    fn download_to_part_file(&self, part_file_path: &Path) -> DownloadResult<u64> {
+        let (size, response_etag) = {
+            let mut file = OpenOptions::new().create(true).append(true).open(part_file_path)?;
+            let offset = file.metadata()?.len();
+            self.download(&mut file, offset)?
+        };
+        if let Some(expected) = self.expected_etag(response_etag)? {
+            let actual = etag::from_file(part_file_path)?;
+            if actual != expected {
+                remove_file(part_file_path)?;
+                return Err(DownloadError::EtagMismatch { expected, actual });
+            }
+        }
+        Ok(size)
+    }
+
+    fn download(&self, writer: &mut dyn Write, mut offset: u64) -> DownloadResult<(u64, Option<String>)> {
+        let mut response_etag: Option<String> = None;
+        let on_progress = |downloaded: u64, total: Option<u64>| {
+            if let Some(callback) = &self.on_downloading_progress {
+                callback(downloaded, total);
+            }
+        };
+        self.try_urls(|url| {
+            match self.download_from_url(url, writer, &mut offset, None, &mut response_etag, &on_progress) {
+                Err(DownloadError::HTTPError(err)) if offset > 0 => match err.error_kind() {
+                    // 已经下载的数据就是完整的对象
+                    HTTPErrorKind::ResponseStatusCodeError(416, _) => Ok(()),
+                    _ => Err(err.into()),
+                },
+                result => result,
+            }
+        })?;
+        Ok((offset, response_etag))
+    }
+
+    fn download_ranges(
+        &self,
+        file_path: &Path,
+        part_file_path: &Path,
+        file_size: u64,
+        etag: &str,
+    ) -> DownloadResult<bool> {
+        let recorder = self.url_builder.bucket().config().download_recorder();
+        let key = Some(self.url_builder.key());
+        let range_size = self.range_size;
+        let range_count = ((file_size + range_size - 1) / range_size) as usize;
+        let mut completed_ranges = vec![false; range_count];
+        let mut resumed = false;
+        let medium = match recorder.load(file_path, key).ok().and_then(|record| record) {
+            Some((metadata, range_items))
+                if metadata.file_size == file_size
+                    && metadata.etag.as_ref() == etag
+                    && metadata.range_size == range_size
+                    && part_file_path.metadata().map(|m| m.len()).ok() == Some(file_size) =>
+            {
+                for range_item in range_items.iter() {
+                    if let Some(completed) = completed_ranges.get_mut(range_item.range_number) {
+                        *completed = true;
+                        resumed = true;
+                    }
+                }
+                recorder.open_for_appending(file_path, key).ok()
+            }
+            _ => {
+                OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .truncate(true)
+                    .open(part_file_path)?
+                    .set_len(file_size)?;
+                recorder
+                    .open_and_write_metadata(file_path, key, file_size, etag, range_size)
+                    .ok()
+            }
+        };
+        let pending_ranges = (0..range_count)
+            .filter(|&range_number| !completed_ranges[range_number])
+            .collect::<Vec<_>>();
+        let completed_size = AtomicU64::new(
+            (0..range_count)
+                .filter(|&range_number| completed_ranges[range_number])
+                .map(|range_number| {
+                    Self::range_end(range_number, range_size, file_size) - range_number as u64 * range_size
+                })
+                .sum(),
+        );
+        let next_range = AtomicUsize::new(0);
+        let first_error = Mutex::new(None::<DownloadError>);
+        let global_thread_pool_guard;
+        let thread_pool: &ThreadPool = match &self.thread_pool {
+            Some(thread_pool) => thread_pool.as_ref(),
+            None => {
+                global_thread_pool_guard = global_thread_pool.read().unwrap();
+                &global_thread_pool_guard
+            }
+        };
+        let concurrency = {
+            let mut c = thread_pool.current_num_threads();
+            if (1..c).contains(&self.max_concurrency) {
+                c = self.max_concurrency;
+            }
+            c.min(pending_ranges.len())
+        };
+
+        thread_pool.scope(|s| {
+            for _ in 0..concurrency {
+                s.spawn(|_| {
+                    let result = (|| -> DownloadResult<()> {
+                        let mut file = OpenOptions::new().write(true).open(part_file_path)?;
+                        while first_error.lock().unwrap().is_none() {
+                            let range_number = match pending_ranges.get(next_range.fetch_add(1, Relaxed)) {
+                                Some(&range_number) => range_number,
+                                None => return Ok(()),
+                            };
+                            let start = range_number as u64 * range_size;
+                            let end = Self::range_end(range_number, range_size, file_size);
+                            file.seek(SeekFrom::Start(start))?;
+                            self.download_range(&mut file, start, end, file_size, etag, &completed_size)?;
+                            if let Some(medium) = &medium {
+                                let _ = medium.append(range_number);
+                            }
+                        }
+                        Ok(())
+                    })();
+                    if let Err(err) = result {
+                        first_error.lock().unwrap().get_or_insert(err);
+                    }
+                });
+            }
+        });
+
+        match first_error.into_inner().unwrap() {
+            Some(err) => Err(err),
+            None => Ok(resumed),
+        }
+    }
+
+    fn download_range(
+        &self,
+        writer: &mut dyn Write,
+        start: u64,
+        end: u64,
+        file_size: u64,
+        etag: &str,
+        completed_size: &AtomicU64,
+    ) -> DownloadResult<()> {
+        let mut offset = start;
+        let mut response_etag = if etag.is_empty() { None } else { Some(etag.to_owned()) };
+        let last_position = Cell::new(start);
+        let on_progress = |position: u64, _: Option<u64>| {
+            if let Some(callback) = &self.on_downloading_progress {
+                let position = position.min(end);
+                let prev_position = last_position.get();
+                if position > prev_position {
+                    last_position.set(position);
+                    let added_size = position - prev_position;
+                    callback(
+                        completed_size.fetch_add(added_size, Relaxed) + added_size,
+                        Some(file_size),
+                    );
+                }
+            }
+        };
+        self.try_urls(|url| {
+            self.download_from_url(url, writer, &mut offset, Some(end), &mut response_etag, &on_progress)
+        })?;
+        if self.on_downloading_progress.is_none() {
+            completed_size.fetch_add(end - start, Relaxed);
+        }
+        Ok(())
+    }
+
+    fn stat_remote(&self) -> DownloadResult<(Option<u64>, Option<String>)> {
+        self.try_urls(|url| {
+            let parsed_url = Url::parse(url).map_err(DownloadUrlError::from)?;
+            let base_urls = [&parsed_url[..Position::BeforePath]];
+            let request = self
+                .url_builder
+                .bucket()
+                .http_client()
+                .head(&parsed_url[Position::BeforePath..], &base_urls)
+                .no_body();
+            let response = request.send()?;
+            Ok((
+                response
+                    .header("Content-Length")
+                    .and_then(|length| length.parse::<u64>().ok()),
+                Self::parse_etag(response.header("ETag").map(|etag| etag.as_ref())),
+            ))
+        })
+    }
+
+    fn try_urls<T>(&self, mut f: impl FnMut(&str) -> DownloadResult<T>) -> DownloadResult<T> {
+        let config = self.url_builder.bucket().config();
+        let domains_manager = config.domains_manager();
+        let mut urls = self
+            .url_builder
+            .domains()?
+            .into_iter()
+            .map(|domain| self.url_builder.build_with_domain(domain))
+            .collect::<Result<Vec<_>, _>>()?;
+        if urls.is_empty() {
+            return Err(DownloadUrlError::NoDomainAvailable.into());
+        }
+        // 优先使用未被冻结的域名
+        urls.sort_by_key(|url| domains_manager.is_frozen_url(url).unwrap_or(false));
+
+        let mut prev_err: Option<HTTPError> = None;
+        for url in urls.iter().cycle().take(urls.len() + config.http_request_retries()) {
+            match f(url) {
+                Err(DownloadError::HTTPError(err)) => match err.retry_kind() {
+                    HTTPRetryKind::UnretryableError => return Err(err.into()),
+                    _ => {
+                        prev_err = Some(err);
+                    }
+                },
+                result => return result,
+            }
+        }
+        Err(prev_err.unwrap().into())
+    }
+
+    fn download_from_url(
+        &self,
+        url: &str,
+        writer: &mut dyn Write,
+        offset: &mut u64,
+        end: Option<u64>,
+        response_etag: &mut Option<String>,
+        on_progress: &dyn Fn(u64, Option<u64>),
+    ) -> DownloadResult<()> {
+        let parsed_url = Url::parse(url).map_err(DownloadUrlError::from)?;
+        let base_urls = [&parsed_url[..Position::BeforePath]];
+        let path = &parsed_url[Position::BeforePath..];
+        let start = Cell::new(*offset);
+        let on_downloading_progress = |downloaded: u64, total: u64| {
+            let start = start.get();
+            on_progress(start + downloaded, if total > 0 { Some(start + total) } else { None });
+        };
+        let range = format!(
+            "bytes={}-{}",
+            *offset,
+            end.map(|end| (end - 1).to_string()).unwrap_or_default()
+        );
+
+        let mut builder = self.url_builder.bucket().http_client().get(path, &base_urls);
+        if *offset > 0 || end.is_some() {
+            builder = builder.header("Range", range.as_str());
+            if let Some(etag) = response_etag.as_ref() {
+                builder = builder.header("If-Range", format!("\"{}\"", etag));
+            }
+        }
+        if self.on_downloading_progress.is_some() {
+            builder = builder.on_downloading_progress(&on_downloading_progress);
+        }
+        let request = builder.no_body();
+        let response = request.send()?;
+
+        let etag = Self::parse_etag(response.header("ETag").map(|etag| etag.as_ref()));
+        let (skip, total) = if response.status_code() == 206 {
+            let total = response
+                .header("Content-Range")
+                .and_then(|range| range.rsplit('/').next())
+                .and_then(|total| total.parse::<u64>().ok());
+            (0, end.or(total))
+        } else {
+            // 服务器忽略了 Range 请求，需要跳过已经下载的数据
+            start.set(0);
+            let total = response
+                .header("Content-Length")
+                .and_then(|length| length.parse::<u64>().ok());
+            (*offset, end.or(total))
+        };
+        if etag.is_some() {
+            *response_etag = etag;
+        }
+
+        let read_error = |err: IOError| {
+            self.url_builder
+                .bucket()
+                .config()
+                .domains_manager()
+                .freeze_url(url)
+                .ok();
+            DownloadError::HTTPError(HTTPError::new_retryable_error_from_parts(
+                HTTPErrorKind::IOError(err),
+                true,
+                Some(Method::GET),
+                Some(url.into()),
+            ))
+        };
+        let mut body: Box<dyn Read> = match response.into_body() {
+            Some(HTTPResponseBody::Reader(reader)) => reader,
+            Some(HTTPResponseBody::Bytes(bytes)) => Box::new(Cursor::new(bytes)),
+            Some(HTTPResponseBody::File(file)) => Box::new(file),
+            None => Box::new(io_empty()),
+        };
+        if skip > 0 && io_copy(&mut body.by_ref().take(skip), &mut io_sink()).map_err(read_error)? < skip {
+            return Err(read_error(IOError::from(IOErrorKind::UnexpectedEof)));
+        }
+        let mut body = body.take(end.map(|end| end - *offset).unwrap_or(u64::max_value()));
+        let mut buf = vec![0u8; BUFFER_SIZE];
+        loop {
+            match body.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    writer.write_all(&buf[..n])?;
+                    *offset += n as u64;
+                }
+                Err(ref err) if err.kind() == IOErrorKind::Interrupted => continue,
+                Err(err) => return Err(read_error(err)),
+            }
+        }
+        writer.flush()?;
+        if let Some(total) = total {
+            if *offset < total {
+                return Err(read_error(IOError::from(IOErrorKind::UnexpectedEof)));
+            }
+        }
+        Ok(())
+    }
+
+    fn expected_etag(&self, response_etag: Option<String>) -> DownloadResult<Option<String>> {
+        if !self.checksum_enabled || self.url_builder.is_processed() {
+            return Ok(None);
+        }
+        let expected = match response_etag {
+            Some(etag) => etag,
+            None => self
+                .url_builder
+                .bucket()
+                .stat(self.url_builder.key())?
+                .hash()
+                .to_owned(),
+        };
+        // 仅有七牛 Etag V1 能够通过 `etag::from_file` 计算得到
+        if expected.len() == etag::ETAG_SIZE && (expected.starts_with('F') || expected.starts_with('l')) {
+            Ok(Some(expected))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_etag(etag: Option<&str>) -> Option<String> {
+        etag.map(|etag| etag.trim_matches('"').to_owned())
+            .filter(|etag| !etag.is_empty())
+    }
+
+    fn range_end(range_number: usize, range_size: u64, file_size: u64) -> u64 {
+        ((range_number as u64 + 1) * range_size).min(file_size)
+    }
+
+    fn part_file_path(file_path: &Path) -> PathBuf {
+        let mut part_file_path = OsString::from(file_path.as_os_str());
+        part_file_path.push(PART_FILE_SUFFIX);
+        part_file_path.into()
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+struct EtagWriter<W: Write> {
+    inner: W,
+    etag: etag::Etag,
+}
+
+impl<W: Write> Write for EtagWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IOResult<usize> {
+        let n = self.inner.write(buf)?;
+        self.etag.input(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> IOResult<()> {
+        self.inner.flush()
+    }
+}
+
+/// 下载错误
+#[derive(Error, Debug)]
+pub enum DownloadError {
+    /// 写入下载数据时发生 IO 错误
+    #[error("Failed to do local io operation during downloading: {0}")]
+    IOError(#[from] IOError),
+    /// 调用七牛 API 下载时发生错误
+    #[error("Qiniu API call error: {0}")]
+    HTTPError(#[from] HTTPError),
+    /// 生成下载地址错误
+    #[error("Failed to build download url: {0}")]
+    DownloadUrlError(#[from] DownloadUrlError),
+    /// 下载内容的 Etag 与对象的 Etag 不一致
+    #[error("Etag mismatched, expected: {expected}, actual: {actual}")]
+    EtagMismatch {
+        /// 对象的 Etag
+        expected: String,
+        /// 下载内容的 Etag
+        actual: String,
+    },
+}
+
+/// 下载结果
+pub type DownloadResult<T> = Result<T, DownloadError>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            super::{
+                bucket::{Bucket, BucketBuilder},
+                recorder::FileSystemRecorder,
+                uploader::UploadManager,
+            },
+            DownloadRecorderBuilder,
+        },
+        *,
+    };
+    use crate::{
+        config::ConfigBuilder,
+        credential::Credential,
+        http::{DomainsManagerBuilder, HTTPCaller},
+    };
+    use qiniu_http::ResponseBuilder;
+    use qiniu_test_utils::{
+        http_call_mock::{fake_req_id, CallHandlers, CounterCallMock, ErrorResponseMock},
+        temp_file::create_temp_file,
+    };
+    use std::{
+        env::temp_dir,
+        error::Error,
+        fs::{read, write},
+        sync::Arc,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_storage_downloader_resume_file() -> Result<(), Box<dyn Error>> {
+        let data = (0..10000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        let etag = etag::from_bytes(&data);
+        let file_path = create_temp_file(0)?.into_temp_path();
+        let part_file_path = Downloader::part_file_path(&file_path);
+        write(&part_file_path, &data[..4000])?;
+
+        let response_data = data.to_owned();
+        let response_etag = etag.to_owned();
+        let bucket = get_bucket(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"^https://def\.com/test-key$", move |request, _| {
+                assert_eq!(
+                    request.headers().get(&"Range".into()).map(|range| range.as_ref()),
+                    Some("bytes=4000-")
+                );
+                Ok(ResponseBuilder::default()
+                    .status_code(206u16)
+                    .header("ETag", format!("\"{}\"", response_etag))
+                    .header("Content-Range", "bytes 4000-9999/10000")
+                    .header("X-Reqid", fake_req_id())
+                    .bytes_as_body(&response_data[4000..])
+                    .build())
+            }),
+        );
+        assert_eq!(bucket.downloader("test-key").download_to_file(&file_path)?, 10000);
+        assert_eq!(read(&file_path)?, data);
+        assert!(!part_file_path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_downloader_failover() -> Result<(), Box<dyn Error>> {
+        let data = b"hello world".to_vec();
+        let etag = etag::from_bytes(&data);
+        let bucket = get_bucket(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"^https://def\.com/test-key$", |request, _| {
+                ErrorResponseMock::new(503, "service unavailable").call(request)
+            })
+            .install(Method::GET, r"^https://abc\.com/test-key$", move |_, _| {
+                Ok(ResponseBuilder::default()
+                    .status_code(200u16)
+                    .header("ETag", format!("\"{}\"", etag))
+                    .header("X-Reqid", fake_req_id())
+                    .bytes_as_body(data.to_owned())
+                    .build())
+            }),
+        );
+        let mut buf = Vec::new();
+        assert_eq!(bucket.downloader("test-key").download_to_writer(&mut buf)?, 11);
+        assert_eq!(buf, b"hello world");
+        assert!(bucket.config().domains_manager().is_frozen_url("https://def.com")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_downloader_etag_mismatch() -> Result<(), Box<dyn Error>> {
+        let bucket = get_bucket(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"/test-key$", |_, _| {
+                Ok(ResponseBuilder::default()
+                    .status_code(200u16)
+                    .header("ETag", "\"FhOPGUW8AaIdsdUGjnj-8jDTYqtk\"")
+                    .header("X-Reqid", fake_req_id())
+                    .bytes_as_body("hello world")
+                    .build())
+            }),
+        );
+        match bucket.downloader("test-key").download_to_writer(Vec::new()) {
+            Err(DownloadError::EtagMismatch { expected, actual }) => {
+                assert_eq!(expected, "FhOPGUW8AaIdsdUGjnj-8jDTYqtk");
+                assert_eq!(actual, etag::from_bytes("hello world"));
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert_eq!(
+            bucket
+                .downloader("test-key")
+                .disable_checksum()
+                .download_to_writer(Vec::new())?,
+            11
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_downloader_download_concurrently() -> Result<(), Box<dyn Error>> {
+        let data = Arc::new((0..10000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>());
+        let etag = etag::from_bytes(data.as_ref());
+        let file_path = create_temp_file(0)?.into_temp_path();
+        let mock = CounterCallMock::new(ranges_mock(data.to_owned(), etag, |_| {}));
+        let bucket = get_bucket_with_recorder(mock.clone());
+        assert_eq!(
+            bucket
+                .downloader("test-key")
+                .thread_pool_size(3)
+                .range_size(3000)
+                .download_to_file_concurrently(&file_path)?,
+            10000
+        );
+        assert_eq!(&read(&file_path)?, data.as_ref());
+        assert_eq!(mock.call_called(), 5);
+        assert!(!Downloader::part_file_path(&file_path).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_downloader_resume_concurrently() -> Result<(), Box<dyn Error>> {
+        let data = Arc::new((0..10000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>());
+        let etag = etag::from_bytes(data.as_ref());
+        let file_path = create_temp_file(0)?.into_temp_path();
+        let mock = CounterCallMock::new(ranges_mock(data.to_owned(), etag.to_owned(), |range| {
+            assert!(range != "bytes=0-2999" && range != "bytes=6000-8999");
+        }));
+        let bucket = get_bucket_with_recorder(mock.clone());
+        {
+            let mut part_data = vec![0u8; 10000];
+            part_data[..3000].copy_from_slice(&data[..3000]);
+            part_data[6000..9000].copy_from_slice(&data[6000..9000]);
+            write(Downloader::part_file_path(&file_path), &part_data)?;
+            let medium = bucket.config().download_recorder().open_and_write_metadata(
+                &file_path,
+                Some("test-key"),
+                10000,
+                &etag,
+                3000,
+            )?;
+            medium.append(0)?;
+            medium.append(2)?;
+        }
+        bucket
+            .downloader("test-key")
+            .thread_pool_size(2)
+            .range_size(3000)
+            .download_to_file_concurrently(&file_path)?;
+        assert_eq!(&read(&file_path)?, data.as_ref());
+        assert_eq!(mock.call_called(), 3);
+        Ok(())
+    }
+
+    fn ranges_mock(
+        data: Arc<Vec<u8>>,
+        etag: String,
+        check_range: impl Fn(&str) + Send + Sync + 'static,
+    ) -> CallHandlers {
+        let head_etag = etag.to_owned();
+        CallHandlers::new(|request| {
+            panic!("Unexpected Request: {} {}", request.method(), request.url());
+        })
+        .install(Method::HEAD, r"/test-key$", move |_, _| {
+            Ok(ResponseBuilder::default()
+                .status_code(200u16)
+                .header("ETag", format!("\"{}\"", head_etag))
+                .header("Content-Length", "10000")
+                .header("X-Reqid", fake_req_id())
+                .build())
+        })
+        .install(Method::GET, r"/test-key$", move |request, _| {
+            let range = request.headers().get(&"Range".into()).unwrap().to_string();
+            check_range(&range);
+            let mut range_parts = range.trim_start_matches("bytes=").split('-');
+            let start: usize = range_parts.next().unwrap().parse().unwrap();
+            let end: usize = range_parts.next().unwrap().parse().unwrap();
+            Ok(ResponseBuilder::default()
+                .status_code(206u16)
+                .header("ETag", format!("\"{}\"", etag))
+                .header("Content-Range", format!("bytes {}-{}/{}", start, end, data.len()))
+                .header("X-Reqid", fake_req_id())
+                .bytes_as_body(&data[start..=end])
+                .build())
+        })
+    }
+
+    fn get_bucket_with_recorder(handler: impl HTTPCaller + 'static) -> Bucket<'static> {
+        BucketBuilder::new(
+            "test-bucket".into(),
+            Credential::new("abcdefghklmnopq", "1234567890").into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .http_request_retries(1)
+                    .http_request_retry_delay(Duration::from_secs(0))
+                    .download_recorder(
+                        DownloadRecorderBuilder::default()
+                            .recorder(FileSystemRecorder::from(temp_dir()))
+                            .build(),
+                    )
+                    .domains_manager(
+                        DomainsManagerBuilder::default()
+                            .disable_url_resolution()
+                            .disable_persistent()
+                            .build(),
+                    )
+                    .http_request_handler(handler)
+                    .build(),
+            ),
+        )
+        .prepend_domain("def.com")
+        .build()
+    }
+
+    fn get_bucket(handler: impl HTTPCaller + 'static) -> Bucket<'static> {
+        BucketBuilder::new(
+            "test-bucket".into(),
+            Credential::new("abcdefghklmnopq", "1234567890").into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .http_request_retries(1)
+                    .http_request_retry_delay(Duration::from_secs(0))
+                    .domains_manager(
+                        DomainsManagerBuilder::default()
+                            .disable_url_resolution()
+                            .disable_persistent()
+                            .build(),
+                    )
+                    .http_request_handler(handler)
+                    .build(),
+            ),
+        )
+        .prepend_domain("abc.com")
+        .prepend_domain("def.com")
+        .build()
+    }
+}
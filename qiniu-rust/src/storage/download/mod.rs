@@ -0,0 +1,11 @@
+//! 下载模块
+//!
+//! 提供下载地址生成，对象下载，分片下载进度记录等下载相关功能
+
+mod download_recorder;
+mod downloader;
+mod url_builder;
+
+pub use download_recorder::{DownloadRecorder, DownloadRecorderBuilder};
+pub use downloader::{DownloadError, DownloadResult, Downloader};
+pub use url_builder::{DownloadUrlBuilder, DownloadUrlError, DownloadUrlResult};
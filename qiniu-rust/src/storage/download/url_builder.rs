@@ -0,0 +1,377 @@
+use super::{super::bucket::Bucket, Downloader};
+use crate::{cdn::TimestampSigner, http::Error as HTTPError};
+use assert_impl::assert_impl;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::{
+    borrow::Cow,
+    result::Result,
+    time::{Duration, SystemTime, SystemTimeError},
+};
+use thiserror::Error;
+use url::{ParseError as URLParseError, Url};
+
+const KEY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b'/');
+
+/// 下载地址生成器
+///
+/// 通过 `Bucket::download_url` 获取。
+/// 对于公开空间，直接生成下载地址即可；对于私有空间，则需要调用 `private` 方法，SDK 将为下载地址签名。
+/// 如果下载域名开启了 CDN 时间戳防盗链，则需要调用 `timestamp_anti_leech` 方法
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let bucket = client.storage().bucket("[Bucket name]").build();
+/// let public_url = bucket.download_url("[Key]").build()?;
+/// let private_url = bucket.download_url("[Key]").fop("imageView2/2/w/200").private().build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DownloadUrlBuilder<'a, 'r: 'a> {
+    bucket: &'a Bucket<'r>,
+    key: Cow<'a, str>,
+    domain: Option<Cow<'a, str>>,
+    fop: Option<Cow<'a, str>>,
+    style: Option<Cow<'a, str>>,
+    style_separator: Cow<'a, str>,
+    private: bool,
+    deadline: Option<SystemTime>,
+    timestamp_signer: Option<(TimestampSigner<'a>, SystemTime)>,
+}
+
+impl<'a, 'r: 'a> DownloadUrlBuilder<'a, 'r> {
+    pub(crate) fn new(bucket: &'a Bucket<'r>, key: Cow<'a, str>) -> DownloadUrlBuilder<'a, 'r> {
+        DownloadUrlBuilder {
+            bucket,
+            key,
+            domain: None,
+            fop: None,
+            style: None,
+            style_separator: Cow::Borrowed("-"),
+            private: false,
+            deadline: None,
+            timestamp_signer: None,
+        }
+    }
+
+    /// 指定下载域名
+    ///
+    /// 默认将使用存储空间的第一个下载域名。
+    /// 域名可以包含协议，如果不包含协议，则将根据 `Config::use_https` 决定使用的协议
+    pub fn domain(mut self, domain: impl Into<Cow<'a, str>>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// 指定数据处理指令
+    ///
+    /// 数据处理指令将作为下载地址的查询字符串
+    pub fn fop(mut self, fop: impl Into<Cow<'a, str>>) -> Self {
+        self.fop = Some(fop.into());
+        self
+    }
+
+    /// 指定图片样式
+    ///
+    /// 样式将通过样式分隔符附加在对象名称之后
+    pub fn style(mut self, style: impl Into<Cow<'a, str>>) -> Self {
+        self.style = Some(style.into());
+        self
+    }
+
+    /// 指定样式分隔符
+    ///
+    /// 默认为 `-`
+    pub fn style_separator(mut self, style_separator: impl Into<Cow<'a, str>>) -> Self {
+        self.style_separator = style_separator.into();
+        self
+    }
+
+    /// 生成私有空间下载地址
+    ///
+    /// 下载地址将被签名，默认有效期为 `Config::download_url_lifetime`
+    pub fn private(mut self) -> Self {
+        self.private = true;
+        self
+    }
+
+    /// 指定私有空间下载地址的有效期
+    ///
+    /// 调用该方法将隐含调用 `private` 方法
+    pub fn lifetime(mut self, lifetime: Duration) -> Self {
+        self.private = true;
+        self.deadline = Some(SystemTime::now() + lifetime);
+        self
+    }
+
+    /// 指定私有空间下载地址的过期时间
+    ///
+    /// 调用该方法将隐含调用 `private` 方法
+    pub fn deadline(mut self, deadline: SystemTime) -> Self {
+        self.private = true;
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// 为下载地址添加 CDN 时间戳防盗链签名
+    ///
+    /// `key` 为 CDN 域名配置的防盗链密钥，`lifetime` 为签名的有效期。
+    /// 该签名与私有空间签名相互独立，如果同时调用了 `private` 方法，则将先进行私有空间签名，再进行时间戳防盗链签名
+    pub fn timestamp_anti_leech(mut self, key: impl Into<Cow<'a, str>>, lifetime: Duration) -> Self {
+        self.timestamp_signer = Some((TimestampSigner::new(key), SystemTime::now() + lifetime));
+        self
+    }
+
+    /// 创建下载器
+    ///
+    /// 下载器将使用当前生成器的设置生成下载地址
+    pub fn downloader(self) -> Downloader<'a, 'r> {
+        Downloader::new(self)
+    }
+
+    /// 生成下载地址
+    pub fn build(self) -> DownloadUrlResult<String> {
+        let domains = self.domains()?;
+        self.build_with_domain(domains.first().ok_or(DownloadUrlError::NoDomainAvailable)?)
+    }
+
+    pub(super) fn build_with_domain(&self, domain: &str) -> DownloadUrlResult<String> {
+        let mut url = self.unsigned_url(domain);
+        if self.private {
+            let deadline = self
+                .deadline
+                .unwrap_or_else(|| SystemTime::now() + self.bucket.config().download_url_lifetime());
+            url = self
+                .bucket
+                .credential()
+                .sign_download_url_with_deadline(Url::parse(&url)?, deadline, false)?;
+        }
+        if let Some((signer, deadline)) = &self.timestamp_signer {
+            url = signer.sign_with_deadline(&url, *deadline)?;
+        }
+        Ok(url)
+    }
+
+    pub(super) fn domains(&self) -> DownloadUrlResult<Vec<&str>> {
+        match &self.domain {
+            Some(domain) => Ok(vec![domain.as_ref()]),
+            None => Ok(self.bucket.domains()?),
+        }
+    }
+
+    pub(super) fn bucket(&self) -> &'a Bucket<'r> {
+        self.bucket
+    }
+
+    pub(super) fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub(super) fn is_processed(&self) -> bool {
+        self.fop.is_some() || self.style.is_some()
+    }
+
+    fn unsigned_url(&self, domain: &str) -> String {
+        let mut url = String::with_capacity(1024);
+        if !domain.contains("://") {
+            url.push_str(if self.bucket.config().use_https() {
+                "https://"
+            } else {
+                "http://"
+            });
+        }
+        url.push_str(domain.trim_end_matches('/'));
+        url.push('/');
+        url.extend(utf8_percent_encode(&self.key, KEY_ENCODE_SET));
+        if let Some(style) = &self.style {
+            url.push_str(&self.style_separator);
+            url.extend(utf8_percent_encode(style, KEY_ENCODE_SET));
+        }
+        if let Some(fop) = &self.fop {
+            url.push('?');
+            url.push_str(fop);
+        }
+        url
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+/// 生成下载地址错误
+#[derive(Error, Debug)]
+pub enum DownloadUrlError {
+    /// 七牛 API 调用错误
+    #[error("Qiniu API call error: {0}")]
+    HTTPError(#[from] HTTPError),
+    /// 存储空间没有可用的下载域名
+    #[error("No domain is available for the bucket")]
+    NoDomainAvailable,
+    /// 下载地址解析错误
+    #[error("Failed to parse download url: {0}")]
+    URLParseError(#[from] URLParseError),
+    /// 过期时间错误
+    #[error("Invalid deadline: {0}")]
+    SystemTimeError(#[from] SystemTimeError),
+}
+
+/// 生成下载地址结果
+pub type DownloadUrlResult<T> = Result<T, DownloadUrlError>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::super::{
+            bucket::BucketBuilder,
+            processing::{ImageMogr2, ImageView2, Pipeline},
+            uploader::UploadManager,
+        },
+        *,
+    };
+    use crate::{
+        config::ConfigBuilder,
+        credential::Credential,
+        http::{DomainsManagerBuilder, Headers, PanickedHTTPCaller},
+    };
+    use qiniu_test_utils::http_call_mock::JSONCallMock;
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_storage_download_public_url() -> Result<(), Box<dyn Error>> {
+        let bucket = get_bucket();
+        assert_eq!(
+            bucket.download_url("test/中文 key+1.png").build()?,
+            "https://def.com/test/%E4%B8%AD%E6%96%87%20key%2B1.png"
+        );
+        assert_eq!(
+            bucket
+                .download_url("test-key")
+                .domain("http://ghi.com/")
+                .fop("imageView2/2/w/200")
+                .build()?,
+            "http://ghi.com/test-key?imageView2/2/w/200"
+        );
+        assert_eq!(
+            bucket
+                .download_url("test-key")
+                .fop(Pipeline::from(ImageView2::new(1).width(200)).then(ImageMogr2::new().strip()))
+                .build()?,
+            "https://def.com/test-key?imageView2/1/w/200|imageMogr2/strip"
+        );
+        assert_eq!(
+            bucket.download_url("test-key").style("thumb").build()?,
+            "https://def.com/test-key-thumb"
+        );
+        assert_eq!(
+            bucket
+                .download_url("test-key")
+                .style("thumb")
+                .style_separator("!")
+                .build()?,
+            "https://def.com/test-key!thumb"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_download_private_url() -> Result<(), Box<dyn Error>> {
+        let bucket = get_bucket();
+        let deadline = SystemTime::UNIX_EPOCH + Duration::from_secs(1_234_567_890 + 3600);
+        assert_eq!(
+            bucket
+                .download_url("test-key")
+                .domain("http://www.qiniu.com")
+                .deadline(deadline)
+                .build()?,
+            get_credential().sign_download_url_with_deadline(
+                Url::parse("http://www.qiniu.com/test-key")?,
+                deadline,
+                false
+            )?,
+        );
+        let url = bucket.download_url("test-key").fop("imageInfo").private().build()?;
+        assert!(url.starts_with("https://def.com/test-key?imageInfo&e="));
+        assert!(url.contains("&token=abcdefghklmnopq:"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_download_timestamp_anti_leech_url() -> Result<(), Box<dyn Error>> {
+        let bucket = get_bucket();
+        let url = bucket
+            .download_url("test/中文 key.mp4")
+            .fop("avinfo")
+            .timestamp_anti_leech("anti-leech-key", Duration::from_secs(3600))
+            .build()?;
+        assert!(url.starts_with("https://def.com/test/%E4%B8%AD%E6%96%87%20key.mp4?avinfo&sign="));
+        let timestamp = url.rsplit("&t=").next().unwrap();
+        let sign = url.split("&sign=").nth(1).unwrap().split('&').next().unwrap();
+        assert_eq!(
+            sign,
+            format!(
+                "{:x}",
+                md5::compute(format!("anti-leech-key/test/%E4%B8%AD%E6%96%87%20key.mp4{}", timestamp))
+            )
+        );
+
+        let url = bucket
+            .download_url("test-key")
+            .private()
+            .timestamp_anti_leech("anti-leech-key", Duration::from_secs(3600))
+            .build()?;
+        assert!(url.starts_with("https://def.com/test-key?e="));
+        assert!(url.contains("&token=abcdefghklmnopq:"));
+        assert!(url.contains("&sign="));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_download_url_without_domain() {
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(
+                ConfigBuilder::default()
+                    .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                    .http_request_handler(JSONCallMock::new(200, Headers::new(), Vec::<String>::new()))
+                    .build(),
+            ),
+        )
+        .build();
+        match bucket.download_url("test-key").build() {
+            Err(DownloadUrlError::NoDomainAvailable) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    fn get_bucket() -> Bucket<'static> {
+        BucketBuilder::new("test-bucket".into(), get_credential().into(), get_upload_manager())
+            .prepend_domain("abc.com")
+            .prepend_domain("def.com")
+            .build()
+    }
+
+    fn get_upload_manager() -> UploadManager {
+        UploadManager::new(
+            ConfigBuilder::default()
+                .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                .http_request_handler(PanickedHTTPCaller("Should not call it"))
+                .build(),
+        )
+    }
+
+    fn get_credential() -> Credential {
+        Credential::new("abcdefghklmnopq", "1234567890")
+    }
+}
@@ -0,0 +1,232 @@
+//! 远程抓取模块
+//!
+//! 提供从指定 URL 抓取资源并存储到存储空间的功能，支持同步抓取和异步抓取
+
+use super::{bucket::Bucket, object::FileType};
+use crate::http::{Result, TokenVersion};
+use assert_impl::assert_impl;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// 同步抓取结果
+///
+/// 通过 `Bucket::fetch` 获取
+#[derive(Deserialize, Getters, CopyGetters, Debug, Clone)]
+pub struct FetchResult {
+    /// 抓取后存储的对象名称
+    #[get = "pub"]
+    key: String,
+
+    /// 对象 Etag
+    #[get = "pub"]
+    hash: String,
+
+    /// 对象 MIME 类型
+    #[serde(rename = "mimeType", default)]
+    #[get = "pub"]
+    mime_type: String,
+
+    /// 对象大小，单位为字节
+    #[serde(rename = "fsize", default)]
+    #[get_copy = "pub"]
+    size: u64,
+}
+
+/// 异步抓取任务
+///
+/// 通过 `AsyncFetchBuilder::send` 或 `Bucket::async_fetch_status` 获取
+#[derive(Deserialize, Getters, Debug, Clone)]
+pub struct AsyncFetchJob {
+    /// 异步抓取任务 ID，可以用于查询任务状态
+    #[get = "pub"]
+    id: String,
+
+    wait: i64,
+}
+
+impl AsyncFetchJob {
+    /// 当前任务之前正在排队的任务数量
+    ///
+    /// 返回 `Some(0)` 表示当前任务正在处理，返回 `None` 表示当前任务已经至少被处理过一次
+    pub fn queued_tasks(&self) -> Option<u64> {
+        if self.wait >= 0 {
+            Some(self.wait as u64)
+        } else {
+            None
+        }
+    }
+
+    /// 当前任务是否已经至少被处理过一次
+    ///
+    /// 注意，处理过的任务依然可能因为失败而进入重试
+    pub fn is_processed(&self) -> bool {
+        self.wait < 0
+    }
+}
+
+#[derive(Serialize)]
+struct AsyncFetchRequest<'a> {
+    url: Cow<'a, str>,
+    bucket: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    md5: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    etag: Option<Cow<'a, str>>,
+    #[serde(rename = "callbackurl", skip_serializing_if = "Option::is_none")]
+    callback_url: Option<Cow<'a, str>>,
+    #[serde(rename = "callbackbody", skip_serializing_if = "Option::is_none")]
+    callback_body: Option<Cow<'a, str>>,
+    #[serde(rename = "callbackbodytype", skip_serializing_if = "Option::is_none")]
+    callback_body_type: Option<Cow<'a, str>>,
+    #[serde(rename = "callbackhost", skip_serializing_if = "Option::is_none")]
+    callback_host: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_type: Option<u8>,
+    #[serde(skip_serializing_if = "is_false")]
+    ignore_same_key: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// 异步抓取生成器
+///
+/// 通过 `Bucket::async_fetch` 获取，异步抓取任务提交后将在七牛服务器上排队执行
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let bucket = client.storage().bucket("[Bucket name]").build();
+/// let job = bucket
+///     .async_fetch("https://www.example.com/video.mp4")
+///     .key("[Key]")
+///     .etag("[Etag]")
+///     .callback_url("https://www.example.com/callback")
+///     .send()?;
+/// let status = bucket.async_fetch_status(job.id())?;
+/// println!("processed: {}", status.is_processed());
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncFetchBuilder<'a, 'r: 'a> {
+    bucket: &'a Bucket<'r>,
+    request: AsyncFetchRequest<'a>,
+}
+
+impl<'a, 'r: 'a> AsyncFetchBuilder<'a, 'r> {
+    pub(super) fn new(bucket: &'a Bucket<'r>, url: Cow<'a, str>) -> AsyncFetchBuilder<'a, 'r> {
+        AsyncFetchBuilder {
+            request: AsyncFetchRequest {
+                url,
+                bucket: Cow::Borrowed(bucket.name()),
+                host: None,
+                key: None,
+                md5: None,
+                etag: None,
+                callback_url: None,
+                callback_body: None,
+                callback_body_type: None,
+                callback_host: None,
+                file_type: None,
+                ignore_same_key: false,
+            },
+            bucket,
+        }
+    }
+
+    /// 指定抓取后存储的对象名称
+    ///
+    /// 默认将使用抓取资源的 Etag 作为对象名称
+    pub fn key(mut self, key: impl Into<Cow<'a, str>>) -> Self {
+        self.request.key = Some(key.into());
+        self
+    }
+
+    /// 指定抓取资源时使用的 Host 头
+    pub fn host(mut self, host: impl Into<Cow<'a, str>>) -> Self {
+        self.request.host = Some(host.into());
+        self
+    }
+
+    /// 指定抓取资源的 MD5，抓取完毕后将校验资源内容
+    pub fn md5(mut self, md5: impl Into<Cow<'a, str>>) -> Self {
+        self.request.md5 = Some(md5.into());
+        self
+    }
+
+    /// 指定抓取资源的七牛 Etag，抓取完毕后将校验资源内容
+    pub fn etag(mut self, etag: impl Into<Cow<'a, str>>) -> Self {
+        self.request.etag = Some(etag.into());
+        self
+    }
+
+    /// 指定抓取完毕后的回调地址
+    pub fn callback_url(mut self, callback_url: impl Into<Cow<'a, str>>) -> Self {
+        self.request.callback_url = Some(callback_url.into());
+        self
+    }
+
+    /// 指定回调请求体
+    ///
+    /// 请求体中可以使用魔法变量
+    pub fn callback_body(mut self, callback_body: impl Into<Cow<'a, str>>) -> Self {
+        self.request.callback_body = Some(callback_body.into());
+        self
+    }
+
+    /// 指定回调请求体的 MIME 类型
+    pub fn callback_body_type(mut self, callback_body_type: impl Into<Cow<'a, str>>) -> Self {
+        self.request.callback_body_type = Some(callback_body_type.into());
+        self
+    }
+
+    /// 指定回调请求的 Host 头
+    pub fn callback_host(mut self, callback_host: impl Into<Cow<'a, str>>) -> Self {
+        self.request.callback_host = Some(callback_host.into());
+        self
+    }
+
+    /// 指定抓取后对象的存储类型
+    pub fn file_type(mut self, file_type: FileType) -> Self {
+        self.request.file_type = Some(file_type.as_u8());
+        self
+    }
+
+    /// 如果存储空间中已经存在同名对象，则放弃抓取
+    ///
+    /// 默认将覆盖同名对象
+    pub fn ignore_same_key(mut self) -> Self {
+        self.request.ignore_same_key = true;
+        self
+    }
+
+    /// 提交异步抓取任务
+    pub fn send(self) -> Result<AsyncFetchJob> {
+        let api_urls = self.bucket.api_urls();
+        let api_urls = api_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        self.bucket
+            .http_client()
+            .post("/sisyphus/fetch", &api_urls)
+            .token(TokenVersion::V2, self.bucket.credential().into())
+            .accept_json()
+            .json_body(&self.request)
+            .unwrap()
+            .send()?
+            .parse_json()
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
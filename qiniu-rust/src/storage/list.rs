@@ -0,0 +1,409 @@
+//! 对象列举模块
+//!
+//! 提供存储空间中对象的列举功能
+
+use super::object::{FileType, ObjectInfo};
+use crate::{
+    credential::Credential,
+    http::{Client, Result, TokenVersion},
+};
+use assert_impl::assert_impl;
+use delegate::delegate;
+use serde::Deserialize;
+use std::{borrow::Cow, collections::VecDeque, iter::Iterator, time::SystemTime};
+
+/// 对象列举生成器
+///
+/// 通过 `Bucket::list` 获取，用于设置列举参数
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let bucket = client.storage().bucket("[Bucket name]").build();
+/// for object in bucket.list().prefix("images/").iter() {
+///     let object = object?;
+///     println!("key: {}, size: {}", object.key(), object.size());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ListBuilder<'b> {
+    bucket: Cow<'b, str>,
+    credential: Cow<'b, Credential>,
+    rsf_urls: Vec<Cow<'static, str>>,
+    prefix: Option<Cow<'b, str>>,
+    delimiter: Option<Cow<'b, str>>,
+    limit: Option<usize>,
+    marker: Option<Cow<'b, str>>,
+    http_client: Client,
+}
+
+/// 对象列举迭代器
+///
+/// 迭代器将自动根据七牛服务器返回的位置标记获取下一页对象，直到所有对象列举完毕。
+/// 一旦发生错误，迭代器将在返回错误后终止，此时可以通过 `marker` 方法获取位置标记，用于重新列举。
+/// 如果需要保存列举进度，应当同时保存 `marker` 和 `is_finished` 的返回值
+pub struct ListIter<'b> {
+    builder: ListBuilder<'b>,
+    current_marker: Option<String>,
+    next_marker: Option<String>,
+    objects: VecDeque<ListedObject>,
+    common_prefixes: Vec<String>,
+    last_page_listed: bool,
+    terminated: bool,
+}
+
+/// 被列举的对象
+#[derive(Deserialize, Debug, Clone)]
+pub struct ListedObject {
+    key: String,
+    #[serde(flatten)]
+    info: ObjectInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListResponse {
+    #[serde(default)]
+    marker: Option<String>,
+    #[serde(default)]
+    common_prefixes: Vec<String>,
+    #[serde(default)]
+    items: Vec<ListedObject>,
+}
+
+impl<'b> ListBuilder<'b> {
+    pub(crate) fn new(
+        bucket: Cow<'b, str>,
+        credential: Cow<'b, Credential>,
+        rsf_urls: Vec<Cow<'static, str>>,
+        http_client: Client,
+    ) -> ListBuilder<'b> {
+        ListBuilder {
+            bucket,
+            credential,
+            rsf_urls,
+            http_client,
+            prefix: None,
+            delimiter: None,
+            limit: None,
+            marker: None,
+        }
+    }
+
+    /// 仅列举指定前缀的对象
+    pub fn prefix(mut self, prefix: impl Into<Cow<'b, str>>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// 指定目录分隔符
+    ///
+    /// 指定后，列举结果中将不包含“子目录”中的对象，“子目录”将作为公共前缀通过 `ListIter::common_prefixes` 返回
+    pub fn delimiter(mut self, delimiter: impl Into<Cow<'b, str>>) -> Self {
+        self.delimiter = Some(delimiter.into());
+        self
+    }
+
+    /// 每次请求列举的最大对象数量
+    ///
+    /// 七牛服务器限制该值的范围为 1 到 1000，默认为 1000
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 从指定的位置标记开始列举
+    ///
+    /// 可以将之前列举中断时由 `ListIter::marker` 返回的位置标记传入，从而继续列举
+    pub fn marker(mut self, marker: impl Into<Cow<'b, str>>) -> Self {
+        self.marker = Some(marker.into());
+        self
+    }
+
+    /// 生成对象列举迭代器
+    pub fn iter(self) -> ListIter<'b> {
+        let marker = self.marker.as_ref().map(|marker| marker.to_string());
+        ListIter {
+            builder: self,
+            current_marker: marker.to_owned(),
+            next_marker: marker,
+            objects: VecDeque::new(),
+            common_prefixes: Vec::new(),
+            last_page_listed: false,
+            terminated: false,
+        }
+    }
+
+    fn list(&self, marker: Option<&str>) -> Result<ListResponse> {
+        let rsf_urls = self.rsf_urls.iter().map(|url| url.as_ref()).collect::<Vec<_>>();
+        let mut builder = self
+            .http_client
+            .get("/list", &rsf_urls)
+            .query("bucket", self.bucket.as_ref())
+            .token(TokenVersion::V2, self.credential.as_ref().into())
+            .idempotent()
+            .accept_json();
+        if let Some(prefix) = &self.prefix {
+            builder = builder.query("prefix", prefix.as_ref());
+        }
+        if let Some(delimiter) = &self.delimiter {
+            builder = builder.query("delimiter", delimiter.as_ref());
+        }
+        if let Some(limit) = self.limit {
+            builder = builder.query("limit", limit.to_string());
+        }
+        if let Some(marker) = marker {
+            builder = builder.query("marker", marker);
+        }
+        builder.no_body().send()?.parse_json()
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+impl<'b> ListIter<'b> {
+    /// 位置标记
+    ///
+    /// 使用该位置标记重新列举，可以确保不会遗漏任何尚未被迭代器返回的对象，但可能会重复返回部分已经返回过的对象。
+    ///
+    /// 仅当 `is_finished` 返回 `false` 时，返回 `None` 才表示需要从头开始列举。
+    /// 如果列举已经完成，则无需再重新列举，此时该方法同样返回 `None`
+    pub fn marker(&self) -> Option<&str> {
+        if self.objects.is_empty() {
+            self.next_marker.as_ref().map(String::as_str)
+        } else {
+            self.current_marker.as_ref().map(String::as_str)
+        }
+    }
+
+    /// 列举是否已经完成
+    ///
+    /// 仅当所有对象都已经从七牛服务器获取并被迭代器返回后，才会返回 `true`。
+    /// 因发生错误而终止的迭代器将返回 `false`，此时可以使用 `marker` 返回的位置标记重新列举
+    pub fn is_finished(&self) -> bool {
+        self.last_page_listed && self.objects.is_empty()
+    }
+
+    /// 目前为止获取到的所有公共前缀
+    ///
+    /// 仅当设置了目录分隔符时才会有值
+    pub fn common_prefixes(&self) -> &[String] {
+        &self.common_prefixes
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+impl Iterator for ListIter<'_> {
+    type Item = Result<ListedObject>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(object) = self.objects.pop_front() {
+                return Some(Ok(object));
+            }
+            if self.last_page_listed || self.terminated {
+                return None;
+            }
+            match self.builder.list(self.next_marker.as_ref().map(String::as_str)) {
+                Ok(response) => {
+                    self.current_marker = self.next_marker.take();
+                    self.objects.extend(response.items);
+                    self.common_prefixes.extend(response.common_prefixes);
+                    match response.marker {
+                        Some(marker) if !marker.is_empty() => self.next_marker = Some(marker),
+                        _ => self.last_page_listed = true,
+                    }
+                }
+                Err(err) => {
+                    self.terminated = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+impl ListedObject {
+    /// 对象名称
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 对象元信息
+    pub fn info(&self) -> &ObjectInfo {
+        &self.info
+    }
+
+    delegate! {
+        target self.info {
+            /// 文件大小，单位为字节
+            pub fn size(&self) -> u64;
+            /// 文件 Etag
+            pub fn hash(&self) -> &String;
+            /// 文件 MIME 类型
+            pub fn mime_type(&self) -> &String;
+            /// 文件上传时间
+            pub fn put_time(&self) -> SystemTime;
+            /// 文件存储类型
+            pub fn file_type(&self) -> FileType;
+            /// 文件 MD5 值
+            pub fn md5(&self) -> Option<&str>;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ConfigBuilder,
+        http::{DomainsManagerBuilder, HTTPCaller, Method},
+    };
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock, ErrorResponseMock};
+    use serde_json::{json, Value};
+    use std::{error::Error, result::Result};
+    use url::Url;
+
+    #[test]
+    fn test_storage_list_objects() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"/list\?", |request, _| {
+                let url = Url::parse(request.url()).unwrap();
+                let query = url.query_pairs().into_owned().collect::<Vec<_>>();
+                assert!(query.contains(&("bucket".to_owned(), "test-bucket".to_owned())));
+                assert!(query.contains(&("prefix".to_owned(), "test-".to_owned())));
+                assert!(query.contains(&("delimiter".to_owned(), "/".to_owned())));
+                let marker = query
+                    .iter()
+                    .find(|(name, _)| name == "marker")
+                    .map(|(_, value)| value.as_str());
+                let body = match marker {
+                    None => json!({
+                        "marker": "marker-1",
+                        "commonPrefixes": ["test-dir-1/"],
+                        "items": [list_item("test-key-1"), list_item("test-key-2")],
+                    }),
+                    Some("marker-1") => json!({
+                        "marker": "marker-2",
+                        "commonPrefixes": ["test-dir-2/"],
+                        "items": [],
+                    }),
+                    Some("marker-2") => json!({
+                        "items": [list_item("test-key-3")],
+                    }),
+                    Some(marker) => panic!("Unexpected marker: {}", marker),
+                };
+                Ok(json_response(200, body))
+            }),
+        );
+        let mut iter = ListBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            vec!["https://rsf.qbox.me".into()],
+            get_client(mock.clone()),
+        )
+        .prefix("test-")
+        .delimiter("/")
+        .iter();
+        assert_eq!(iter.marker(), None);
+        assert!(!iter.is_finished());
+        assert_eq!(iter.next().unwrap()?.key(), "test-key-1");
+        assert_eq!(iter.marker(), None);
+        assert_eq!(iter.next().unwrap()?.key(), "test-key-2");
+        assert_eq!(iter.marker(), Some("marker-1"));
+        assert!(!iter.is_finished());
+        let object = iter.next().unwrap()?;
+        assert_eq!(object.key(), "test-key-3");
+        assert_eq!(object.size(), 1024);
+        assert_eq!(object.mime_type(), "text/plain");
+        // 最后一页已经获取且所有对象都已经返回，列举完成，此时位置标记为空但无需重新列举
+        assert!(iter.is_finished());
+        assert_eq!(iter.marker(), None);
+        assert!(iter.next().is_none());
+        assert!(iter.is_finished());
+        assert_eq!(
+            iter.common_prefixes(),
+            &["test-dir-1/".to_owned(), "test-dir-2/".to_owned()]
+        );
+        assert_eq!(mock.call_called(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_list_objects_from_marker_with_error() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"/list\?.*marker=marker-1", |_, _| {
+                Ok(json_response(
+                    200,
+                    json!({
+                        "marker": "marker-2",
+                        "items": [list_item("test-key-2")],
+                    }),
+                ))
+            })
+            .install(Method::GET, r"/list\?.*marker=marker-2", |request, _| {
+                ErrorResponseMock::new(631, "no such bucket").call(request)
+            }),
+        );
+        let mut iter = ListBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            vec!["https://rsf.qbox.me".into()],
+            get_client(mock.clone()),
+        )
+        .marker("marker-1")
+        .iter();
+        assert_eq!(iter.marker(), Some("marker-1"));
+        assert_eq!(iter.next().unwrap()?.key(), "test-key-2");
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        assert!(!iter.is_finished());
+        assert_eq!(iter.marker(), Some("marker-2"));
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+
+    fn list_item(key: &str) -> Value {
+        json!({
+            "key": key,
+            "fsize": 1024,
+            "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk",
+            "mimeType": "text/plain",
+            "putTime": 15_735_614_000_000_000u64,
+            "type": 0,
+            "status": 0,
+        })
+    }
+
+    fn get_client(mock: CounterCallMock<CallHandlers>) -> Client {
+        Client::new(
+            ConfigBuilder::default()
+                .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                .http_request_handler(mock)
+                .build(),
+        )
+    }
+
+    fn get_credential() -> Credential {
+        Credential::new("abcdefghklmnopq", "1234567890")
+    }
+}
@@ -2,11 +2,12 @@
 //!
 //! 封装存储相关管理功能
 
-use super::{bucket::BucketBuilder, uploader::UploadManager};
+use super::{batch::Batch, bucket::BucketBuilder, bucket_manager::BucketManager, uploader::UploadManager};
 use crate::{
     config::Config,
     credential::Credential,
     http::{Client, Error as HTTPError, ErrorKind as HTTPErrorKind, Result as HTTPResult, TokenVersion},
+    utils::base64,
 };
 use assert_impl::assert_impl;
 use std::{
@@ -20,15 +21,19 @@ use thiserror::Error;
 /// 封装存储相关管理功能
 #[derive(Clone)]
 pub struct StorageManager {
-    http_client: Client,
-    credential: Credential,
+    pub(super) http_client: Client,
+    pub(super) credential: Credential,
     rs_url: Box<str>,
+    pub(super) uc_url: Box<str>,
+    pub(super) api_url: Box<str>,
 }
 
 impl StorageManager {
     pub(crate) fn new(credential: Credential, config: Config) -> StorageManager {
         StorageManager {
             rs_url: config.rs_url().into(),
+            uc_url: config.uc_url().into(),
+            api_url: config.api_url().into(),
             credential,
             http_client: Client::new(config),
         }
@@ -85,6 +90,66 @@ impl StorageManager {
         Ok(())
     }
 
+    /// 设置存储空间的镜像源
+    ///
+    /// 当存储空间中不存在被访问的对象时，七牛将从镜像源 `source_url` 拉取对象并存储。
+    /// `host` 用于指定回源请求的 Host 头，如果传入 `None`，则使用镜像源的域名
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use qiniu_ng::{Client, Config};
+    /// # use std::{result::Result, error::Error};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+    /// client.storage().set_mirror_source("[Bucket name]", "https://origin.example.com", Some("www.example.com"))?;
+    /// client.storage().prefetch("[Bucket name]", "[Key]")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_mirror_source(
+        &self,
+        bucket: impl AsRef<str>,
+        source_url: impl AsRef<str>,
+        host: Option<&str>,
+    ) -> HTTPResult<()> {
+        let mut path =
+            "/image/".to_owned() + bucket.as_ref() + "/from/" + &base64::urlsafe(source_url.as_ref().as_bytes());
+        if let Some(host) = host {
+            path.push_str("/host/");
+            path.push_str(&base64::urlsafe(host.as_bytes()));
+        }
+        self.http_client
+            .post(&path, &[&self.uc_url])
+            .token(TokenVersion::V2, self.credential.borrow().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 取消存储空间的镜像源
+    pub fn unset_mirror_source(&self, bucket: impl AsRef<str>) -> HTTPResult<()> {
+        self.http_client
+            .post(&("/unimage/".to_owned() + bucket.as_ref()), &[&self.uc_url])
+            .token(TokenVersion::V2, self.credential.borrow().into())
+            .idempotent()
+            .no_body()
+            .send()?
+            .ignore_body();
+        Ok(())
+    }
+
+    /// 镜像回源预取
+    ///
+    /// 从镜像源拉取指定对象的最新内容并覆盖存储空间中的对象。
+    /// 该方法将查询存储空间所在区域，并使用该区域的 IO 服务器，与 `Bucket::prefetch` 相同
+    pub fn prefetch(&self, bucket: impl AsRef<str>, key: impl AsRef<str>) -> HTTPResult<()> {
+        self.bucket(bucket.as_ref()).build().prefetch(key)
+    }
+
     /// 删除存储空间
     ///
     /// 删除存储空间前务必保证存储空间里已经没有任何文件，否则删除将会失败。
@@ -121,6 +186,25 @@ impl StorageManager {
         BucketBuilder::new(bucket.into(), self.credential.borrow().into(), self.upload_manager())
     }
 
+    /// 获取存储空间管理器
+    ///
+    /// 用于读取和修改七牛服务器上存储空间的配置
+    pub fn bucket_manager<'b>(&'b self, bucket: impl Into<Cow<'b, str>>) -> BucketManager<'b> {
+        BucketManager::new(self, bucket.into())
+    }
+
+    /// 获取批量操作生成器
+    ///
+    /// 批量操作将针对指定存储空间中的对象，与 `Bucket::batch` 不同的是，该方法不会查询存储空间所在区域
+    pub fn batch<'b>(&'b self, bucket: impl Into<Cow<'b, str>>) -> Batch<'b> {
+        Batch::new(
+            bucket.into(),
+            self.credential.borrow().into(),
+            vec![Cow::Owned(self.rs_url.to_string())],
+            self.http_client.to_owned(),
+        )
+    }
+
     pub fn credential(&self) -> &Credential {
         &self.credential
     }
@@ -143,3 +227,47 @@ pub enum DropBucketError {
 
 /// 删除存储空间结果
 pub type DropBucketResult<T> = Result<T, DropBucketError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ConfigBuilder,
+        http::{DomainsManagerBuilder, Method},
+    };
+    use qiniu_http::ResponseBuilder;
+    use qiniu_test_utils::http_call_mock::{CallHandlers, CounterCallMock};
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_storage_manager_mirror_source() -> Result<(), Box<dyn Error>> {
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(
+                Method::POST,
+                "^https://uc\\.qbox\\.me/image/test-bucket/from/".to_owned()
+                    + &regex::escape(&base64::urlsafe(b"https://origin.example.com"))
+                    + "/host/"
+                    + &regex::escape(&base64::urlsafe(b"www.example.com"))
+                    + "$",
+                |_, _| Ok(ResponseBuilder::default().status_code(200u16).build()),
+            )
+            .install(Method::POST, r"^https://uc\.qbox\.me/unimage/test-bucket$", |_, _| {
+                Ok(ResponseBuilder::default().status_code(200u16).build())
+            }),
+        );
+        let storage_manager = StorageManager::new(
+            Credential::new("abcdefghklmnopq", "1234567890"),
+            ConfigBuilder::default()
+                .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+                .http_request_handler(mock.clone())
+                .build(),
+        );
+        storage_manager.set_mirror_source("test-bucket", "https://origin.example.com", Some("www.example.com"))?;
+        storage_manager.unset_mirror_source("test-bucket")?;
+        assert_eq!(mock.call_called(), 2);
+        Ok(())
+    }
+}
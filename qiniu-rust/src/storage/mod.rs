@@ -2,8 +2,15 @@
 //!
 //! 负责对整个 SDK 存储方面的逻辑进行处理
 
+pub mod batch;
 pub mod bucket;
+pub mod bucket_manager;
+pub mod download;
+pub mod fetch;
+pub mod list;
 pub mod manager;
+pub mod object;
+pub mod processing;
 pub mod recorder;
 pub mod region;
 pub mod uploader;
@@ -0,0 +1,283 @@
+//! 对象模块
+//!
+//! 封装存储空间中对象的元信息
+
+use crate::utils::base64;
+use getset::{CopyGetters, Getters};
+use serde::{
+    de::{Deserializer, Error as DeserializeError},
+    Deserialize,
+};
+use std::time::{Duration, SystemTime};
+
+/// 文件存储类型
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FileType {
+    /// 标准存储
+    Standard,
+    /// 低频存储
+    InfrequentAccess,
+    /// 归档存储
+    Archive,
+    /// 深度归档存储
+    DeepArchive,
+}
+
+impl FileType {
+    /// 获取文件存储类型对应的整型值
+    pub fn as_u8(self) -> u8 {
+        match self {
+            FileType::Standard => 0,
+            FileType::InfrequentAccess => 1,
+            FileType::Archive => 2,
+            FileType::DeepArchive => 3,
+        }
+    }
+
+    /// 根据整型值获取文件存储类型
+    pub fn from_u8(value: u8) -> Option<FileType> {
+        match value {
+            0 => Some(FileType::Standard),
+            1 => Some(FileType::InfrequentAccess),
+            2 => Some(FileType::Archive),
+            3 => Some(FileType::DeepArchive),
+            _ => None,
+        }
+    }
+}
+
+impl Default for FileType {
+    #[inline]
+    fn default() -> Self {
+        FileType::Standard
+    }
+}
+
+impl<'de> Deserialize<'de> for FileType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        FileType::from_u8(value).ok_or_else(|| D::Error::custom(format!("Unrecognized file type: {}", value)))
+    }
+}
+
+/// 对象元信息
+///
+/// 通过 `Bucket::stat` 获取
+#[derive(Deserialize, Getters, CopyGetters, Debug, Clone)]
+pub struct ObjectInfo {
+    /// 文件大小，单位为字节
+    #[serde(rename = "fsize")]
+    #[get_copy = "pub"]
+    size: u64,
+
+    /// 文件 Etag
+    #[get = "pub"]
+    hash: String,
+
+    /// 文件 MIME 类型
+    #[serde(rename = "mimeType")]
+    #[get = "pub"]
+    mime_type: String,
+
+    #[serde(rename = "putTime")]
+    put_time: u64,
+
+    /// 文件存储类型
+    #[serde(rename = "type", default)]
+    #[get_copy = "pub"]
+    file_type: FileType,
+
+    #[serde(default)]
+    md5: Option<String>,
+}
+
+impl ObjectInfo {
+    /// 文件上传时间
+    pub fn put_time(&self) -> SystemTime {
+        // 七牛返回的上传时间单位为 100 纳秒
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.put_time * 100)
+    }
+
+    /// 文件 MD5 值
+    ///
+    /// 仅当七牛服务器返回该字段时才有值
+    pub fn md5(&self) -> Option<&str> {
+        self.md5.as_ref().map(String::as_str)
+    }
+}
+
+pub(crate) mod path {
+    use super::{base64, FileType};
+
+    const METADATA_PREFIX: &str = "x-qn-meta-";
+
+    pub(crate) fn stat(bucket: &str, key: &str) -> String {
+        "/stat/".to_owned() + &encode_entry(bucket, key)
+    }
+
+    pub(crate) fn delete(bucket: &str, key: &str) -> String {
+        "/delete/".to_owned() + &encode_entry(bucket, key)
+    }
+
+    pub(crate) fn copy_to(src_bucket: &str, src_key: &str, dest_bucket: &str, dest_key: &str, force: bool) -> String {
+        copy_or_move("/copy/", src_bucket, src_key, dest_bucket, dest_key, force)
+    }
+
+    pub(crate) fn move_to(src_bucket: &str, src_key: &str, dest_bucket: &str, dest_key: &str, force: bool) -> String {
+        copy_or_move("/move/", src_bucket, src_key, dest_bucket, dest_key, force)
+    }
+
+    pub(crate) fn change_mime_type(bucket: &str, key: &str, mime_type: &str) -> String {
+        change_metadata(bucket, key, Some(mime_type), &[])
+    }
+
+    pub(crate) fn change_metadata(
+        bucket: &str,
+        key: &str,
+        mime_type: Option<&str>,
+        metadata: &[(&str, &str)],
+    ) -> String {
+        let mut path = "/chgm/".to_owned() + &encode_entry(bucket, key);
+        if let Some(mime_type) = mime_type {
+            path.push_str("/mime/");
+            path.push_str(&base64::urlsafe(mime_type.as_bytes()));
+        }
+        for (name, value) in metadata {
+            path.push('/');
+            if !name.starts_with(METADATA_PREFIX) {
+                path.push_str(METADATA_PREFIX);
+            }
+            path.push_str(name);
+            path.push('/');
+            path.push_str(&base64::urlsafe(value.as_bytes()));
+        }
+        path
+    }
+
+    pub(crate) fn change_file_type(bucket: &str, key: &str, file_type: FileType) -> String {
+        "/chtype/".to_owned() + &encode_entry(bucket, key) + "/type/" + &file_type.as_u8().to_string()
+    }
+
+    pub(crate) fn change_status(bucket: &str, key: &str, disabled: bool) -> String {
+        "/chstatus/".to_owned() + &encode_entry(bucket, key) + "/status/" + if disabled { "1" } else { "0" }
+    }
+
+    pub(crate) fn delete_after_days(bucket: &str, key: &str, days: u32) -> String {
+        "/deleteAfterDays/".to_owned() + &encode_entry(bucket, key) + "/" + &days.to_string()
+    }
+
+    pub(crate) fn restore_archive(bucket: &str, key: &str, freeze_after_days: u32) -> String {
+        "/restoreAr/".to_owned() + &encode_entry(bucket, key) + "/freezeAfterDays/" + &freeze_after_days.to_string()
+    }
+
+    pub(crate) fn prefetch(bucket: &str, key: &str) -> String {
+        "/prefetch/".to_owned() + &encode_entry(bucket, key)
+    }
+
+    fn copy_or_move(
+        prefix: &str,
+        src_bucket: &str,
+        src_key: &str,
+        dest_bucket: &str,
+        dest_key: &str,
+        force: bool,
+    ) -> String {
+        let mut path =
+            prefix.to_owned() + &encode_entry(src_bucket, src_key) + "/" + &encode_entry(dest_bucket, dest_key);
+        if force {
+            path.push_str("/force/true");
+        }
+        path
+    }
+
+    pub(crate) fn encode_entry(bucket: &str, key: &str) -> String {
+        base64::urlsafe((bucket.to_owned() + ":" + key).as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_storage_object_parse_object_info() -> Result<(), Box<dyn Error>> {
+        let info: ObjectInfo = serde_json::from_value(json!({
+            "fsize": 1024,
+            "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk",
+            "mimeType": "image/png",
+            "putTime": 15_735_614_000_000_000u64,
+            "type": 1,
+            "md5": "1b2cf535f27731c974343645a3985328",
+        }))?;
+        assert_eq!(info.size(), 1024);
+        assert_eq!(info.hash(), "FhOPGUW8AaIdsdUGjnj-8jDTYqtk");
+        assert_eq!(info.mime_type(), "image/png");
+        assert_eq!(
+            info.put_time().duration_since(SystemTime::UNIX_EPOCH)?,
+            Duration::from_secs(1_573_561_400)
+        );
+        assert_eq!(info.file_type(), FileType::InfrequentAccess);
+        assert_eq!(info.md5(), Some("1b2cf535f27731c974343645a3985328"));
+
+        let info: ObjectInfo = serde_json::from_value(json!({
+            "fsize": 0,
+            "hash": "Fto5o-5ea0sNMlW_75VgGJCv2AcJ",
+            "mimeType": "application/octet-stream",
+            "putTime": 0,
+        }))?;
+        assert_eq!(info.file_type(), FileType::Standard);
+        assert_eq!(info.md5(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_object_paths() {
+        assert_eq!(
+            path::encode_entry("test-bucket", "test-key"),
+            "dGVzdC1idWNrZXQ6dGVzdC1rZXk="
+        );
+        assert_eq!(
+            path::stat("test-bucket", "test-key"),
+            "/stat/dGVzdC1idWNrZXQ6dGVzdC1rZXk="
+        );
+        assert_eq!(
+            path::copy_to("test-bucket", "test-key", "test-bucket", "test-key-2", true),
+            "/copy/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/dGVzdC1idWNrZXQ6dGVzdC1rZXktMg==/force/true"
+        );
+        assert_eq!(
+            path::move_to("test-bucket", "test-key", "test-bucket", "test-key-2", false),
+            "/move/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/dGVzdC1idWNrZXQ6dGVzdC1rZXktMg=="
+        );
+        assert_eq!(
+            path::change_mime_type("test-bucket", "test-key", "text/plain"),
+            "/chgm/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/mime/dGV4dC9wbGFpbg=="
+        );
+        assert_eq!(
+            path::change_file_type("test-bucket", "test-key", FileType::InfrequentAccess),
+            "/chtype/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/type/1"
+        );
+        assert_eq!(
+            path::change_metadata(
+                "test-bucket",
+                "test-key",
+                None,
+                &[("author", "qiniu"), ("x-qn-meta-version", "2")]
+            ),
+            "/chgm/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/x-qn-meta-author/cWluaXU=/x-qn-meta-version/Mg=="
+        );
+        assert_eq!(
+            path::change_status("test-bucket", "test-key", true),
+            "/chstatus/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/status/1"
+        );
+        assert_eq!(
+            path::delete_after_days("test-bucket", "test-key", 30),
+            "/deleteAfterDays/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/30"
+        );
+        assert_eq!(
+            path::restore_archive("test-bucket", "test-key", 7),
+            "/restoreAr/dGVzdC1idWNrZXQ6dGVzdC1rZXk=/freezeAfterDays/7"
+        );
+    }
+}
@@ -0,0 +1,816 @@
+use crate::utils::base64;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    time::Duration,
+};
+
+/// 数据处理指令
+///
+/// 所有实现该 Trait 的类型都可以通过 `Pipeline` 组合为管道处理指令。
+/// 对于 SDK 尚未提供类型化生成器的数据处理指令，可以直接使用字符串
+pub trait Fop: Display {}
+
+impl Fop for &str {}
+impl Fop for String {}
+impl<'a> Fop for Cow<'a, str> {}
+
+/// 管道处理指令
+///
+/// 多个数据处理指令将通过 `|` 连接，前一个指令的处理结果将作为后一个指令的输入。
+/// 生成的管道处理指令可以用于 `DownloadUrlBuilder::fop`，`Bucket::pfop` 以及上传策略的持久化数据处理指令
+///
+/// ```rust
+/// use qiniu_ng::storage::processing::{ImageView2, Pipeline};
+///
+/// let fop = Pipeline::new()
+///     .then(ImageView2::new(1).width(200).height(200))
+///     .save_as("test-bucket", "test-key-thumb.png");
+/// assert_eq!(
+///     fop.as_ref(),
+///     "imageView2/1/w/200/h/200|saveas/dGVzdC1idWNrZXQ6dGVzdC1rZXktdGh1bWIucG5n"
+/// );
+/// ```
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Pipeline {
+    inner: String,
+}
+
+impl Pipeline {
+    /// 创建空的管道处理指令
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// 追加数据处理指令
+    pub fn then(mut self, fop: impl Fop) -> Self {
+        if !self.inner.is_empty() {
+            self.inner.push('|');
+        }
+        self.inner.push_str(&fop.to_string());
+        self
+    }
+
+    /// 追加另存为指令
+    ///
+    /// 处理结果将被保存到指定的存储空间和对象名称
+    pub fn save_as(self, bucket: impl AsRef<str>, key: impl AsRef<str>) -> Self {
+        self.then(SaveAs::new(bucket.as_ref(), key.as_ref()))
+    }
+
+    /// 管道处理指令是否为空
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl AsRef<str> for Pipeline {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl Display for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl From<Pipeline> for String {
+    fn from(pipeline: Pipeline) -> Self {
+        pipeline.inner
+    }
+}
+
+impl<'a> From<Pipeline> for Cow<'a, str> {
+    fn from(pipeline: Pipeline) -> Self {
+        Cow::Owned(pipeline.inner)
+    }
+}
+
+impl<F: Fop> From<F> for Pipeline {
+    fn from(fop: F) -> Self {
+        Pipeline::new().then(fop)
+    }
+}
+
+/// 图片基本处理（imageView2）
+///
+/// 可以点击[这里](https://developer.qiniu.com/dora/manual/1279/basic-processing-images-imageview2)了解详细参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImageView2<'a> {
+    mode: u8,
+    width: Option<u32>,
+    height: Option<u32>,
+    format: Option<Cow<'a, str>>,
+    interlace: Option<bool>,
+    quality: Option<u8>,
+    ignore_error: bool,
+}
+
+impl<'a> ImageView2<'a> {
+    /// 创建图片基本处理指令
+    ///
+    /// `mode` 为缩略模式，取值范围为 0 到 5
+    pub fn new(mode: u8) -> Self {
+        ImageView2 {
+            mode,
+            width: None,
+            height: None,
+            format: None,
+            interlace: None,
+            quality: None,
+            ignore_error: false,
+        }
+    }
+
+    /// 指定目标图片宽度
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// 指定目标图片高度
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// 指定目标图片格式
+    pub fn format(mut self, format: impl Into<Cow<'a, str>>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// 是否支持渐进显示
+    pub fn interlace(mut self, interlace: bool) -> Self {
+        self.interlace = Some(interlace);
+        self
+    }
+
+    /// 指定目标图片质量，取值范围为 1 到 100
+    pub fn quality(mut self, quality: u8) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// 处理失败时返回原图
+    pub fn ignore_error(mut self) -> Self {
+        self.ignore_error = true;
+        self
+    }
+}
+
+impl Display for ImageView2<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "imageView2/{}", self.mode)?;
+        if let Some(width) = self.width {
+            write!(f, "/w/{}", width)?;
+        }
+        if let Some(height) = self.height {
+            write!(f, "/h/{}", height)?;
+        }
+        if let Some(format) = &self.format {
+            write!(f, "/format/{}", format)?;
+        }
+        if let Some(interlace) = self.interlace {
+            write!(f, "/interlace/{}", interlace as u8)?;
+        }
+        if let Some(quality) = self.quality {
+            write!(f, "/q/{}", quality)?;
+        }
+        if self.ignore_error {
+            f.write_str("/ignore-error/1")?;
+        }
+        Ok(())
+    }
+}
+
+impl Fop for ImageView2<'_> {}
+
+/// 图片高级处理（imageMogr2）
+///
+/// 可以点击[这里](https://developer.qiniu.com/dora/manual/1270/the-advanced-treatment-of-images-imagemogr2)了解详细参数
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct ImageMogr2<'a> {
+    auto_orient: bool,
+    thumbnail: Option<Cow<'a, str>>,
+    strip: bool,
+    gravity: Option<Cow<'a, str>>,
+    crop: Option<Cow<'a, str>>,
+    rotate: Option<u32>,
+    format: Option<Cow<'a, str>>,
+    blur: Option<(u32, u32)>,
+    interlace: Option<bool>,
+    quality: Option<u8>,
+    ignore_error: bool,
+}
+
+impl<'a> ImageMogr2<'a> {
+    /// 创建图片高级处理指令
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// 根据原图 EXIF 信息自动旋正
+    pub fn auto_orient(mut self) -> Self {
+        self.auto_orient = true;
+        self
+    }
+
+    /// 指定缩放参数，例如 `200x200>`
+    pub fn thumbnail(mut self, thumbnail: impl Into<Cow<'a, str>>) -> Self {
+        self.thumbnail = Some(thumbnail.into());
+        self
+    }
+
+    /// 去除图片中的元信息
+    pub fn strip(mut self) -> Self {
+        self.strip = true;
+        self
+    }
+
+    /// 指定裁剪锚点，例如 `NorthWest`
+    pub fn gravity(mut self, gravity: impl Into<Cow<'a, str>>) -> Self {
+        self.gravity = Some(gravity.into());
+        self
+    }
+
+    /// 指定裁剪参数，例如 `!300x400a10a10`
+    pub fn crop(mut self, crop: impl Into<Cow<'a, str>>) -> Self {
+        self.crop = Some(crop.into());
+        self
+    }
+
+    /// 指定顺时针旋转角度，取值范围为 1 到 360
+    pub fn rotate(mut self, rotate: u32) -> Self {
+        self.rotate = Some(rotate);
+        self
+    }
+
+    /// 指定目标图片格式
+    pub fn format(mut self, format: impl Into<Cow<'a, str>>) -> Self {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// 指定高斯模糊的半径和标准差
+    pub fn blur(mut self, radius: u32, sigma: u32) -> Self {
+        self.blur = Some((radius, sigma));
+        self
+    }
+
+    /// 是否支持渐进显示
+    pub fn interlace(mut self, interlace: bool) -> Self {
+        self.interlace = Some(interlace);
+        self
+    }
+
+    /// 指定目标图片质量，取值范围为 1 到 100
+    pub fn quality(mut self, quality: u8) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    /// 处理失败时返回原图
+    pub fn ignore_error(mut self) -> Self {
+        self.ignore_error = true;
+        self
+    }
+}
+
+impl Display for ImageMogr2<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("imageMogr2")?;
+        if self.auto_orient {
+            f.write_str("/auto-orient")?;
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            write!(f, "/thumbnail/{}", thumbnail)?;
+        }
+        if self.strip {
+            f.write_str("/strip")?;
+        }
+        if let Some(gravity) = &self.gravity {
+            write!(f, "/gravity/{}", gravity)?;
+        }
+        if let Some(crop) = &self.crop {
+            write!(f, "/crop/{}", crop)?;
+        }
+        if let Some(rotate) = self.rotate {
+            write!(f, "/rotate/{}", rotate)?;
+        }
+        if let Some(format) = &self.format {
+            write!(f, "/format/{}", format)?;
+        }
+        if let Some((radius, sigma)) = self.blur {
+            write!(f, "/blur/{}x{}", radius, sigma)?;
+        }
+        if let Some(interlace) = self.interlace {
+            write!(f, "/interlace/{}", interlace as u8)?;
+        }
+        if let Some(quality) = self.quality {
+            write!(f, "/quality/{}", quality)?;
+        }
+        if self.ignore_error {
+            f.write_str("/ignore-error/1")?;
+        }
+        Ok(())
+    }
+}
+
+impl Fop for ImageMogr2<'_> {}
+
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+struct WatermarkPosition<'a> {
+    dissolve: Option<u8>,
+    gravity: Option<Cow<'a, str>>,
+    dx: Option<i32>,
+    dy: Option<i32>,
+}
+
+impl Display for WatermarkPosition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(dissolve) = self.dissolve {
+            write!(f, "/dissolve/{}", dissolve)?;
+        }
+        if let Some(gravity) = &self.gravity {
+            write!(f, "/gravity/{}", gravity)?;
+        }
+        if let Some(dx) = self.dx {
+            write!(f, "/dx/{}", dx)?;
+        }
+        if let Some(dy) = self.dy {
+            write!(f, "/dy/{}", dy)?;
+        }
+        Ok(())
+    }
+}
+
+/// 图片水印（watermark/1）
+///
+/// 可以点击[这里](https://developer.qiniu.com/dora/manual/1316/image-watermarking-processing-watermark)了解详细参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImageWatermark<'a> {
+    image_url: Cow<'a, str>,
+    scale: Option<u8>,
+    position: WatermarkPosition<'a>,
+}
+
+impl<'a> ImageWatermark<'a> {
+    /// 创建图片水印指令
+    ///
+    /// `image_url` 为水印图片地址，SDK 将负责对其进行 URL 安全的 Base64 编码
+    pub fn new(image_url: impl Into<Cow<'a, str>>) -> Self {
+        ImageWatermark {
+            image_url: image_url.into(),
+            scale: None,
+            position: Default::default(),
+        }
+    }
+
+    /// 指定透明度，取值范围为 1 到 100
+    pub fn dissolve(mut self, dissolve: u8) -> Self {
+        self.position.dissolve = Some(dissolve);
+        self
+    }
+
+    /// 指定水印位置，例如 `SouthEast`
+    pub fn gravity(mut self, gravity: impl Into<Cow<'a, str>>) -> Self {
+        self.position.gravity = Some(gravity.into());
+        self
+    }
+
+    /// 指定横轴边距
+    pub fn dx(mut self, dx: i32) -> Self {
+        self.position.dx = Some(dx);
+        self
+    }
+
+    /// 指定纵轴边距
+    pub fn dy(mut self, dy: i32) -> Self {
+        self.position.dy = Some(dy);
+        self
+    }
+
+    /// 指定水印图片相对于原图的短边自适应比例，单位为百分比
+    pub fn scale(mut self, scale: u8) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+}
+
+impl Display for ImageWatermark<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "watermark/1/image/{}{}",
+            base64::urlsafe(self.image_url.as_bytes()),
+            self.position
+        )?;
+        if let Some(scale) = self.scale {
+            write!(f, "/ws/{}", f64::from(scale) / 100f64)?;
+        }
+        Ok(())
+    }
+}
+
+impl Fop for ImageWatermark<'_> {}
+
+/// 文字水印（watermark/2）
+///
+/// 可以点击[这里](https://developer.qiniu.com/dora/manual/1316/image-watermarking-processing-watermark)了解详细参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TextWatermark<'a> {
+    text: Cow<'a, str>,
+    font: Option<Cow<'a, str>>,
+    font_size: Option<u32>,
+    fill: Option<Cow<'a, str>>,
+    position: WatermarkPosition<'a>,
+}
+
+impl<'a> TextWatermark<'a> {
+    /// 创建文字水印指令
+    ///
+    /// SDK 将负责对水印文字进行 URL 安全的 Base64 编码
+    pub fn new(text: impl Into<Cow<'a, str>>) -> Self {
+        TextWatermark {
+            text: text.into(),
+            font: None,
+            font_size: None,
+            fill: None,
+            position: Default::default(),
+        }
+    }
+
+    /// 指定字体名称
+    pub fn font(mut self, font: impl Into<Cow<'a, str>>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// 指定字体大小，单位为缇
+    pub fn font_size(mut self, font_size: u32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// 指定字体颜色，例如 `#FFFFFF`
+    pub fn fill(mut self, fill: impl Into<Cow<'a, str>>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    /// 指定透明度，取值范围为 1 到 100
+    pub fn dissolve(mut self, dissolve: u8) -> Self {
+        self.position.dissolve = Some(dissolve);
+        self
+    }
+
+    /// 指定水印位置，例如 `SouthEast`
+    pub fn gravity(mut self, gravity: impl Into<Cow<'a, str>>) -> Self {
+        self.position.gravity = Some(gravity.into());
+        self
+    }
+
+    /// 指定横轴边距
+    pub fn dx(mut self, dx: i32) -> Self {
+        self.position.dx = Some(dx);
+        self
+    }
+
+    /// 指定纵轴边距
+    pub fn dy(mut self, dy: i32) -> Self {
+        self.position.dy = Some(dy);
+        self
+    }
+}
+
+impl Display for TextWatermark<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "watermark/2/text/{}", base64::urlsafe(self.text.as_bytes()))?;
+        if let Some(font) = &self.font {
+            write!(f, "/font/{}", base64::urlsafe(font.as_bytes()))?;
+        }
+        if let Some(font_size) = self.font_size {
+            write!(f, "/fontsize/{}", font_size)?;
+        }
+        if let Some(fill) = &self.fill {
+            write!(f, "/fill/{}", base64::urlsafe(fill.as_bytes()))?;
+        }
+        write!(f, "{}", self.position)
+    }
+}
+
+impl Fop for TextWatermark<'_> {}
+
+/// 音视频转码（avthumb）
+///
+/// 可以点击[这里](https://developer.qiniu.com/dora/manual/1248/audio-and-video-transcoding-avthumb)了解详细参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Avthumb<'a> {
+    format: Cow<'a, str>,
+    audio_bitrate: Option<Cow<'a, str>>,
+    audio_sample_rate: Option<u32>,
+    frame_rate: Option<u32>,
+    video_bitrate: Option<Cow<'a, str>>,
+    video_codec: Option<Cow<'a, str>>,
+    audio_codec: Option<Cow<'a, str>>,
+    resolution: Option<(u32, u32)>,
+    seek_start: Option<Duration>,
+    duration: Option<Duration>,
+}
+
+impl<'a> Avthumb<'a> {
+    /// 创建音视频转码指令
+    ///
+    /// `format` 为目标容器格式，例如 `mp4`
+    pub fn new(format: impl Into<Cow<'a, str>>) -> Self {
+        Avthumb {
+            format: format.into(),
+            audio_bitrate: None,
+            audio_sample_rate: None,
+            frame_rate: None,
+            video_bitrate: None,
+            video_codec: None,
+            audio_codec: None,
+            resolution: None,
+            seek_start: None,
+            duration: None,
+        }
+    }
+
+    /// 指定音频码率，例如 `128k`
+    pub fn audio_bitrate(mut self, audio_bitrate: impl Into<Cow<'a, str>>) -> Self {
+        self.audio_bitrate = Some(audio_bitrate.into());
+        self
+    }
+
+    /// 指定音频采样频率，单位为赫兹
+    pub fn audio_sample_rate(mut self, audio_sample_rate: u32) -> Self {
+        self.audio_sample_rate = Some(audio_sample_rate);
+        self
+    }
+
+    /// 指定视频帧率
+    pub fn frame_rate(mut self, frame_rate: u32) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
+
+    /// 指定视频码率，例如 `1m`
+    pub fn video_bitrate(mut self, video_bitrate: impl Into<Cow<'a, str>>) -> Self {
+        self.video_bitrate = Some(video_bitrate.into());
+        self
+    }
+
+    /// 指定视频编码方案，例如 `libx264`
+    pub fn video_codec(mut self, video_codec: impl Into<Cow<'a, str>>) -> Self {
+        self.video_codec = Some(video_codec.into());
+        self
+    }
+
+    /// 指定音频编码方案，例如 `libfaac`
+    pub fn audio_codec(mut self, audio_codec: impl Into<Cow<'a, str>>) -> Self {
+        self.audio_codec = Some(audio_codec.into());
+        self
+    }
+
+    /// 指定视频分辨率
+    pub fn resolution(mut self, width: u32, height: u32) -> Self {
+        self.resolution = Some((width, height));
+        self
+    }
+
+    /// 指定转码的起始时间点
+    pub fn seek_start(mut self, seek_start: Duration) -> Self {
+        self.seek_start = Some(seek_start);
+        self
+    }
+
+    /// 指定转码的时长
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+impl Display for Avthumb<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "avthumb/{}", self.format)?;
+        if let Some(audio_bitrate) = &self.audio_bitrate {
+            write!(f, "/ab/{}", audio_bitrate)?;
+        }
+        if let Some(audio_sample_rate) = self.audio_sample_rate {
+            write!(f, "/ar/{}", audio_sample_rate)?;
+        }
+        if let Some(frame_rate) = self.frame_rate {
+            write!(f, "/r/{}", frame_rate)?;
+        }
+        if let Some(video_bitrate) = &self.video_bitrate {
+            write!(f, "/vb/{}", video_bitrate)?;
+        }
+        if let Some(video_codec) = &self.video_codec {
+            write!(f, "/vcodec/{}", video_codec)?;
+        }
+        if let Some(audio_codec) = &self.audio_codec {
+            write!(f, "/acodec/{}", audio_codec)?;
+        }
+        if let Some((width, height)) = self.resolution {
+            write!(f, "/s/{}x{}", width, height)?;
+        }
+        if let Some(seek_start) = self.seek_start {
+            write!(f, "/ss/{}", seek_start.as_secs_f64())?;
+        }
+        if let Some(duration) = self.duration {
+            write!(f, "/t/{}", duration.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
+
+impl Fop for Avthumb<'_> {}
+
+/// 视频帧缩略图（vframe）
+///
+/// 可以点击[这里](https://developer.qiniu.com/dora/manual/1313/video-frame-thumbnails-vframe)了解详细参数
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Vframe<'a> {
+    format: Cow<'a, str>,
+    offset: Duration,
+    width: Option<u32>,
+    height: Option<u32>,
+    rotate: Option<u32>,
+}
+
+impl<'a> Vframe<'a> {
+    /// 创建视频帧缩略图指令
+    ///
+    /// `format` 为目标图片格式，例如 `jpg`，`offset` 为截取帧的时间点
+    pub fn new(format: impl Into<Cow<'a, str>>, offset: Duration) -> Self {
+        Vframe {
+            format: format.into(),
+            offset,
+            width: None,
+            height: None,
+            rotate: None,
+        }
+    }
+
+    /// 指定缩略图宽度
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// 指定缩略图高度
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// 指定顺时针旋转角度，可选值为 90，180，270
+    pub fn rotate(mut self, rotate: u32) -> Self {
+        self.rotate = Some(rotate);
+        self
+    }
+}
+
+impl Display for Vframe<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "vframe/{}/offset/{}", self.format, self.offset.as_secs_f64())?;
+        if let Some(width) = self.width {
+            write!(f, "/w/{}", width)?;
+        }
+        if let Some(height) = self.height {
+            write!(f, "/h/{}", height)?;
+        }
+        if let Some(rotate) = self.rotate {
+            write!(f, "/rotate/{}", rotate)?;
+        }
+        Ok(())
+    }
+}
+
+impl Fop for Vframe<'_> {}
+
+/// 处理结果另存（saveas）
+///
+/// 通常作为管道处理指令的最后一个指令，将处理结果保存到指定的存储空间和对象名称
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SaveAs<'a> {
+    bucket: Cow<'a, str>,
+    key: Cow<'a, str>,
+}
+
+impl<'a> SaveAs<'a> {
+    /// 创建另存为指令
+    pub fn new(bucket: impl Into<Cow<'a, str>>, key: impl Into<Cow<'a, str>>) -> Self {
+        SaveAs {
+            bucket: bucket.into(),
+            key: key.into(),
+        }
+    }
+}
+
+impl Display for SaveAs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entry = String::with_capacity(self.bucket.len() + self.key.len() + 1);
+        entry.push_str(&self.bucket);
+        entry.push(':');
+        entry.push_str(&self.key);
+        write!(f, "saveas/{}", base64::urlsafe(entry.as_bytes()))
+    }
+}
+
+impl Fop for SaveAs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_processing_fop_image() {
+        assert_eq!(
+            ImageView2::new(1)
+                .width(200)
+                .height(100)
+                .format("webp")
+                .quality(75)
+                .to_string(),
+            "imageView2/1/w/200/h/100/format/webp/q/75"
+        );
+        assert_eq!(
+            ImageMogr2::new()
+                .auto_orient()
+                .thumbnail("200x200>")
+                .strip()
+                .crop("!300x400a10a10")
+                .rotate(90)
+                .blur(3, 5)
+                .interlace(true)
+                .quality(80)
+                .ignore_error()
+                .to_string(),
+            "imageMogr2/auto-orient/thumbnail/200x200>/strip/crop/!300x400a10a10/rotate/90/blur/3x5/interlace/1/quality/80/ignore-error/1"
+        );
+        assert_eq!(
+            ImageWatermark::new("http://www.b1.qiniudn.com/images/logo-2.png")
+                .dissolve(50)
+                .gravity("SouthEast")
+                .dx(20)
+                .dy(20)
+                .scale(10)
+                .to_string(),
+            "watermark/1/image/aHR0cDovL3d3dy5iMS5xaW5pdWRuLmNvbS9pbWFnZXMvbG9nby0yLnBuZw==\
+             /dissolve/50/gravity/SouthEast/dx/20/dy/20/ws/0.1"
+        );
+        assert_eq!(
+            TextWatermark::new("七牛云存储")
+                .font("宋体")
+                .font_size(500)
+                .fill("#FFFFFF")
+                .gravity("North")
+                .to_string(),
+            "watermark/2/text/5LiD54mb5LqR5a2Y5YKo/font/5a6L5L2T/fontsize/500/fill/I0ZGRkZGRg==/gravity/North"
+        );
+    }
+
+    #[test]
+    fn test_storage_processing_fop_av() {
+        assert_eq!(
+            Avthumb::new("mp4")
+                .audio_bitrate("128k")
+                .audio_sample_rate(44100)
+                .frame_rate(30)
+                .video_bitrate("1m")
+                .video_codec("libx264")
+                .resolution(1280, 720)
+                .seek_start(Duration::from_millis(1500))
+                .duration(Duration::from_secs(60))
+                .to_string(),
+            "avthumb/mp4/ab/128k/ar/44100/r/30/vb/1m/vcodec/libx264/s/1280x720/ss/1.5/t/60"
+        );
+        assert_eq!(
+            Vframe::new("jpg", Duration::from_secs(7))
+                .width(480)
+                .height(360)
+                .to_string(),
+            "vframe/jpg/offset/7/w/480/h/360"
+        );
+    }
+
+    #[test]
+    fn test_storage_processing_fop_pipeline() {
+        let pipeline = Pipeline::new()
+            .then(Avthumb::new("mp4"))
+            .save_as("test-bucket", "test-key.mp4");
+        assert_eq!(pipeline.as_ref(), "avthumb/mp4|saveas/dGVzdC1idWNrZXQ6dGVzdC1rZXkubXA0");
+        assert_eq!(Pipeline::from("imageInfo").then("exif").to_string(), "imageInfo|exif");
+        assert!(Pipeline::new().is_empty());
+        assert_eq!(
+            String::from(Pipeline::from(ImageView2::new(2).width(200))),
+            "imageView2/2/w/200"
+        );
+    }
+}
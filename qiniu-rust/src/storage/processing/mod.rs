@@ -0,0 +1,10 @@
+//! 数据处理模块
+//!
+//! 提供数据处理指令生成，持久化数据处理（pfop）的处理状态等数据处理相关功能
+
+mod fop;
+mod status;
+
+pub use fop::{Avthumb, Fop, ImageMogr2, ImageView2, ImageWatermark, Pipeline, SaveAs, TextWatermark, Vframe};
+pub(crate) use status::PfopResult;
+pub use status::{PersistentItem, PersistentStatus, PersistentStatusCode};
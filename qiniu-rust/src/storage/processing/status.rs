@@ -0,0 +1,195 @@
+use getset::{CopyGetters, Getters};
+use matches::matches;
+use serde::{
+    de::{Deserializer, Error as DeserializeError},
+    Deserialize,
+};
+
+/// 持久化数据处理状态码
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PersistentStatusCode {
+    /// 处理成功
+    Succeeded,
+    /// 等待处理
+    Waiting,
+    /// 正在处理
+    Processing,
+    /// 处理失败
+    Failed,
+    /// 通知提交失败
+    NotifyFailed,
+}
+
+impl PersistentStatusCode {
+    /// 获取状态码对应的整型值
+    pub fn as_u8(self) -> u8 {
+        match self {
+            PersistentStatusCode::Succeeded => 0,
+            PersistentStatusCode::Waiting => 1,
+            PersistentStatusCode::Processing => 2,
+            PersistentStatusCode::Failed => 3,
+            PersistentStatusCode::NotifyFailed => 4,
+        }
+    }
+
+    /// 根据整型值获取状态码
+    pub fn from_u8(value: u8) -> Option<PersistentStatusCode> {
+        match value {
+            0 => Some(PersistentStatusCode::Succeeded),
+            1 => Some(PersistentStatusCode::Waiting),
+            2 => Some(PersistentStatusCode::Processing),
+            3 => Some(PersistentStatusCode::Failed),
+            4 => Some(PersistentStatusCode::NotifyFailed),
+            _ => None,
+        }
+    }
+
+    /// 处理是否已经结束
+    ///
+    /// 处理成功，处理失败或通知提交失败均视为已经结束
+    pub fn is_finished(self) -> bool {
+        !matches!(self, PersistentStatusCode::Waiting | PersistentStatusCode::Processing)
+    }
+}
+
+impl<'de> Deserialize<'de> for PersistentStatusCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        PersistentStatusCode::from_u8(value)
+            .ok_or_else(|| D::Error::custom(format!("Unrecognized persistent status code: {}", value)))
+    }
+}
+
+/// 持久化数据处理状态
+///
+/// 通过 `Bucket::prefop` 获取
+#[derive(Deserialize, Getters, CopyGetters, Debug, Clone)]
+pub struct PersistentStatus {
+    /// 持久化处理 ID
+    #[get = "pub"]
+    id: String,
+
+    /// 处理状态码
+    #[get_copy = "pub"]
+    code: PersistentStatusCode,
+
+    /// 处理状态描述
+    #[get = "pub"]
+    desc: String,
+
+    /// 处理队列名称
+    #[serde(default)]
+    #[get = "pub"]
+    pipeline: String,
+
+    /// 输入对象所在的存储空间
+    #[serde(rename = "inputBucket", default)]
+    #[get = "pub"]
+    input_bucket: String,
+
+    /// 输入对象名称
+    #[serde(rename = "inputKey", default)]
+    #[get = "pub"]
+    input_key: String,
+
+    /// 每个数据处理指令的处理结果
+    #[serde(default)]
+    #[get = "pub"]
+    items: Vec<PersistentItem>,
+}
+
+/// 单个数据处理指令的处理结果
+#[derive(Deserialize, Getters, CopyGetters, Debug, Clone)]
+pub struct PersistentItem {
+    /// 数据处理指令
+    #[get = "pub"]
+    cmd: String,
+
+    /// 处理状态码
+    #[get_copy = "pub"]
+    code: PersistentStatusCode,
+
+    /// 处理状态描述
+    #[serde(default)]
+    #[get = "pub"]
+    desc: String,
+
+    #[serde(default)]
+    error: Option<String>,
+
+    #[serde(default)]
+    hash: Option<String>,
+
+    #[serde(default)]
+    key: Option<String>,
+}
+
+impl PersistentItem {
+    /// 处理失败时的错误信息
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_ref().map(String::as_str)
+    }
+
+    /// 处理结果的 Etag
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.as_ref().map(String::as_str)
+    }
+
+    /// 处理结果的对象名称
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_ref().map(String::as_str)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct PfopResult {
+    #[serde(rename = "persistentId")]
+    pub(crate) persistent_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::{boxed::Box, error::Error, result::Result};
+
+    #[test]
+    fn test_storage_processing_parse_persistent_status() -> Result<(), Box<dyn Error>> {
+        let status: PersistentStatus = serde_json::from_value(json!({
+            "code": 3,
+            "desc": "The fop is failed",
+            "id": "z0.5e0aee6e3d2ab7e0ed8d4d3d",
+            "inputBucket": "test-bucket",
+            "inputKey": "test-key.mp4",
+            "pipeline": "0.default",
+            "reqid": "RIkAAAB4BxYpZPUV",
+            "items": [{
+                "cmd": "avthumb/mp4|saveas/dGVzdC1idWNrZXQ6dGVzdC1rZXkubXA0",
+                "code": 0,
+                "desc": "The fop was completed successfully",
+                "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk",
+                "key": "test-key.mp4",
+                "returnOld": 0,
+            }, {
+                "cmd": "vframe/jpg/offset/7",
+                "code": 3,
+                "desc": "The fop is failed",
+                "error": "invalid offset",
+            }],
+        }))?;
+        assert_eq!(status.id(), "z0.5e0aee6e3d2ab7e0ed8d4d3d");
+        assert_eq!(status.code(), PersistentStatusCode::Failed);
+        assert!(status.code().is_finished());
+        assert_eq!(status.pipeline(), "0.default");
+        assert_eq!(status.input_bucket(), "test-bucket");
+        assert_eq!(status.input_key(), "test-key.mp4");
+        assert_eq!(status.items().len(), 2);
+        assert_eq!(status.items()[0].code(), PersistentStatusCode::Succeeded);
+        assert_eq!(status.items()[0].key(), Some("test-key.mp4"));
+        assert_eq!(status.items()[0].hash(), Some("FhOPGUW8AaIdsdUGjnj-8jDTYqtk"));
+        assert_eq!(status.items()[0].error(), None);
+        assert_eq!(status.items()[1].cmd(), "vframe/jpg/offset/7");
+        assert_eq!(status.items()[1].error(), Some("invalid offset"));
+        Ok(())
+    }
+}
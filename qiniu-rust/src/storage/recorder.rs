@@ -2,7 +2,9 @@
 //!
 //! 提供上传日志记录仪的持久化相关特性和基于文件系统的持久化方案实现
 
+use digest::{FixedOutput, Input};
 use dirs::cache_dir;
+use sha1::Sha1;
 use std::{
     any::Any,
     borrow::Cow,
@@ -111,3 +113,15 @@ impl Recorder for FileSystemRecorder {
 }
 
 impl RecordMedium for File {}
+
+pub(crate) fn default_id_generator(name: &str, path: &Path, key: Option<&str>) -> String {
+    let mut sha1 = Sha1::default();
+    if let Some(key) = key {
+        sha1.input(key.as_bytes());
+        sha1.input(b"_._");
+    }
+    sha1.input(name.as_bytes());
+    sha1.input(b"_._");
+    sha1.input(path.to_string_lossy().as_ref().as_bytes());
+    hex::encode(sha1.fixed_result())
+}
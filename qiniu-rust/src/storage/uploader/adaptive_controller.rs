@@ -0,0 +1,200 @@
+use assert_impl::assert_impl;
+use std::{
+    cmp::{max, min},
+    sync::Mutex,
+    time::Duration,
+};
+
+// 分片上传 v2 要求除最后一个分片外，每个分片不得小于 1 MB，自适应调整时分片尺寸也总是 1 MB 的整数倍
+const MIN_PART_SIZE: u64 = 1 << 20;
+// 分片上传 v2 允许的最大分片尺寸为 1 GB，但由于并发上传的分片都需要读入内存，自适应调整时最大仅使用 64 MB
+const MAX_PART_SIZE: u64 = 1 << 26;
+// 分片上传 v2 允许的最大分片数量
+const MAX_PARTS_COUNT: u64 = 10000;
+// 期望每个分片的上传耗时，分片尺寸将根据测得的吞吐量向该耗时靠拢
+const TARGET_PART_DURATION: Duration = Duration::from_secs(5);
+// 失败率的指数加权移动平均中新样本的权重
+const FAILURE_RATE_SMOOTHING_FACTOR: f64 = 0.3;
+// 仅当失败率低于该值时才会提高并发度
+const MAX_FAILURE_RATE_TO_GROW: f64 = 0.1;
+
+struct State {
+    part_size: u64,
+    concurrency: usize,
+    active: usize,
+    failure_rate: f64,
+}
+
+// 自适应分片上传控制器
+//
+// 根据每个分片的吞吐量调整后续分片的尺寸，使每个分片的上传耗时接近期望值，每次调整幅度不超过两倍。
+// 并发度采用加性增，乘性减的策略：分片上传成功且失败率较低时并发度加一，每次请求失败则并发度减半，同时分片尺寸减半
+pub(super) struct AdaptiveController {
+    min_part_size: u64,
+    max_part_size: u64,
+    max_concurrency: usize,
+    state: Mutex<State>,
+}
+
+pub(super) struct ConcurrencyPermit<'a> {
+    controller: &'a AdaptiveController,
+}
+
+impl AdaptiveController {
+    // 对于尺寸未知的数据流，为了避免分片数量超出限制，分片尺寸不会小于初始尺寸
+    pub(super) fn new(initial_part_size: u32, total_size: Option<u64>, max_concurrency: usize) -> AdaptiveController {
+        let min_part_size = match total_size {
+            Some(total_size) => max(
+                MIN_PART_SIZE,
+                round_down((total_size + MAX_PARTS_COUNT - 1) / MAX_PARTS_COUNT + MIN_PART_SIZE - 1),
+            ),
+            None => max(MIN_PART_SIZE, round_down(initial_part_size.into())),
+        };
+        let max_part_size = max(MAX_PART_SIZE, min_part_size);
+        let max_concurrency = max(max_concurrency, 1);
+        AdaptiveController {
+            min_part_size,
+            max_part_size,
+            max_concurrency,
+            state: Mutex::new(State {
+                part_size: max(
+                    min(round_down(u64::from(initial_part_size)), max_part_size),
+                    min_part_size,
+                ),
+                concurrency: max(max_concurrency / 2, 1),
+                active: 0,
+                failure_rate: 0f64,
+            }),
+        }
+    }
+
+    // 下一个分片的尺寸
+    pub(super) fn part_size(&self) -> u32 {
+        self.state.lock().unwrap().part_size as u32
+    }
+
+    // 自适应调整时可能使用的最大分片尺寸
+    pub(super) fn max_part_size(&self) -> u32 {
+        self.max_part_size as u32
+    }
+
+    // 尝试获取一个并发名额，如果正在上传的分片数量已经达到当前并发度，则返回 None
+    pub(super) fn try_acquire(&self) -> Option<ConcurrencyPermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        if state.active >= state.concurrency {
+            return None;
+        }
+        state.active += 1;
+        Some(ConcurrencyPermit { controller: self })
+    }
+
+    pub(super) fn part_completed(&self, part_size: u64, elapsed: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.failure_rate *= 1f64 - FAILURE_RATE_SMOOTHING_FACTOR;
+        if elapsed > Duration::from_millis(0) {
+            let expected_size = (part_size as f64 * TARGET_PART_DURATION.as_secs_f64() / elapsed.as_secs_f64()) as u64;
+            let part_size = round_down(max(min(expected_size, state.part_size * 2), state.part_size / 2));
+            state.part_size = max(min(part_size, self.max_part_size), self.min_part_size);
+        }
+        if state.failure_rate < MAX_FAILURE_RATE_TO_GROW && state.concurrency < self.max_concurrency {
+            state.concurrency += 1;
+        }
+    }
+
+    pub(super) fn part_failed(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.failure_rate += (1f64 - state.failure_rate) * FAILURE_RATE_SMOOTHING_FACTOR;
+        state.concurrency = max(state.concurrency / 2, 1);
+        state.part_size = max(round_down(state.part_size / 2), self.min_part_size);
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+impl Drop for ConcurrencyPermit<'_> {
+    fn drop(&mut self) {
+        self.controller.state.lock().unwrap().active -= 1;
+    }
+}
+
+#[inline]
+fn round_down(size: u64) -> u64 {
+    size / MIN_PART_SIZE * MIN_PART_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_uploader_adaptive_controller_part_size() {
+        let controller = AdaptiveController::new(1 << 22, Some(1 << 30), 4);
+        assert_eq!(controller.part_size(), 1 << 22);
+
+        // 上传速度很快，分片尺寸每次最多翻倍，直到最大值
+        controller.part_completed(1 << 22, Duration::from_millis(100));
+        assert_eq!(controller.part_size(), 1 << 23);
+        for _ in 0..10 {
+            controller.part_completed(u64::from(controller.part_size()), Duration::from_millis(100));
+        }
+        assert_eq!(controller.part_size(), 1 << 26);
+
+        // 上传速度为 4 MB/s，分片尺寸应该调整到 20 MB 左右
+        controller.part_completed(1 << 26, Duration::from_secs(16));
+        assert_eq!(controller.part_size(), 1 << 25);
+        controller.part_completed(1 << 25, Duration::from_secs(8));
+        assert_eq!(controller.part_size(), 20 << 20);
+
+        // 上传失败后分片尺寸减半，但不会小于 1 MB
+        controller.part_failed();
+        assert_eq!(controller.part_size(), 10 << 20);
+        for _ in 0..10 {
+            controller.part_failed();
+        }
+        assert_eq!(controller.part_size(), 1 << 20);
+    }
+
+    #[test]
+    fn test_storage_uploader_adaptive_controller_part_size_limits() {
+        // 分片数量不能超过 10000 个
+        let controller = AdaptiveController::new(1 << 22, Some(100 << 30), 4);
+        assert_eq!(controller.part_size(), 11 << 20);
+        controller.part_failed();
+        assert_eq!(controller.part_size(), 11 << 20);
+
+        // 数据流尺寸未知，分片尺寸不会小于初始尺寸
+        let controller = AdaptiveController::new(1 << 22, None, 4);
+        controller.part_failed();
+        assert_eq!(controller.part_size(), 1 << 22);
+    }
+
+    #[test]
+    fn test_storage_uploader_adaptive_controller_concurrency() {
+        let controller = AdaptiveController::new(1 << 22, Some(1 << 30), 4);
+        let acquire_all = || (0..8).filter_map(|_| controller.try_acquire()).collect::<Vec<_>>();
+
+        // 初始并发度为最大并发度的一半
+        assert_eq!(acquire_all().len(), 2);
+
+        // 上传成功后并发度逐步提高，但不超过最大并发度
+        for _ in 0..4 {
+            controller.part_completed(1 << 22, Duration::from_secs(5));
+        }
+        assert_eq!(acquire_all().len(), 4);
+
+        // 上传失败后并发度减半，且失败率较高时不再提高并发度
+        controller.part_failed();
+        controller.part_completed(1 << 22, Duration::from_secs(5));
+        let permits = acquire_all();
+        assert_eq!(permits.len(), 2);
+
+        // 名额释放后才能再次获取
+        assert!(controller.try_acquire().is_none());
+        drop(permits);
+        assert!(controller.try_acquire().is_some());
+    }
+}
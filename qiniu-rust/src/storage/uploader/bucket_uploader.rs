@@ -3,29 +3,38 @@
 /// 封装存储空间上传器和文件上传器逻辑。
 /// 需要注意的是，该模块内所有提到的与线程，并发相关的概念仅在分片上传时起效
 use super::{
-    super::uploader::{UploadPolicy, UploadToken},
+    super::{
+        object::{path, ObjectInfo},
+        uploader::{UploadPolicy, UploadToken},
+    },
+    dir_uploader::DirUploaderBuilder,
     form_uploader::FormUploaderBuilder,
+    rate_limiter::RateLimiter,
     resumable_uploader::{ResumableUploader, ResumableUploaderBuilder},
+    upload_event::UploadEvent,
+    upload_handle::{UploadControl, UploadHandle},
     upload_recorder::UploadRecorder,
     UploadLogger, UploadResponse,
 };
 use crate::{
     config::Config,
     credential::Credential,
-    http::Client,
-    utils::{rob::Rob, ron::Ron},
+    http::{Client, ErrorKind as HTTPErrorKind, TokenVersion},
+    utils::{etag, rob::Rob, ron::Ron},
 };
 use assert_impl::assert_impl;
 use getset::Getters;
 use mime::Mime;
 use rayon::{ThreadPool, ThreadPoolBuilder};
+use serde_json::json;
 use std::{
     borrow::Cow,
     collections::HashMap,
     fs::File,
     io::{Error as IOError, Read, Result as IOResult},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
+    thread,
 };
 use thiserror::Error;
 
@@ -35,10 +44,12 @@ use thiserror::Error;
 pub struct BucketUploaderInner {
     bucket_name: Box<str>,
     up_urls_list: Box<[Box<[Box<str>]>]>,
+    rs_urls: Box<[Box<str>]>,
     http_client: Client,
     upload_logger: Option<UploadLogger>,
     recorder: UploadRecorder,
     thread_pool: Option<ThreadPool>,
+    rate_limiter: Option<RateLimiter>,
 }
 
 /// 存储空间上传器
@@ -57,6 +68,9 @@ impl BucketUploader {
     pub(super) fn up_urls_list(&self) -> &[Box<[Box<str>]>] {
         self.inner.up_urls_list()
     }
+    pub(super) fn rs_urls(&self) -> &[Box<str>] {
+        self.inner.rs_urls()
+    }
     pub(super) fn http_client(&self) -> &Client {
         self.inner.http_client()
     }
@@ -69,6 +83,9 @@ impl BucketUploader {
     pub(super) fn thread_pool(&self) -> Option<&ThreadPool> {
         self.inner.thread_pool().as_ref()
     }
+    pub(super) fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner.rate_limiter().as_ref()
+    }
 }
 
 /// 存储空间上传器生成器
@@ -87,7 +104,9 @@ impl BucketUploaderBuilder {
             inner: BucketUploaderInner {
                 bucket_name,
                 up_urls_list,
+                rs_urls: vec![config.rs_url().into_boxed_str()].into(),
                 thread_pool: None,
+                rate_limiter: None,
                 recorder: config.upload_recorder().to_owned(),
                 upload_logger: config.upload_logger().to_owned(),
                 http_client: Client::new(config),
@@ -95,6 +114,12 @@ impl BucketUploaderBuilder {
         }
     }
 
+    pub(super) fn rs_urls(mut self, rs_urls: Box<[Box<str>]>) -> BucketUploaderBuilder {
+        assert!(!rs_urls.is_empty());
+        self.inner.rs_urls = rs_urls;
+        self
+    }
+
     /// 为指定的文件上传指定线程池
     pub fn thread_pool(mut self, thread_pool: ThreadPool) -> BucketUploaderBuilder {
         self.inner.thread_pool = Some(thread_pool);
@@ -112,6 +137,14 @@ impl BucketUploaderBuilder {
         )
     }
 
+    /// 为存储空间上传器指定上传限速器
+    ///
+    /// 该存储空间上传器上传的所有文件将共享该限速器，分片上传时所有并发上传的分片也将共享该限速器
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> BucketUploaderBuilder {
+        self.inner.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     /// 生成存储空间上传器
     pub fn build(self) -> BucketUploader {
         BucketUploader {
@@ -138,6 +171,18 @@ impl BucketUploader {
         )
     }
 
+    /// 根据上传凭证创建目录上传器生成器
+    ///
+    /// 目录中的所有文件都将使用同一个上传凭证上传，因此上传凭证不能限定对象名称。
+    /// 如果需要覆盖同名对象，推荐使用 `UploadPolicyBuilder::new_policy_for_objects_with_prefix` 生成上传策略
+    pub fn upload_dir<'b>(
+        &'b self,
+        dir_path: impl Into<PathBuf>,
+        upload_token: impl Into<UploadToken<'b>>,
+    ) -> DirUploaderBuilder<'b> {
+        DirUploaderBuilder::new(self, dir_path.into(), upload_token.into().to_string().into())
+    }
+
     #[doc(hidden)]
     pub unsafe fn from_raw(ptr: *const BucketUploaderInner) -> BucketUploader {
         BucketUploader {
@@ -176,8 +221,14 @@ pub struct FileUploaderBuilder<'b> {
     resumable_policy: ResumablePolicy,
     #[allow(clippy::type_complexity)]
     on_uploading_progress: Option<Rob<'b, dyn Fn(u64, Option<u64>) + Send + Sync>>,
+    #[allow(clippy::type_complexity)]
+    on_event: Option<Rob<'b, dyn Fn(&UploadEvent) + Send + Sync>>,
     thread_pool: Option<Ron<'b, ThreadPool>>,
     max_concurrency: usize,
+    adaptive: bool,
+    skip_if_identical: Option<Cow<'b, Credential>>,
+    rate_limiter: Option<RateLimiter>,
+    control: Option<Arc<UploadControl>>,
 }
 
 impl<'b> FileUploaderBuilder<'b> {
@@ -189,8 +240,13 @@ impl<'b> FileUploaderBuilder<'b> {
             metadata: None,
             checksum_enabled: true,
             on_uploading_progress: None,
+            on_event: None,
             thread_pool: None,
             max_concurrency: 0,
+            adaptive: false,
+            skip_if_identical: None,
+            rate_limiter: None,
+            control: None,
             resumable_policy: ResumablePolicy::Threshold(bucket_uploader.http_client().config().upload_threshold()),
             bucket_uploader,
         }
@@ -225,6 +281,27 @@ impl<'b> FileUploaderBuilder<'b> {
         self
     }
 
+    /// 启用自适应分片上传
+    ///
+    /// 默认情况下，分片上传总是使用客户端配置中的分片尺寸，并使用最大并发度上传。
+    /// 启用后，将根据每个分片的上传速度和失败率动态调整后续分片的尺寸和并发度：
+    /// 分片尺寸将在 1 MB 到 64 MB 之间调整，使每个分片的上传耗时接近 5 秒，且确保分片数量不超过 10000 个；
+    /// 并发度将从最大并发度的一半开始，上传成功时逐步提高，出现失败时减半，但不会超过最大并发度。
+    ///
+    /// 每个分片的尺寸都会被记录在上传进度记录中，因此断点续传时无论是否启用自适应分片上传都能正确跳过已经上传的分片
+    pub fn enable_adaptive_uploading(mut self) -> FileUploaderBuilder<'b> {
+        self.adaptive = true;
+        self
+    }
+
+    /// 为指定的文件上传指定上传限速器
+    ///
+    /// 将覆盖存储空间上传器中设置的上传限速器，对表单上传和分片上传均有效
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> FileUploaderBuilder<'b> {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     /// 指定上传对象的名称
     pub fn key(mut self, key: impl Into<Cow<'b, str>>) -> FileUploaderBuilder<'b> {
         self.key = Some(key.into());
@@ -302,6 +379,19 @@ impl<'b> FileUploaderBuilder<'b> {
         self
     }
 
+    /// 如果存储空间中已经存在内容相同的对象，则跳过上传
+    ///
+    /// 上传文件前将计算本地文件的 Etag，并使用给出的认证信息查询目标对象的元信息，
+    /// 如果两者的大小和 Etag 一致，将直接返回包含 `key` 和 `hash` 的上传响应而不传输任何数据，
+    /// 此时上传策略中的回调和自定义响应体都不会生效。
+    ///
+    /// 仅当指定了对象名称且上传的是文件时才会生效。如果目标对象不存在，将照常上传文件；
+    /// 如果因其他原因（例如认证信息错误）查询失败，则直接返回该错误
+    pub fn skip_if_identical(mut self, credential: impl Into<Cow<'b, Credential>>) -> FileUploaderBuilder<'b> {
+        self.skip_if_identical = Some(credential.into());
+        self
+    }
+
     /// 上传进度回调
     ///
     /// 将在上传期间反复回调指定的闭包，以获取上传进度。
@@ -330,6 +420,51 @@ impl<'b> FileUploaderBuilder<'b> {
         self
     }
 
+    /// 上传事件回调
+    ///
+    /// 将在上传期间回调指定的闭包，以获取包括上传开始，分片上传状态，域名冻结，上传速度，预计剩余时间以及上传结束在内的详细事件。
+    /// 可以与 `on_progress` 同时使用
+    pub fn on_event_ref(mut self, callback: &'b (dyn Fn(&UploadEvent) + Send + Sync)) -> FileUploaderBuilder<'b> {
+        self.on_event = Some(callback.into());
+        self
+    }
+
+    /// 上传事件回调
+    ///
+    /// 将在上传期间回调指定的闭包，以获取包括上传开始，分片上传状态，域名冻结，上传速度，预计剩余时间以及上传结束在内的详细事件。
+    /// 可以与 `on_progress` 同时使用
+    ///
+    /// ```rust,no_run
+    /// use qiniu_ng::{storage::uploader::UploadEvent, Client, Config};
+    /// # use std::{result::Result, error::Error};
+    ///
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+    /// let response = client
+    ///     .upload()
+    ///     .for_upload_token("[Upload Token]".to_owned())?
+    ///     .on_event(|event| match event {
+    ///         UploadEvent::PartRetried { part_number, host, error } => {
+    ///             println!("retry part {} on {}: {}", part_number, host, error);
+    ///         }
+    ///         UploadEvent::Progress {
+    ///             bytes_per_second,
+    ///             estimated_remaining_time,
+    ///             ..
+    ///         } => {
+    ///             println!("{} B/s, ETA: {:?}", bytes_per_second, estimated_remaining_time);
+    ///         }
+    ///         _ => {}
+    ///     })
+    ///     .upload_file("/path/to/file", "", None)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_event(mut self, callback: impl Fn(&UploadEvent) + Send + Sync + 'static) -> FileUploaderBuilder<'b> {
+        self.on_event = Some(Rob::Owned(Box::new(callback)));
+        self
+    }
+
     /// 开始上传文件
     ///
     /// # Arguments
@@ -344,6 +479,12 @@ impl<'b> FileUploaderBuilder<'b> {
         mime: Option<Mime>,
     ) -> UploadResult {
         let file_path = file_path.as_ref();
+        self.wait_until_runnable()?;
+        if let (Some(credential), Some(key)) = (&self.skip_if_identical, &self.key) {
+            if let Some(response) = self.find_identical_object(file_path, key, credential)? {
+                return Ok(response);
+            }
+        }
         let file_name = file_name.into();
         let file_name = if file_name.is_empty() { None } else { Some(file_name) };
         match self.resumable_policy {
@@ -372,6 +513,7 @@ impl<'b> FileUploaderBuilder<'b> {
         file_name: impl Into<Cow<'n, str>>,
         mime: Option<Mime>,
     ) -> UploadResult {
+        self.wait_until_runnable()?;
         let file_name = file_name.into();
         let file_name = if file_name.is_empty() { None } else { Some(file_name) };
         match self.resumable_policy {
@@ -382,12 +524,60 @@ impl<'b> FileUploaderBuilder<'b> {
         }
     }
 
+    fn wait_until_runnable(&self) -> Result<(), UploadError> {
+        if let Some(control) = &self.control {
+            control.wait_until_runnable()?;
+        }
+        Ok(())
+    }
+
+    fn find_identical_object(
+        &self,
+        file_path: &Path,
+        key: &str,
+        credential: &Credential,
+    ) -> Result<Option<UploadResponse>, UploadError> {
+        let rs_urls = self
+            .bucket_uploader
+            .rs_urls()
+            .iter()
+            .map(|url| url.as_ref())
+            .collect::<Vec<_>>();
+        let object_info: ObjectInfo = match self
+            .bucket_uploader
+            .http_client()
+            .post(&path::stat(self.bucket_uploader.bucket_name(), key), &rs_urls)
+            .token(TokenVersion::V2, credential.into())
+            .idempotent()
+            .accept_json()
+            .no_body()
+            .send()
+            .and_then(|mut response| response.parse_json())
+        {
+            Ok(object_info) => object_info,
+            Err(err) => match err.error_kind() {
+                // 仅当对象不存在时才继续上传，其他错误（例如认证失败）将直接返回
+                HTTPErrorKind::ResponseStatusCodeError(612, _) => return Ok(None),
+                _ => return Err(err.into()),
+            },
+        };
+        if object_info.size() != file_path.metadata()?.len() {
+            return Ok(None);
+        }
+        let hash = etag::from_file(file_path)?;
+        if hash != *object_info.hash() {
+            return Ok(None);
+        }
+        Ok(Some(json!({"key": key, "hash": hash}).into()))
+    }
+
     fn upload_file_by_form<'n>(
         self,
         file_path: &Path,
         file_name: Option<Cow<'n, str>>,
         mime: Option<Mime>,
     ) -> UploadResult {
+        let rate_limiter = self.effective_rate_limiter();
         let mut uploader = FormUploaderBuilder::new(&self.bucket_uploader, &self.upload_token);
         if let Some(key) = self.key {
             uploader = uploader.key(key);
@@ -405,6 +595,15 @@ impl<'b> FileUploaderBuilder<'b> {
         if let Some(callback) = &self.on_uploading_progress {
             uploader = uploader.on_uploading_progress(callback.as_ref());
         }
+        if let Some(callback) = &self.on_event {
+            uploader = uploader.on_event(callback.as_ref());
+        }
+        if let Some(rate_limiter) = &rate_limiter {
+            uploader = uploader.rate_limiter(rate_limiter);
+        }
+        if let Some(control) = self.control {
+            uploader = uploader.control(control);
+        }
         Ok(uploader
             .seekable_stream(
                 File::open(file_path)?,
@@ -421,8 +620,10 @@ impl<'b> FileUploaderBuilder<'b> {
         file_name: Option<Cow<'n, str>>,
         mime: Option<Mime>,
     ) -> UploadResult {
+        let rate_limiter = self.effective_rate_limiter();
         let mut uploader = ResumableUploaderBuilder::new(&self.bucket_uploader, self.upload_token)
-            .max_concurrency(self.max_concurrency);
+            .max_concurrency(self.max_concurrency)
+            .adaptive(self.adaptive);
         if let Some(key) = &self.key {
             uploader = uploader.key(key.to_owned());
         }
@@ -435,9 +636,18 @@ impl<'b> FileUploaderBuilder<'b> {
         if let Some(callback) = &self.on_uploading_progress {
             uploader = uploader.on_uploading_progress(callback.as_ref());
         }
+        if let Some(callback) = &self.on_event {
+            uploader = uploader.on_event(callback.as_ref());
+        }
+        if let Some(rate_limiter) = &rate_limiter {
+            uploader = uploader.rate_limiter(rate_limiter);
+        }
         if let Some(thread_pool) = self.thread_pool {
             uploader = uploader.thread_pool(thread_pool);
         }
+        if let Some(control) = self.control {
+            uploader = uploader.control(control);
+        }
         let mut uploader = uploader.file(
             File::open(file_path)?,
             file_path.into(),
@@ -473,6 +683,7 @@ impl<'b> FileUploaderBuilder<'b> {
         file_name: Option<Cow<str>>,
         mime: Option<Mime>,
     ) -> UploadResult {
+        let rate_limiter = self.effective_rate_limiter();
         let mut uploader = FormUploaderBuilder::new(&self.bucket_uploader, &self.upload_token);
         if let Some(key) = self.key {
             uploader = uploader.key(key);
@@ -490,6 +701,15 @@ impl<'b> FileUploaderBuilder<'b> {
         if let Some(callback) = &self.on_uploading_progress {
             uploader = uploader.on_uploading_progress(callback.as_ref());
         }
+        if let Some(callback) = &self.on_event {
+            uploader = uploader.on_event(callback.as_ref());
+        }
+        if let Some(rate_limiter) = &rate_limiter {
+            uploader = uploader.rate_limiter(rate_limiter);
+        }
+        if let Some(control) = self.control {
+            uploader = uploader.control(control);
+        }
         Ok(uploader
             .stream(
                 stream,
@@ -506,8 +726,10 @@ impl<'b> FileUploaderBuilder<'b> {
         file_name: Option<Cow<str>>,
         mime: Option<Mime>,
     ) -> UploadResult {
+        let rate_limiter = self.effective_rate_limiter();
         let mut uploader = ResumableUploaderBuilder::new(&self.bucket_uploader, self.upload_token)
-            .max_concurrency(self.max_concurrency);
+            .max_concurrency(self.max_concurrency)
+            .adaptive(self.adaptive);
         if let Some(key) = self.key {
             uploader = uploader.key(key);
         }
@@ -520,9 +742,18 @@ impl<'b> FileUploaderBuilder<'b> {
         if let Some(callback) = &self.on_uploading_progress {
             uploader = uploader.on_uploading_progress(callback.as_ref());
         }
+        if let Some(callback) = &self.on_event {
+            uploader = uploader.on_event(callback.as_ref());
+        }
+        if let Some(rate_limiter) = &rate_limiter {
+            uploader = uploader.rate_limiter(rate_limiter);
+        }
         if let Some(thread_pool) = self.thread_pool {
             uploader = uploader.thread_pool(thread_pool);
         }
+        if let Some(control) = self.control {
+            uploader = uploader.control(control);
+        }
         Ok(uploader
             .stream(
                 stream,
@@ -533,6 +764,12 @@ impl<'b> FileUploaderBuilder<'b> {
             .send()?)
     }
 
+    fn effective_rate_limiter(&self) -> Option<RateLimiter> {
+        self.rate_limiter
+            .to_owned()
+            .or_else(|| self.bucket_uploader.rate_limiter().cloned())
+    }
+
     fn guess_filename<'n>(file_path: &Path, file_name: Option<Cow<'n, str>>) -> Option<Cow<'n, str>> {
         file_name.or_else(|| {
             file_path
@@ -551,6 +788,55 @@ impl<'b> FileUploaderBuilder<'b> {
     }
 }
 
+impl FileUploaderBuilder<'static> {
+    /// 在后台线程中上传文件
+    ///
+    /// 参数与 `upload_file` 相同，返回的上传句柄可以用于暂停，恢复或取消上传，并等待上传结果
+    pub fn upload_file_in_background(
+        mut self,
+        file_path: impl Into<PathBuf>,
+        file_name: impl Into<Cow<'static, str>>,
+        mime: Option<Mime>,
+    ) -> UploadHandle {
+        let control = Arc::new(UploadControl::new());
+        self.control = Some(control.to_owned());
+        let file_path = file_path.into();
+        let file_name = file_name.into();
+        let thread_control = control.to_owned();
+        UploadHandle::new(
+            control,
+            thread::spawn(move || {
+                let result = self.upload_file(file_path, file_name, mime);
+                thread_control.finish();
+                result
+            }),
+        )
+    }
+
+    /// 在后台线程中上传文件流
+    ///
+    /// 参数与 `upload_stream` 相同，返回的上传句柄可以用于暂停，恢复或取消上传，并等待上传结果
+    pub fn upload_stream_in_background(
+        mut self,
+        stream: impl Read + Send + 'static,
+        file_name: impl Into<Cow<'static, str>>,
+        mime: Option<Mime>,
+    ) -> UploadHandle {
+        let control = Arc::new(UploadControl::new());
+        self.control = Some(control.to_owned());
+        let file_name = file_name.into();
+        let thread_control = control.to_owned();
+        UploadHandle::new(
+            control,
+            thread::spawn(move || {
+                let result = self.upload_stream(stream, file_name, mime);
+                thread_control.finish();
+                result
+            }),
+        )
+    }
+}
+
 /// 上传错误
 #[derive(Error, Debug)]
 pub enum UploadError {
@@ -563,3 +849,318 @@ pub enum UploadError {
 }
 /// 上传结果
 pub type UploadResult = Result<UploadResponse, UploadError>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            super::{bucket::BucketBuilder, object::path, region::RegionId},
+            BucketUploaderBuilder, UploadManager, UploadPolicyBuilder, UploadToken,
+        },
+        *,
+    };
+    use crate::{
+        config::ConfigBuilder,
+        http::{DomainsManagerBuilder, Error as HTTPError, ErrorKind as HTTPErrorKind, Method},
+    };
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock};
+    use std::{
+        boxed::Box,
+        error::Error,
+        fs,
+        io::Read,
+        result::Result,
+        sync::Mutex,
+        time::{Duration, Instant},
+    };
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_storage_uploader_skip_if_identical() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("file");
+        fs::write(&file_path, b"hello world")?;
+        let stat_path = path::stat("test-bucket", "test-key");
+        let stat_regex = "^https://rs\\.qbox\\.me".to_owned() + &regex::escape(&stat_path) + "$";
+        let remote_hash = etag::from_bytes(b"hello world");
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, &stat_regex, move |_, _| {
+                Ok(json_response(
+                    200,
+                    json!({
+                        "fsize": 11,
+                        "hash": remote_hash,
+                        "mimeType": "text/plain",
+                        "putTime": 15_735_614_000_000_000u64,
+                    }),
+                ))
+            })
+            .install(Method::POST, r"^http://z1h1\.com", |_, _| {
+                Ok(json_response(200, json!({"key": "test-key", "hash": "new-hash"})))
+            }),
+        );
+        let config = ConfigBuilder::default()
+            .http_request_handler(mock.clone())
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let credential = Credential::new("abcdefghklmnopq", "1234567890");
+        let policy = UploadPolicyBuilder::new_policy_for_object("test-bucket", "test-key", &config).build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .build();
+
+        let response = bucket_uploader
+            .upload_token(UploadToken::new(policy.to_owned(), &credential))
+            .key("test-key")
+            .skip_if_identical(&credential)
+            .upload_file(&file_path, "", None)?;
+        assert_eq!(mock.call_called(), 1);
+        assert_eq!(response.key(), Some("test-key"));
+        assert_eq!(response.hash(), Some(etag::from_bytes(b"hello world").as_str()));
+
+        fs::write(&file_path, b"hello qiniu")?;
+        let response = bucket_uploader
+            .upload_token(UploadToken::new(policy, &credential))
+            .key("test-key")
+            .skip_if_identical(&credential)
+            .upload_file(&file_path, "", None)?;
+        assert_eq!(mock.call_called(), 3);
+        assert_eq!(response.hash(), Some("new-hash"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_skip_if_identical_with_stat_errors() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("file");
+        fs::write(&file_path, b"hello world")?;
+        let stat_path = path::stat("test-bucket", "test-key");
+        let stat_regex = "^https://rs\\.qbox\\.me".to_owned() + &regex::escape(&stat_path) + "$";
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, &stat_regex, |_, called| match called {
+                1 => Ok(json_response(612, json!({"error": "no such file or directory"}))),
+                _ => Ok(json_response(401, json!({"error": "bad token"}))),
+            })
+            .install(Method::POST, r"^http://z1h1\.com", |_, _| {
+                Ok(json_response(200, json!({"key": "test-key", "hash": "new-hash"})))
+            }),
+        );
+        let config = ConfigBuilder::default()
+            .http_request_handler(mock.clone())
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let credential = Credential::new("abcdefghklmnopq", "1234567890");
+        let policy = UploadPolicyBuilder::new_policy_for_object("test-bucket", "test-key", &config).build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .build();
+
+        // 对象不存在，将继续上传
+        let response = bucket_uploader
+            .upload_token(UploadToken::new(policy.to_owned(), &credential))
+            .key("test-key")
+            .skip_if_identical(&credential)
+            .upload_file(&file_path, "", None)?;
+        assert_eq!(mock.call_called(), 2);
+        assert_eq!(response.hash(), Some("new-hash"));
+
+        // 查询对象时认证失败，将直接返回错误而不再上传
+        match bucket_uploader
+            .upload_token(UploadToken::new(policy, &credential))
+            .key("test-key")
+            .skip_if_identical(&credential)
+            .upload_file(&file_path, "", None)
+            .unwrap_err()
+        {
+            UploadError::QiniuError(err) => match err.error_kind() {
+                HTTPErrorKind::ResponseStatusCodeError(401, _) => {}
+                kind => panic!("Unexpected HTTP error kind: {}", kind),
+            },
+            err => panic!("Unexpected error: {}", err),
+        }
+        assert_eq!(mock.call_called(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_skip_if_identical_with_bucket_region() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("file");
+        fs::write(&file_path, b"hello world")?;
+        let stat_path = path::stat("test-bucket", "test-key");
+        let stat_regex = "^https://rs-z1\\.qbox\\.me".to_owned() + &regex::escape(&stat_path) + "$";
+        let remote_hash = etag::from_bytes(b"hello world");
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::POST, &stat_regex, move |_, _| {
+                Ok(json_response(
+                    200,
+                    json!({
+                        "fsize": 11,
+                        "hash": remote_hash,
+                        "mimeType": "text/plain",
+                        "putTime": 15_735_614_000_000_000u64,
+                    }),
+                ))
+            }),
+        );
+        let config = ConfigBuilder::default()
+            .http_request_handler(mock.clone())
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let credential = Credential::new("abcdefghklmnopq", "1234567890");
+        let policy = UploadPolicyBuilder::new_policy_for_object("test-bucket", "test-key", &config).build();
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            Cow::Borrowed(&credential),
+            UploadManager::new(config),
+        )
+        .region_id(RegionId::Z1)
+        .build();
+        let bucket_uploader = bucket.uploader().build();
+
+        // 查询对象时应当使用存储空间所在区域的 RS 服务器
+        let response = bucket_uploader
+            .upload_token(UploadToken::new(policy, &credential))
+            .key("test-key")
+            .skip_if_identical(&credential)
+            .upload_file(&file_path, "", None)?;
+        assert_eq!(mock.call_called(), 1);
+        assert_eq!(response.hash(), Some(etag::from_bytes(b"hello world").as_str()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_rate_limiter() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("file");
+        fs::write(&file_path, vec![0u8; 3 << 19])?;
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(Method::POST, r"^http://z1h1\.com", |_, _| {
+                    Ok(json_response(200, json!({"key": "test-key", "hash": "test-hash"})))
+                }),
+            )
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_object("test-bucket", "test-key", &config).build();
+        let upload_token = UploadToken::new(policy, Credential::new("abcdefghklmnopq", "1234567890"));
+        let rate_limiter = RateLimiter::new(1 << 20);
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .rate_limiter(rate_limiter.to_owned())
+        .build();
+
+        // 令牌桶初始时是满的，虽然该 HTTPCaller 没有通过读取器读取请求体，但发送结束后也将补充消耗令牌，
+        // 因此 1 MB/s 的速率下上传 1.5 MB 的文件应该耗时 0.5 秒左右
+        let timer = Instant::now();
+        bucket_uploader
+            .upload_token(upload_token.to_string())
+            .key("test-key")
+            .never_be_resumable()
+            .upload_file(&file_path, "", None)?;
+        assert!(timer.elapsed() >= Duration::from_millis(400));
+
+        // 提高限速值后，再次上传应该很快完成
+        rate_limiter.set_bytes_per_second(1 << 30);
+        let timer = Instant::now();
+        bucket_uploader
+            .upload_token(upload_token.to_string())
+            .key("test-key")
+            .never_be_resumable()
+            .upload_file(&file_path, "", None)?;
+        assert!(timer.elapsed() < Duration::from_millis(400));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_rate_limiter_with_retries() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join("file");
+        fs::write(&file_path, vec![0u8; 3 << 19])?;
+        let timer = Instant::now();
+        let sent = Mutex::new(Vec::new());
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(Method::POST, r"^http://z1h1\.com", move |request, called| {
+                    // 模拟 HTTPCaller 逐块读取并发送请求体
+                    let mut body_reader = request.body_reader().unwrap();
+                    let mut buf = [0u8; 1 << 14];
+                    loop {
+                        let size = body_reader.read(&mut buf).unwrap();
+                        if size == 0 {
+                            break;
+                        }
+                        sent.lock().unwrap().push((timer.elapsed(), size as u64));
+                        // 发送至今的数据总量不能超过令牌桶容量与按限速值累积的令牌之和，允许有一个小块的误差
+                        let total_sent: u64 = sent.lock().unwrap().iter().map(|&(_, size)| size).sum();
+                        let allowed = (1u64 << 20) + (timer.elapsed().as_secs_f64() * f64::from(1 << 20)) as u64;
+                        assert!(total_sent <= allowed + (1 << 16), "sent: {:?}", sent.lock().unwrap());
+                    }
+                    if called == 1 {
+                        Err(HTTPError::new_retryable_error_from_parts(
+                            HTTPErrorKind::MaliciousResponse,
+                            true,
+                            None,
+                            None,
+                        ))
+                    } else {
+                        Ok(json_response(200, json!({"key": "test-key", "hash": "test-hash"})))
+                    }
+                }),
+            )
+            .http_request_retry_delay(Duration::from_millis(0))
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_object("test-bucket", "test-key", &config).build();
+        let upload_token = UploadToken::new(policy, Credential::new("abcdefghklmnopq", "1234567890"));
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .rate_limiter(RateLimiter::new(1 << 20))
+        .build();
+
+        // 发送过程中即被限速，且重试时同样需要消耗令牌，1 MB/s 的速率下两次发送 1.5 MB 的表单应该耗时 2 秒左右
+        bucket_uploader
+            .upload_token(upload_token.to_string())
+            .key("test-key")
+            .never_be_resumable()
+            .upload_file(&file_path, "", None)?;
+        assert!(
+            timer.elapsed() >= Duration::from_millis(1800),
+            "elapsed: {:?}",
+            timer.elapsed()
+        );
+        Ok(())
+    }
+}
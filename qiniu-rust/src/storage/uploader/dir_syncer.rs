@@ -0,0 +1,475 @@
+use super::{
+    super::bucket::Bucket,
+    dir_uploader::{DirUploadError, DirUploadFile, DirUploadSummary, DirUploaderBuilder},
+};
+use crate::utils::etag;
+use assert_impl::assert_impl;
+use getset::Getters;
+use std::collections::{HashMap, HashSet};
+
+/// 目录同步器生成器
+///
+/// 通过 `DirUploaderBuilder::sync_with` 创建，将本地目录增量同步到存储空间中。
+/// 同步时将列举存储空间中以 `key_prefix` 为前缀的对象，对于同名对象，先比较文件大小，
+/// 大小一致时再比较本地文件的 Etag 与对象的 Etag，仅上传新增或已修改的文件
+///
+/// ```rust,no_run
+/// use qiniu_ng::{
+///     storage::uploader::{UploadPolicyBuilder, UploadToken},
+///     Client, Config,
+/// };
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let config = Config::default();
+/// let client = Client::new("[Access Key]", "[Secret Key]", config.to_owned());
+/// let bucket = client.storage().bucket("[Bucket name]").build();
+/// let policy = UploadPolicyBuilder::new_policy_for_objects_with_prefix("[Bucket name]", "static/", &config).build();
+/// let bucket_uploader = bucket.uploader().build();
+/// let syncer = bucket_uploader
+///     .upload_dir("/path/to/dir", UploadToken::new(policy, client.credential()))
+///     .key_prefix("static/")
+///     .sync_with(&bucket)
+///     .delete_extraneous();
+/// let plan = syncer.plan()?;
+/// println!("{} files to upload, {} objects to delete", plan.files_to_upload().count(), plan.extraneous().len());
+/// let summary = syncer.execute(plan)?;
+/// assert!(summary.is_all_succeeded());
+/// # Ok(())
+/// # }
+/// ```
+pub struct DirSyncBuilder<'b, 'r: 'b> {
+    uploader: DirUploaderBuilder<'b>,
+    bucket: &'b Bucket<'r>,
+    delete_extraneous: bool,
+}
+
+impl<'b, 'r: 'b> DirSyncBuilder<'b, 'r> {
+    pub(super) fn new(uploader: DirUploaderBuilder<'b>, bucket: &'b Bucket<'r>) -> DirSyncBuilder<'b, 'r> {
+        DirSyncBuilder {
+            uploader,
+            bucket,
+            delete_extraneous: false,
+        }
+    }
+
+    /// 删除存储空间中多余的对象
+    ///
+    /// 以 `key_prefix` 为前缀，但在本地目录中没有对应文件的对象都将被删除。
+    /// 注意，被 `include` 或 `exclude` 过滤掉的文件对应的对象同样被认为是多余的。默认不删除
+    pub fn delete_extraneous(mut self) -> Self {
+        self.delete_extraneous = true;
+        self
+    }
+
+    /// 生成同步计划
+    ///
+    /// 仅比较本地目录与存储空间中的对象，不会上传或删除任何对象，可以用于预演同步操作
+    pub fn plan(&self) -> Result<DirSyncPlan, DirUploadError> {
+        self.check_bucket()?;
+        let mut remote_objects = HashMap::new();
+        let prefix = self.uploader.key_prefix.as_ref();
+        let mut list = self.bucket.list();
+        if !prefix.is_empty() {
+            list = list.prefix(prefix);
+        }
+        for object in list.iter() {
+            let object = object?;
+            remote_objects.insert(object.key().to_owned(), (object.size(), object.hash().to_owned()));
+        }
+
+        let mut plan = DirSyncPlan {
+            added: Vec::new(),
+            modified: Vec::new(),
+            unchanged: Vec::new(),
+            extraneous: Vec::new(),
+            delete_extraneous: self.delete_extraneous,
+        };
+        let mut local_keys = HashSet::new();
+        for file in self.uploader.scan()? {
+            local_keys.insert(file.key().to_owned());
+            match remote_objects.get(file.key()) {
+                None => plan.added.push(file),
+                Some((size, hash)) if *size == file.size() && etag::from_file(file.path())? == *hash => {
+                    plan.unchanged.push(file)
+                }
+                Some(_) => plan.modified.push(file),
+            }
+        }
+        plan.extraneous = remote_objects
+            .into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| !local_keys.contains(key))
+            .collect();
+        plan.extraneous.sort();
+        Ok(plan)
+    }
+
+    /// 开始同步目录
+    ///
+    /// 仅当生成同步计划失败时才会返回错误，单个文件的上传错误和单个对象的删除错误都将记录在返回的 `DirSyncSummary` 中
+    pub fn start(self) -> DirSyncResult {
+        let plan = self.plan()?;
+        self.execute(plan)
+    }
+
+    /// 按照指定的同步计划同步目录
+    ///
+    /// 可以在 `plan` 预演后确认无误再执行，以确保实际操作与预演结果一致
+    pub fn execute(self, plan: DirSyncPlan) -> DirSyncResult {
+        self.check_bucket()?;
+        let upload = self
+            .uploader
+            .upload_files(plan.added.into_iter().chain(plan.modified).collect());
+        let mut summary = DirSyncSummary {
+            upload,
+            skipped: plan.unchanged,
+            deleted: Vec::new(),
+            delete_failed: Vec::new(),
+        };
+        if plan.delete_extraneous && !plan.extraneous.is_empty() {
+            let mut batch = self.bucket.batch();
+            for key in plan.extraneous.iter() {
+                batch.delete(key);
+            }
+            // 批量删除出错时，此前已经执行完毕的删除操作的结果仍然有效，其余对象均视为删除失败
+            let (results, batch_error) = match batch.execute() {
+                Ok(results) => (results, None),
+                Err(err) => {
+                    let batch_error = err.to_string();
+                    (err.into_completed(), Some(batch_error))
+                }
+            };
+            let mut results = results.into_iter();
+            for key in plan.extraneous.into_iter() {
+                match results.next() {
+                    Some(ref result) if result.is_success() => summary.deleted.push(key),
+                    Some(result) => {
+                        let error = result.error().unwrap_or_else(|| result.code().to_string());
+                        summary.delete_failed.push((key, error));
+                    }
+                    None => {
+                        let error = batch_error
+                            .to_owned()
+                            .unwrap_or_else(|| "Missing batch operation result".to_owned());
+                        summary.delete_failed.push((key, error));
+                    }
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    fn check_bucket(&self) -> Result<(), DirUploadError> {
+        let uploader_bucket_name = self.uploader.bucket_uploader.bucket_name();
+        if uploader_bucket_name != self.bucket.name() {
+            return Err(DirUploadError::BucketMismatch {
+                uploader: uploader_bucket_name.to_owned(),
+                bucket: self.bucket.name().to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+/// 目录同步计划
+///
+/// 通过 `DirSyncBuilder::plan` 获取
+#[derive(Getters, Debug, Clone)]
+pub struct DirSyncPlan {
+    /// 存储空间中不存在对应对象，需要上传的文件
+    #[get = "pub"]
+    added: Vec<DirUploadFile>,
+
+    /// 内容与存储空间中的对象不一致，需要重新上传的文件
+    #[get = "pub"]
+    modified: Vec<DirUploadFile>,
+
+    /// 内容与存储空间中的对象一致，将被跳过的文件
+    #[get = "pub"]
+    unchanged: Vec<DirUploadFile>,
+
+    /// 存储空间中多余的对象名称，仅当指定 `delete_extraneous` 时才会被删除
+    #[get = "pub"]
+    extraneous: Vec<String>,
+
+    delete_extraneous: bool,
+}
+
+impl DirSyncPlan {
+    /// 需要上传的所有文件
+    pub fn files_to_upload(&self) -> impl Iterator<Item = &DirUploadFile> {
+        self.added.iter().chain(self.modified.iter())
+    }
+
+    /// 需要删除的所有对象名称
+    pub fn keys_to_delete(&self) -> &[String] {
+        if self.delete_extraneous {
+            &self.extraneous
+        } else {
+            &[]
+        }
+    }
+}
+
+/// 目录同步结果汇总
+#[derive(Getters, Debug)]
+#[get = "pub"]
+pub struct DirSyncSummary {
+    /// 上传新增或已修改文件的结果
+    upload: DirUploadSummary,
+    /// 内容一致而被跳过的文件
+    skipped: Vec<DirUploadFile>,
+    /// 成功删除的多余对象名称
+    deleted: Vec<String>,
+    /// 删除失败的多余对象名称及其错误信息
+    delete_failed: Vec<(String, String)>,
+}
+
+impl DirSyncSummary {
+    /// 是否所有上传和删除操作都成功
+    pub fn is_all_succeeded(&self) -> bool {
+        self.upload.is_all_succeeded() && self.delete_failed.is_empty()
+    }
+}
+
+/// 目录同步结果
+pub type DirSyncResult = Result<DirSyncSummary, DirUploadError>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            super::{bucket::BucketBuilder, region::RegionId},
+            BucketUploaderBuilder, UploadManager, UploadPolicyBuilder, UploadToken,
+        },
+        *,
+    };
+    use crate::{
+        config::{Config, ConfigBuilder},
+        credential::Credential,
+        http::{DomainsManagerBuilder, Method},
+        utils::base64,
+    };
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock};
+    use serde_json::{json, Value};
+    use std::{boxed::Box, error::Error, fs, result::Result};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_storage_uploader_dir_syncer() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        fs::write(dir.path().join("unchanged.txt"), b"unchanged")?;
+        fs::write(dir.path().join("resized.txt"), b"resized")?;
+        fs::write(dir.path().join("modified.txt"), b"modified")?;
+        fs::write(dir.path().join("added.txt"), b"added")?;
+
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"/list\?", |request, _| {
+                assert!(request.url().contains("prefix=static%2F"));
+                Ok(json_response(
+                    200,
+                    json!({
+                        "items": [
+                            list_item("static/unchanged.txt", 9, &etag::from_bytes(b"unchanged")),
+                            list_item("static/resized.txt", 1, &etag::from_bytes(b"resized")),
+                            list_item("static/modified.txt", 8, &etag::from_bytes(b"original")),
+                            list_item("static/deleted.txt", 7, &etag::from_bytes(b"deleted")),
+                        ],
+                    }),
+                ))
+            })
+            .install(Method::POST, r"^http://z1h1\.com", |request, _| {
+                let body = String::from_utf8_lossy(request.body().as_ref().unwrap().as_ref()).into_owned();
+                assert!(!body.contains("static/unchanged.txt"));
+                Ok(json_response(200, json!({"hash": "fake-hash"})))
+            })
+            .install(Method::POST, r"/batch$", |request, _| {
+                let body = String::from_utf8(request.body().as_ref().unwrap().to_vec()).unwrap();
+                assert_eq!(
+                    serde_urlencoded::from_str::<Vec<(String, String)>>(&body).unwrap(),
+                    vec![(
+                        "op".to_owned(),
+                        "/delete/".to_owned() + &base64::urlsafe(b"test-bucket:static/deleted.txt")
+                    )]
+                );
+                Ok(json_response(200, json!([{"code": 200}])))
+            }),
+        );
+        let config = get_config(mock.clone());
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(config.to_owned()),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config.to_owned(),
+        )
+        .build();
+        let syncer = bucket_uploader
+            .upload_dir(dir.path(), upload_token(&config))
+            .key_prefix("static/")
+            .sync_with(&bucket);
+
+        let plan = syncer.plan()?;
+        assert_eq!(mock.call_called(), 1);
+        assert_eq!(keys(plan.added()), vec!["static/added.txt"]);
+        assert_eq!(keys(plan.modified()), vec!["static/modified.txt", "static/resized.txt"]);
+        assert_eq!(keys(plan.unchanged()), vec!["static/unchanged.txt"]);
+        assert_eq!(plan.extraneous(), &["static/deleted.txt".to_owned()]);
+        assert_eq!(plan.files_to_upload().count(), 3);
+        assert!(plan.keys_to_delete().is_empty());
+
+        let summary = syncer.delete_extraneous().start()?;
+        assert_eq!(mock.call_called(), 6);
+        assert!(summary.is_all_succeeded());
+        assert_eq!(summary.upload().succeeded().len(), 3);
+        assert_eq!(keys(summary.skipped()), vec!["static/unchanged.txt"]);
+        assert_eq!(summary.deleted(), &["static/deleted.txt".to_owned()]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_dir_syncer_delete_partially() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let mock = CounterCallMock::new(
+            CallHandlers::new(|request| {
+                panic!("Unexpected Request: {} {}", request.method(), request.url());
+            })
+            .install(Method::GET, r"/list\?", |_, _| {
+                Ok(json_response(
+                    200,
+                    json!({
+                        "items": [
+                            list_item("static/a.txt", 1, &etag::from_bytes(b"a")),
+                            list_item("static/b.txt", 1, &etag::from_bytes(b"b")),
+                            list_item("static/c.txt", 1, &etag::from_bytes(b"c")),
+                        ],
+                    }),
+                ))
+            })
+            .install(Method::POST, r"/batch$", |_, called| match called {
+                1 => Ok(json_response(200, json!([{"code": 200}]))),
+                2 => Ok(json_response(
+                    298,
+                    json!([{"code": 612, "data": {"error": "no such file or directory"}}]),
+                )),
+                _ => Ok(json_response(631, json!({"error": "no such bucket"}))),
+            }),
+        );
+        let config = ConfigBuilder::default()
+            .http_request_handler(mock.clone())
+            .upload_logger(None)
+            .batch_max_operation_size(1)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let bucket = BucketBuilder::new(
+            "test-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(config.to_owned()),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config.to_owned(),
+        )
+        .build();
+        let summary = bucket_uploader
+            .upload_dir(dir.path(), upload_token(&config))
+            .key_prefix("static/")
+            .sync_with(&bucket)
+            .delete_extraneous()
+            .start()?;
+        assert_eq!(mock.call_called(), 4);
+        assert!(!summary.is_all_succeeded());
+        assert_eq!(summary.deleted(), &["static/a.txt".to_owned()]);
+        assert_eq!(summary.delete_failed().len(), 2);
+        assert_eq!(
+            summary.delete_failed()[0],
+            ("static/b.txt".to_owned(), "no such file or directory".to_owned())
+        );
+        assert_eq!(summary.delete_failed()[1].0, "static/c.txt");
+        assert!(summary.delete_failed()[1].1.contains("631"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_dir_syncer_bucket_mismatch() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let config = get_config(CallHandlers::new(|request| {
+            panic!("Unexpected Request: {} {}", request.method(), request.url());
+        }));
+        let bucket = BucketBuilder::new(
+            "other-bucket".into(),
+            get_credential().into(),
+            UploadManager::new(config.to_owned()),
+        )
+        .region_id(RegionId::Z0)
+        .build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config.to_owned(),
+        )
+        .build();
+        let err = bucket_uploader
+            .upload_dir(dir.path(), upload_token(&config))
+            .sync_with(&bucket)
+            .start()
+            .unwrap_err();
+        match err {
+            DirUploadError::BucketMismatch { uploader, bucket } => {
+                assert_eq!(uploader, "test-bucket");
+                assert_eq!(bucket, "other-bucket");
+            }
+            err => panic!("Unexpected error: {}", err),
+        }
+        Ok(())
+    }
+
+    fn keys(files: &[DirUploadFile]) -> Vec<&str> {
+        files.iter().map(|file| file.key().as_str()).collect()
+    }
+
+    fn list_item(key: &str, size: u64, hash: &str) -> Value {
+        json!({
+            "key": key,
+            "fsize": size,
+            "hash": hash,
+            "mimeType": "text/plain",
+            "putTime": 15_735_614_000_000_000u64,
+        })
+    }
+
+    fn get_config(handler: impl qiniu_http::HTTPCaller + 'static) -> Config {
+        ConfigBuilder::default()
+            .http_request_handler(handler)
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build()
+    }
+
+    fn upload_token(config: &Config) -> UploadToken<'static> {
+        let policy = UploadPolicyBuilder::new_policy_for_objects_with_prefix("test-bucket", "static/", config).build();
+        UploadToken::new(policy, get_credential())
+    }
+
+    fn get_credential() -> Credential {
+        Credential::new("abcdefghklmnopq", "1234567890")
+    }
+}
@@ -0,0 +1,614 @@
+use super::{
+    super::bucket::Bucket, dir_syncer::DirSyncBuilder, BucketUploader, UploadError, UploadResponse, UploadResult,
+};
+use crate::utils::ron::Ron;
+use assert_impl::assert_impl;
+use getset::{CopyGetters, Getters};
+use glob::{MatchOptions, Pattern, PatternError};
+use rayon::ThreadPoolBuilder;
+use std::{
+    borrow::Cow,
+    io::Error as IOError,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+        Mutex,
+    },
+};
+use thiserror::Error;
+use walkdir::{DirEntry, WalkDir};
+
+const GLOB_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// 目录上传器生成器
+///
+/// 通过 `BucketUploader::upload_dir` 创建，遍历指定目录并将其中的文件并发上传到存储空间中。
+/// 单个文件上传失败不会中断整个目录的上传，所有文件的上传结果将汇总在 `DirUploadSummary` 中返回
+///
+/// ```rust,no_run
+/// use qiniu_ng::{
+///     storage::uploader::{UploadPolicyBuilder, UploadToken},
+///     Client, Config,
+/// };
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let config = Config::default();
+/// let client = Client::new("[Access Key]", "[Secret Key]", config.to_owned());
+/// let policy = UploadPolicyBuilder::new_policy_for_objects_with_prefix("[Bucket name]", "static/", &config).build();
+/// let summary = client
+///     .upload()
+///     .for_bucket_name("[Bucket name]", "[Access Key]")
+///     .build()
+///     .upload_dir("/path/to/dir", UploadToken::new(policy, client.credential()))
+///     .include("**/*.js")
+///     .include("**/*.css")
+///     .exclude("node_modules")
+///     .key_prefix("static/")
+///     .on_progress(|progress| {
+///         println!("{}/{} bytes", progress.uploaded_bytes(), progress.total_bytes());
+///     })
+///     .start()?;
+/// for (file, err) in summary.failed() {
+///     println!("failed to upload {:?}: {}", file.path(), err);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct DirUploaderBuilder<'b> {
+    pub(super) bucket_uploader: &'b BucketUploader,
+    upload_token: Cow<'b, str>,
+    dir_path: PathBuf,
+    includes: Vec<Cow<'b, str>>,
+    excludes: Vec<Cow<'b, str>>,
+    pub(super) key_prefix: Cow<'b, str>,
+    key_template: Cow<'b, str>,
+    upload_threshold: Option<u32>,
+    max_concurrency: usize,
+    #[allow(clippy::type_complexity)]
+    on_file_progress: Option<Box<dyn Fn(&DirUploadFile, u64) + Send + Sync + 'b>>,
+    #[allow(clippy::type_complexity)]
+    on_file_completed: Option<Box<dyn Fn(&DirUploadFile, &UploadResult) + Send + Sync + 'b>>,
+    #[allow(clippy::type_complexity)]
+    on_progress: Option<Box<dyn Fn(&DirUploadProgress) + Send + Sync + 'b>>,
+}
+
+impl<'b> DirUploaderBuilder<'b> {
+    pub(super) fn new(
+        bucket_uploader: &'b BucketUploader,
+        dir_path: PathBuf,
+        upload_token: Cow<'b, str>,
+    ) -> DirUploaderBuilder<'b> {
+        DirUploaderBuilder {
+            bucket_uploader,
+            upload_token,
+            dir_path,
+            includes: Vec::new(),
+            excludes: Vec::new(),
+            key_prefix: "".into(),
+            key_template: "$(path)".into(),
+            upload_threshold: None,
+            max_concurrency: 0,
+            on_file_progress: None,
+            on_file_completed: None,
+            on_progress: None,
+        }
+    }
+
+    /// 仅上传匹配指定 Glob 模式的文件
+    ///
+    /// 模式将与文件相对于上传目录的路径进行匹配，路径分隔符总是为 `/`，且 `*` 不匹配路径分隔符，
+    /// 如果需要匹配任意层级的目录，请使用 `**`，例如 `**/*.png`。
+    /// 可以多次调用以指定多个模式，文件只要匹配其中任意一个即可。默认上传所有文件
+    pub fn include(mut self, pattern: impl Into<Cow<'b, str>>) -> DirUploaderBuilder<'b> {
+        self.includes.push(pattern.into());
+        self
+    }
+
+    /// 排除匹配指定 Glob 模式的文件或目录
+    ///
+    /// 模式匹配规则与 `include` 相同，如果目录被排除，其中所有文件都将不会被上传。
+    /// 可以多次调用以指定多个模式，排除规则优先于 `include` 指定的规则
+    pub fn exclude(mut self, pattern: impl Into<Cow<'b, str>>) -> DirUploaderBuilder<'b> {
+        self.excludes.push(pattern.into());
+        self
+    }
+
+    /// 指定对象名称前缀
+    ///
+    /// 前缀将被添加在 `key_template` 生成的名称之前
+    pub fn key_prefix(mut self, prefix: impl Into<Cow<'b, str>>) -> DirUploaderBuilder<'b> {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// 指定对象名称模版
+    ///
+    /// 模版中可以使用以下变量：
+    ///
+    /// * `$(path)` - 文件相对于上传目录的路径，例如 `images/logo.png`
+    /// * `$(dir)` - 文件相对于上传目录的所在目录，如果非空则总是以 `/` 结尾，例如 `images/`
+    /// * `$(fname)` - 文件名称，例如 `logo.png`
+    /// * `$(basename)` - 不包含扩展名的文件名称，例如 `logo`
+    /// * `$(ext)` - 包含 `.` 的文件扩展名，例如 `.png`，如果文件没有扩展名则为空
+    ///
+    /// 默认为 `$(path)`
+    pub fn key_template(mut self, template: impl Into<Cow<'b, str>>) -> DirUploaderBuilder<'b> {
+        self.key_template = template.into();
+        self
+    }
+
+    /// 指定分片上传策略阙值
+    ///
+    /// 对每个文件都将分别应用该阙值，默认将采用客户端配置中的配置项
+    pub fn upload_threshold(mut self, threshold: u32) -> DirUploaderBuilder<'b> {
+        self.upload_threshold = Some(threshold);
+        self
+    }
+
+    /// 同时上传文件的最大数量
+    ///
+    /// 文件将在存储空间上传器的线程池中并发上传，默认最大并发度等于线程池的大小。
+    /// 由于每个文件都将占用线程池中的一个线程，实际并发度不会超过线程池的大小，超出的部分将被忽略。
+    /// 如果存储空间上传器没有指定线程池，将为本次上传创建专用线程池，其大小等于该最大并发度
+    ///
+    /// `concurrency` 必须大于 0
+    pub fn max_concurrency(mut self, concurrency: usize) -> DirUploaderBuilder<'b> {
+        assert!(concurrency > 0);
+        self.max_concurrency = concurrency;
+        self
+    }
+
+    /// 单个文件上传进度回调
+    ///
+    /// 闭包的第一个参数为正在上传的文件，第二个参数为该文件已经上传的数据量，单位为字节。
+    /// 由于文件是并发上传的，该闭包可能被多个线程同时调用
+    pub fn on_file_progress(
+        mut self,
+        callback: impl Fn(&DirUploadFile, u64) + Send + Sync + 'b,
+    ) -> DirUploaderBuilder<'b> {
+        self.on_file_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// 单个文件上传完毕回调
+    ///
+    /// 无论上传成功与否，每个文件上传完毕后都将回调该闭包
+    pub fn on_file_completed(
+        mut self,
+        callback: impl Fn(&DirUploadFile, &UploadResult) + Send + Sync + 'b,
+    ) -> DirUploaderBuilder<'b> {
+        self.on_file_completed = Some(Box::new(callback));
+        self
+    }
+
+    /// 目录整体上传进度回调
+    ///
+    /// 将在上传期间反复回调，以获取所有文件的总体上传进度
+    pub fn on_progress(mut self, callback: impl Fn(&DirUploadProgress) + Send + Sync + 'b) -> DirUploaderBuilder<'b> {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// 将目录同步到指定的存储空间
+    ///
+    /// 同步时仅上传新增或已修改的文件，存储空间必须与存储空间上传器对应同一个存储空间，
+    /// 否则生成同步计划或同步目录时将返回 `DirUploadError::BucketMismatch` 错误
+    pub fn sync_with<'r: 'b>(self, bucket: &'b Bucket<'r>) -> DirSyncBuilder<'b, 'r> {
+        DirSyncBuilder::new(self, bucket)
+    }
+
+    /// 开始上传目录
+    ///
+    /// 仅当遍历目录失败或 Glob 模式非法时才会返回错误，单个文件的上传错误将记录在返回的 `DirUploadSummary` 中
+    pub fn start(self) -> DirUploadResult {
+        let files = self.scan()?;
+        Ok(self.upload_files(files))
+    }
+
+    pub(super) fn scan(&self) -> Result<Vec<DirUploadFile>, DirUploadError> {
+        let includes = compile_patterns(&self.includes)?;
+        let excludes = compile_patterns(&self.excludes)?;
+        let mut files = Vec::new();
+        let walker = WalkDir::new(&self.dir_path)
+            .follow_links(true)
+            .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0 || !entry.file_type().is_dir() || !is_matched(&excludes, &self.relative_path(entry))
+            });
+        for entry in walker {
+            let entry = entry.map_err(IOError::from)?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative_path = self.relative_path(&entry);
+            if is_matched(&excludes, &relative_path) || !includes.is_empty() && !is_matched(&includes, &relative_path) {
+                continue;
+            }
+            files.push(DirUploadFile {
+                key: self.render_key(&relative_path),
+                size: entry.metadata().map_err(IOError::from)?.len(),
+                path: entry.into_path(),
+            });
+        }
+        Ok(files)
+    }
+
+    pub(super) fn upload_files(&self, files: Vec<DirUploadFile>) -> DirUploadSummary {
+        let status = ProgressStatus {
+            uploaded_bytes: AtomicU64::new(0),
+            total_bytes: files.iter().map(|file| file.size).sum(),
+            completed_files: AtomicUsize::new(0),
+            failed_files: AtomicUsize::new(0),
+            total_files: files.len(),
+        };
+        let results = Mutex::new(Vec::with_capacity(files.len()));
+        let queue = Mutex::new(files.into_iter());
+        let thread_pool = self
+            .bucket_uploader
+            .thread_pool()
+            .map(Ron::Referenced)
+            .unwrap_or_else(|| {
+                Ron::Owned(
+                    ThreadPoolBuilder::new()
+                        .num_threads(self.max_concurrency)
+                        .thread_name(|index| format!("dir_uploader_thread_{}", index))
+                        .build()
+                        .unwrap(),
+                )
+            });
+        let concurrency = {
+            let mut c = thread_pool.current_num_threads();
+            if (1..c).contains(&self.max_concurrency) {
+                c = self.max_concurrency;
+            }
+            c
+        };
+
+        thread_pool.scope(|s| {
+            for _ in 0..concurrency {
+                s.spawn(|_| loop {
+                    let file = queue.lock().unwrap().next();
+                    match file {
+                        Some(file) => {
+                            let result = self.upload_file(&file, &status);
+                            if let Some(callback) = &self.on_file_completed {
+                                callback(&file, &result);
+                            }
+                            self.report_progress(&status);
+                            results.lock().unwrap().push((file, result));
+                        }
+                        None => {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
+        let mut summary = DirUploadSummary {
+            succeeded: Vec::with_capacity(results.len()),
+            failed: Vec::new(),
+        };
+        for (file, result) in results.into_iter() {
+            match result {
+                Ok(response) => summary.succeeded.push((file, response)),
+                Err(err) => summary.failed.push((file, err)),
+            }
+        }
+        summary
+    }
+
+    fn upload_file(&self, file: &DirUploadFile, status: &ProgressStatus) -> UploadResult {
+        let last_uploaded = AtomicU64::new(0);
+        let on_progress = |uploaded: u64, _: Option<u64>| {
+            let last = last_uploaded.swap(uploaded, Relaxed);
+            if uploaded >= last {
+                status.uploaded_bytes.fetch_add(uploaded - last, Relaxed);
+            } else {
+                status.uploaded_bytes.fetch_sub(last - uploaded, Relaxed);
+            }
+            if let Some(callback) = &self.on_file_progress {
+                callback(file, uploaded);
+            }
+            self.report_progress(status);
+        };
+        let mut uploader = self
+            .bucket_uploader
+            .upload_token(self.upload_token.as_ref())
+            .key(file.key.as_str())
+            .on_progress_ref(&on_progress);
+        if let Some(threshold) = self.upload_threshold {
+            uploader = uploader.upload_threshold(threshold);
+        }
+        let result = uploader.upload_file(&file.path, "", None);
+
+        // 上传完毕后校正已上传数据量，确保成功的文件被完整计入，失败的文件不被计入
+        let last = last_uploaded.load(Relaxed);
+        match &result {
+            Ok(_) => {
+                status.uploaded_bytes.fetch_add(file.size.saturating_sub(last), Relaxed);
+                status.completed_files.fetch_add(1, Relaxed);
+            }
+            Err(_) => {
+                status.uploaded_bytes.fetch_sub(last, Relaxed);
+                status.failed_files.fetch_add(1, Relaxed);
+            }
+        }
+        result
+    }
+
+    fn report_progress(&self, status: &ProgressStatus) {
+        if let Some(callback) = &self.on_progress {
+            callback(&DirUploadProgress {
+                uploaded_bytes: status.uploaded_bytes.load(Relaxed),
+                total_bytes: status.total_bytes,
+                completed_files: status.completed_files.load(Relaxed),
+                failed_files: status.failed_files.load(Relaxed),
+                total_files: status.total_files,
+            });
+        }
+    }
+
+    fn relative_path(&self, entry: &DirEntry) -> String {
+        entry
+            .path()
+            .strip_prefix(&self.dir_path)
+            .unwrap_or_else(|_| entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn render_key(&self, relative_path: &str) -> String {
+        let (dir, fname) = match relative_path.rfind('/') {
+            Some(index) => relative_path.split_at(index + 1),
+            None => ("", relative_path),
+        };
+        let (basename, ext) = match fname.rfind('.') {
+            Some(index) if index > 0 => fname.split_at(index),
+            _ => (fname, ""),
+        };
+        self.key_prefix.to_string()
+            + &self
+                .key_template
+                .replace("$(path)", relative_path)
+                .replace("$(dir)", dir)
+                .replace("$(fname)", fname)
+                .replace("$(basename)", basename)
+                .replace("$(ext)", ext)
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+fn compile_patterns(patterns: &[Cow<str>]) -> Result<Vec<Pattern>, PatternError> {
+    patterns.iter().map(|pattern| Pattern::new(pattern)).collect()
+}
+
+fn is_matched(patterns: &[Pattern], relative_path: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| pattern.matches_with(relative_path, GLOB_MATCH_OPTIONS))
+}
+
+struct ProgressStatus {
+    uploaded_bytes: AtomicU64,
+    total_bytes: u64,
+    completed_files: AtomicUsize,
+    failed_files: AtomicUsize,
+    total_files: usize,
+}
+
+/// 目录中待上传的文件
+#[derive(Getters, CopyGetters, Debug, Clone)]
+pub struct DirUploadFile {
+    /// 文件路径
+    #[get = "pub"]
+    path: PathBuf,
+
+    /// 上传后的对象名称
+    #[get = "pub"]
+    key: String,
+
+    /// 文件大小，单位为字节
+    #[get_copy = "pub"]
+    size: u64,
+}
+
+/// 目录整体上传进度
+#[derive(CopyGetters, Debug, Copy, Clone)]
+#[get_copy = "pub"]
+pub struct DirUploadProgress {
+    /// 已经上传的数据量，单位为字节
+    uploaded_bytes: u64,
+    /// 所有文件的数据总量，单位为字节
+    total_bytes: u64,
+    /// 已经上传成功的文件数量
+    completed_files: usize,
+    /// 上传失败的文件数量
+    failed_files: usize,
+    /// 待上传的文件总数
+    total_files: usize,
+}
+
+/// 目录上传结果汇总
+#[derive(Getters, Debug)]
+#[get = "pub"]
+pub struct DirUploadSummary {
+    /// 上传成功的文件及其上传响应，按照文件路径排序
+    succeeded: Vec<(DirUploadFile, UploadResponse)>,
+    /// 上传失败的文件及其上传错误，按照文件路径排序
+    failed: Vec<(DirUploadFile, UploadError)>,
+}
+
+impl DirUploadSummary {
+    /// 是否所有文件都上传成功
+    pub fn is_all_succeeded(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// 目录上传错误
+#[derive(Error, Debug)]
+pub enum DirUploadError {
+    /// 遍历目录时发生 IO 错误
+    #[error("Failed to walk through the directory: {0}")]
+    IOError(#[from] IOError),
+    /// 非法的 Glob 模式
+    #[error("Invalid glob pattern: {0}")]
+    PatternError(#[from] PatternError),
+    /// 调用七牛 API 列举对象时发生错误
+    #[error("Qiniu API call error: {0}")]
+    QiniuError(#[from] crate::http::Error),
+    /// 同步目录时，存储空间与存储空间上传器对应的存储空间不一致
+    #[error("Bucket mismatch: uploader = {uploader}, bucket = {bucket}")]
+    BucketMismatch {
+        /// 存储空间上传器对应的存储空间名称
+        uploader: String,
+        /// 同步的目标存储空间名称
+        bucket: String,
+    },
+}
+
+/// 目录上传结果
+pub type DirUploadResult = Result<DirUploadSummary, DirUploadError>;
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{BucketUploaderBuilder, UploadPolicyBuilder, UploadToken},
+        *,
+    };
+    use crate::{config::ConfigBuilder, credential::Credential, http::DomainsManagerBuilder};
+    use matches::matches;
+    use qiniu_test_utils::http_call_mock::{json_response, CallHandlers, CounterCallMock};
+    use serde_json::json;
+    use std::{boxed::Box, error::Error, fs, result::Result};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_storage_uploader_dir_uploader_render_key() -> Result<(), Box<dyn Error>> {
+        let bucket_uploader = new_bucket_uploader(CallHandlers::new(|_| unreachable!()));
+        let builder = bucket_uploader.upload_dir("/tmp", upload_token());
+        assert_eq!(builder.render_key("images/logo.png"), "images/logo.png");
+        let builder = builder.key_prefix("static/").key_template("$(dir)$(basename)-v1$(ext)");
+        assert_eq!(builder.render_key("images/logo.png"), "static/images/logo-v1.png");
+        assert_eq!(builder.render_key("README"), "static/README-v1");
+        assert_eq!(builder.render_key(".gitignore"), "static/.gitignore-v1");
+        let builder = builder.key_template("$(fname)");
+        assert_eq!(builder.render_key("a/b/c.txt"), "static/c.txt");
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_dir_uploader_upload_dir() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        fs::create_dir_all(dir.path().join("css"))?;
+        fs::create_dir_all(dir.path().join("node_modules"))?;
+        fs::write(dir.path().join("index.html"), vec![b'a'; 1 << 10])?;
+        fs::write(dir.path().join("failed.html"), vec![b'b'; 1 << 10])?;
+        fs::write(dir.path().join("css").join("main.css"), vec![b'c'; 1 << 11])?;
+        fs::write(dir.path().join("css").join("main.css.tmp"), vec![b'd'; 1 << 10])?;
+        fs::write(dir.path().join("node_modules").join("lib.html"), vec![b'e'; 1 << 10])?;
+        fs::write(dir.path().join("README"), vec![b'f'; 1 << 10])?;
+
+        let mock = CounterCallMock::new(CallHandlers::new(|request| {
+            let body = request.body().as_ref().unwrap();
+            let body = String::from_utf8_lossy(body.as_ref());
+            if body.contains("static/failed.html") {
+                Ok(json_response(403, json!({"error": "key doesn't match with scope"})))
+            } else {
+                Ok(json_response(
+                    200,
+                    json!({"key": "test-key", "hash": "FhOPGUW8AaIdsdUGjnj-8jDTYqtk"}),
+                ))
+            }
+        }));
+        let bucket_uploader = new_bucket_uploader(mock.clone());
+        let max_total_bytes = Mutex::new(0);
+        let completed_keys = Mutex::new(Vec::new());
+        let summary = bucket_uploader
+            .upload_dir(dir.path(), upload_token())
+            .include("**/*.html")
+            .include("**/*.css")
+            .exclude("node_modules")
+            .key_prefix("static/")
+            .max_concurrency(2)
+            .on_file_completed(|file, _| completed_keys.lock().unwrap().push(file.key().to_owned()))
+            .on_progress(|progress| {
+                assert_eq!(progress.total_files(), 3);
+                assert!(progress.uploaded_bytes() <= progress.total_bytes());
+                let mut max_total_bytes = max_total_bytes.lock().unwrap();
+                *max_total_bytes = (*max_total_bytes).max(progress.uploaded_bytes());
+            })
+            .start()?;
+        assert_eq!(mock.call_called(), 3);
+        assert!(!summary.is_all_succeeded());
+        assert_eq!(
+            summary
+                .succeeded()
+                .iter()
+                .map(|(file, _)| file.key().as_str())
+                .collect::<Vec<_>>(),
+            vec!["static/css/main.css", "static/index.html"]
+        );
+        assert_eq!(summary.failed().len(), 1);
+        assert_eq!(summary.failed()[0].0.key(), "static/failed.html");
+        assert_eq!(summary.failed()[0].0.size(), 1 << 10);
+        assert_eq!(completed_keys.into_inner().unwrap().len(), 3);
+        assert_eq!(max_total_bytes.into_inner().unwrap(), 3 << 10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_dir_uploader_invalid_pattern() -> Result<(), Box<dyn Error>> {
+        let dir = tempdir()?;
+        let bucket_uploader = new_bucket_uploader(CallHandlers::new(|_| unreachable!()));
+        let err = bucket_uploader
+            .upload_dir(dir.path(), upload_token())
+            .include("[")
+            .start()
+            .unwrap_err();
+        assert!(matches!(err, DirUploadError::PatternError(_)));
+        Ok(())
+    }
+
+    fn new_bucket_uploader(handler: impl qiniu_http::HTTPCaller + 'static) -> BucketUploader {
+        let config = ConfigBuilder::default()
+            .http_request_handler(handler)
+            .upload_logger(None)
+            .upload_threshold(1 << 22)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        BucketUploaderBuilder::new(
+            "test-bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .thread_pool_size(4)
+        .build()
+    }
+
+    fn upload_token() -> UploadToken<'static> {
+        let policy = UploadPolicyBuilder::new_policy_for_objects_with_prefix(
+            "test-bucket",
+            "static/",
+            &ConfigBuilder::default().build(),
+        )
+        .build();
+        UploadToken::new(policy, Credential::new("abcdefghklmnopq", "1234567890"))
+    }
+}
@@ -1,4 +1,7 @@
 use super::{
+    rate_limiter::{RateLimiter, RequestBodyThrottle},
+    upload_event::{UploadEvent, UploadEventEmitter, UploadMethod},
+    upload_handle::UploadControl,
     upload_response_callback, BucketUploader, TokenizedUploadLogger, UpType, UploadLoggerRecordBuilder, UploadResponse,
 };
 use crate::{
@@ -12,12 +15,16 @@ use std::{
     borrow::Cow,
     convert::TryInto,
     io::{Read, Result as IOResult, Seek, SeekFrom},
+    sync::Arc,
 };
 
 pub(super) struct FormUploaderBuilder<'u> {
     bucket_uploader: &'u BucketUploader,
     multipart: Multipart<'u, 'u>,
     on_uploading_progress: Option<&'u dyn Fn(u64, Option<u64>)>,
+    on_event: Option<&'u (dyn Fn(&UploadEvent) + Send + Sync)>,
+    rate_limiter: Option<&'u RateLimiter>,
+    control: Option<Arc<UploadControl>>,
     upload_logger: Option<TokenizedUploadLogger>,
 }
 
@@ -26,6 +33,9 @@ pub(super) struct FormUploader<'u> {
     content_type: String,
     body: Vec<u8>,
     on_uploading_progress: Option<&'u dyn Fn(u64, Option<u64>)>,
+    event_emitter: Option<UploadEventEmitter<'u>>,
+    rate_limiter: Option<&'u RateLimiter>,
+    control: Option<Arc<UploadControl>>,
     upload_logger: Option<TokenizedUploadLogger>,
 }
 
@@ -35,6 +45,9 @@ impl<'u> FormUploaderBuilder<'u> {
             bucket_uploader,
             multipart: Multipart::new(),
             on_uploading_progress: None,
+            on_event: None,
+            rate_limiter: None,
+            control: None,
             upload_logger: bucket_uploader.upload_logger().map(|upload_logger| {
                 upload_logger.tokenize(upload_token.into(), bucket_uploader.http_client().to_owned())
             }),
@@ -63,6 +76,21 @@ impl<'u> FormUploaderBuilder<'u> {
         self
     }
 
+    pub(super) fn on_event(mut self, callback: &'u (dyn Fn(&UploadEvent) + Send + Sync)) -> FormUploaderBuilder<'u> {
+        self.on_event = Some(callback);
+        self
+    }
+
+    pub(super) fn rate_limiter(mut self, rate_limiter: &'u RateLimiter) -> FormUploaderBuilder<'u> {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub(super) fn control(mut self, control: Arc<UploadControl>) -> FormUploaderBuilder<'u> {
+        self.control = Some(control);
+        self
+    }
+
     pub(super) fn seekable_stream<'n: 'u, R: Read + Seek + 'u>(
         mut self,
         mut stream: R,
@@ -112,6 +140,9 @@ impl<'u> FormUploaderBuilder<'u> {
             content_type: "multipart/form-data; boundary=".to_owned() + fields.boundary(),
             body,
             on_uploading_progress: self.on_uploading_progress,
+            event_emitter: self.on_event.map(UploadEventEmitter::new),
+            rate_limiter: self.rate_limiter,
+            control: self.control,
             upload_logger: self.upload_logger,
         })
     }
@@ -119,6 +150,27 @@ impl<'u> FormUploaderBuilder<'u> {
 
 impl<'u> FormUploader<'u> {
     pub(super) fn send(&self) -> HTTPResult<UploadResponse> {
+        // 表单上传一旦开始发送就无法中断，此后将不能再被暂停或取消
+        if let Some(control) = &self.control {
+            control.enter_uninterruptible()?;
+        }
+        if let Some(event_emitter) = &self.event_emitter {
+            event_emitter.emit(UploadEvent::Started {
+                method: UploadMethod::Form,
+                upload_id: None,
+            });
+        }
+        let result = self.send_to_all_zones();
+        if let Some(event_emitter) = &self.event_emitter {
+            match &result {
+                Ok(_) => event_emitter.completed(self.body.len() as u64),
+                Err(err) => event_emitter.emit(UploadEvent::Failed { error: err }),
+            }
+        }
+        result
+    }
+
+    fn send_to_all_zones(&self) -> HTTPResult<UploadResponse> {
         let mut prev_err: Option<HTTPError> = None;
         for up_urls in self.bucket_uploader.up_urls_list().iter() {
             match self.send_form_request(&up_urls.iter().map(|url| url.as_ref()).collect::<Box<[&str]>>()) {
@@ -140,15 +192,36 @@ impl<'u> FormUploader<'u> {
     }
 
     fn send_form_request(&self, up_urls: &[&str]) -> HTTPResult<UploadResponse> {
+        let throttle = self
+            .rate_limiter
+            .map(|rate_limiter| RequestBodyThrottle::new(rate_limiter, self.body.len() as u64));
         let upload_result = self
             .bucket_uploader
             .http_client()
             .post("/", up_urls)
             .idempotent()
+            .on_attempt(&|_, _| {
+                if let Some(throttle) = &throttle {
+                    throttle.on_attempt();
+                }
+            })
+            .on_reading_body(&|size| {
+                if let Some(throttle) = &throttle {
+                    throttle.on_reading_body(size);
+                }
+            })
             .on_uploading_progress(&|uploaded, total| {
                 if let Some(on_uploading_progress) = &self.on_uploading_progress {
                     (on_uploading_progress)(uploaded, Some(total));
                 }
+                if let Some(event_emitter) = &self.event_emitter {
+                    event_emitter.progress(uploaded, Some(total));
+                }
+            })
+            .on_host_frozen(&|host, err| {
+                if let Some(event_emitter) = &self.event_emitter {
+                    event_emitter.emit(UploadEvent::HostFrozen { host, error: err });
+                }
             })
             .on_response(&|response, duration| {
                 let result = upload_response_callback(response);
@@ -184,9 +257,12 @@ impl<'u> FormUploader<'u> {
             })
             .accept_json()
             .raw_body(self.content_type.to_owned(), self.body.as_slice())
-            .send()?
-            .try_parse_json::<Value>();
-        match upload_result {
+            .send()
+            .map(|mut response| response.try_parse_json::<Value>());
+        if let Some(throttle) = &throttle {
+            throttle.settle();
+        }
+        match upload_result? {
             Ok(value) => Ok(value.into()),
             Err(bytes) => Ok(bytes.into()),
         }
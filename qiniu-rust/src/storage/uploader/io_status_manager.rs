@@ -1,8 +1,9 @@
+use super::upload_handle::UploadControl;
 use crate::http::Error as HTTPError;
 use assert_impl::assert_impl;
+use matches::matches;
 use std::{
-    collections::HashSet,
-    convert::TryInto,
+    collections::BTreeMap,
     io::{Error as IOError, ErrorKind as IOErrorKind, Read, Seek, SeekFrom},
     sync::Mutex,
 };
@@ -16,9 +17,10 @@ pub(super) enum Result {
 enum Status<R: Read + Seek + Send> {
     Uploading {
         reader: R,
-        block_size: u32,
+        offset: u64,
+        max_part_size: u32,
         current_part_number: usize,
-        uploaded_part_numbers: HashSet<usize>,
+        uploaded_parts: BTreeMap<usize, UploadedPart>,
     },
     IOError(IOError),
     HTTPError(HTTPError),
@@ -32,52 +34,84 @@ pub(super) struct IOStatusManager<R: Read + Seek + Send> {
 pub(super) struct PartData {
     pub(super) data: Vec<u8>,
     pub(super) part_number: usize,
+    pub(super) offset: u64,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(super) struct UploadedPart {
+    pub(super) offset: u64,
+    pub(super) size: u64,
 }
 
 impl<R: Read + Seek + Send> IOStatusManager<R> {
-    pub(super) fn new(io: R, block_size: u32, uploaded_part_numbers: &[usize]) -> IOStatusManager<R> {
+    // `max_part_size` 为所有分片可能的最大尺寸，用于确保已上传分片之间的数据能够被剩余的分片编号容纳
+    pub(super) fn new(io: R, max_part_size: u32, uploaded_parts: BTreeMap<usize, UploadedPart>) -> IOStatusManager<R> {
         IOStatusManager {
             inner: Mutex::new(Status::Uploading {
                 reader: io,
+                offset: 0,
+                max_part_size,
                 current_part_number: 0,
-                block_size,
-                uploaded_part_numbers: {
-                    let mut set = HashSet::new();
-                    for &part_number in uploaded_part_numbers {
-                        set.insert(part_number);
-                    }
-                    set
-                },
+                uploaded_parts,
             }),
         }
     }
 
-    pub(super) fn read(&self) -> Option<PartData> {
+    // 如果上传被暂停，将不再读取新的分片，而是直接返回 `None`，此时上传状态保持不变，可以通过 `is_uploading` 判断
+    pub(super) fn read(&self, part_size: u32, control: Option<&UploadControl>) -> Option<PartData> {
         let mut lock = self.inner.lock().unwrap();
         match &mut *lock {
             Status::Uploading {
                 reader,
-                block_size,
+                offset,
+                max_part_size,
                 current_part_number,
-                uploaded_part_numbers,
+                uploaded_parts,
             } => {
-                let mut have_read = 0;
-                let mut buf = vec![0; block_size.to_owned().try_into().unwrap_or(usize::max_value())];
-                let new_part_number = {
-                    let mut new_part_number = *current_part_number + 1;
-                    let mut skip_bytes = 0i64;
-                    while uploaded_part_numbers.get(&new_part_number).is_some() {
-                        new_part_number += 1;
-                        skip_bytes += *block_size as i64;
+                match control.map(|control| control.is_runnable()) {
+                    Some(Ok(false)) => return None,
+                    Some(Err(err)) => {
+                        *lock = Status::HTTPError(err);
+                        return None;
                     }
-                    if skip_bytes > 0 {
-                        if let Err(err) = reader.seek(SeekFrom::Current(skip_bytes)) {
+                    _ => {}
+                }
+                let (new_part_number, part_size) = {
+                    let mut new_part_number = *current_part_number + 1;
+                    let mut skipped = false;
+                    let part_size = loop {
+                        while let Some(uploaded_part) = uploaded_parts.get(&new_part_number) {
+                            *offset = uploaded_part.offset + uploaded_part.size;
+                            new_part_number += 1;
+                            skipped = true;
+                        }
+                        // 当前分片不能覆盖后续已经上传的分片，且剩余的分片编号必须足以容纳两者之间的数据
+                        match uploaded_parts.range(new_part_number..).next() {
+                            Some((&next_part_number, next_part)) => {
+                                let gap_size = next_part.offset.saturating_sub(*offset);
+                                let rest_part_numbers = (next_part_number - new_part_number - 1) as u64;
+                                let size = u64::from(part_size)
+                                    .min(gap_size)
+                                    .max(gap_size.saturating_sub(rest_part_numbers * u64::from(*max_part_size)));
+                                if size > 0 {
+                                    break size;
+                                }
+                                new_part_number += 1;
+                            }
+                            None => break u64::from(part_size),
+                        }
+                    };
+                    if skipped {
+                        if let Err(err) = reader.seek(SeekFrom::Start(*offset)) {
                             *lock = Status::IOError(err);
                             return None;
                         }
                     }
-                    new_part_number
+                    (new_part_number, part_size)
                 };
+                let part_offset = *offset;
+                let mut have_read = 0;
+                let mut buf = vec![0; part_size as usize];
                 loop {
                     match reader.read(&mut buf[have_read..]) {
                         Ok(0) => {
@@ -87,6 +121,7 @@ impl<R: Read + Seek + Send> IOStatusManager<R> {
                                 return Some(PartData {
                                     data: buf,
                                     part_number: new_part_number,
+                                    offset: part_offset,
                                 });
                             } else {
                                 return None;
@@ -96,9 +131,11 @@ impl<R: Read + Seek + Send> IOStatusManager<R> {
                             have_read += n;
                             if have_read == buf.len() {
                                 *current_part_number = new_part_number;
+                                *offset += part_size;
                                 return Some(PartData {
                                     data: buf,
                                     part_number: new_part_number,
+                                    offset: part_offset,
                                 });
                             }
                         }
@@ -116,6 +153,10 @@ impl<R: Read + Seek + Send> IOStatusManager<R> {
         }
     }
 
+    pub(super) fn is_uploading(&self) -> bool {
+        matches!(*self.inner.lock().unwrap(), Status::Uploading { .. })
+    }
+
     pub(super) fn error(&self, err: HTTPError) {
         *self.inner.lock().unwrap() = Status::HTTPError(err);
     }
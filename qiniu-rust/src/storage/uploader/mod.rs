@@ -2,11 +2,17 @@
 //!
 //! 提供对象上传相关功能
 
+mod adaptive_controller;
 mod bucket_uploader;
 mod callback;
+mod dir_syncer;
+mod dir_uploader;
 mod form_uploader;
 mod io_status_manager;
+mod rate_limiter;
 mod resumable_uploader;
+mod upload_event;
+mod upload_handle;
 mod upload_logger;
 mod upload_manager;
 mod upload_policy;
@@ -16,6 +22,13 @@ mod upload_token;
 
 pub use bucket_uploader::{BucketUploader, BucketUploaderBuilder, FileUploaderBuilder, UploadError, UploadResult};
 use callback::upload_response_callback;
+pub use dir_syncer::{DirSyncBuilder, DirSyncPlan, DirSyncResult, DirSyncSummary};
+pub use dir_uploader::{
+    DirUploadError, DirUploadFile, DirUploadProgress, DirUploadResult, DirUploadSummary, DirUploaderBuilder,
+};
+pub use rate_limiter::RateLimiter;
+pub use upload_event::{UploadEvent, UploadMethod};
+pub use upload_handle::UploadHandle;
 pub use upload_logger::{LockPolicy as UploadLoggerFileLockPolicy, UploadLogger, UploadLoggerBuilder};
 use upload_logger::{TokenizedUploadLogger, UpType, UploadLoggerRecordBuilder};
 pub use upload_manager::{CreateUploaderError, CreateUploaderResult, UploadManager};
@@ -0,0 +1,240 @@
+use assert_impl::assert_impl;
+use std::{
+    cell::Cell,
+    cmp::min,
+    fmt,
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+// 发送数据前每次消耗令牌的最大数量，确保发送前的等待时间与数据尺寸相符
+const MAX_ACQUIRE_SIZE: u64 = 1 << 16;
+// 等待令牌时单次休眠的最长时间，确保运行时调整的速率能够尽快生效
+const MAX_SLEEP_DURATION: Duration = Duration::from_millis(100);
+
+struct TokenBucket {
+    bytes_per_second: u64,
+    tokens: f64,
+    last_refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let capacity = self.bytes_per_second as f64;
+        self.tokens = (self.tokens + now.duration_since(self.last_refilled_at).as_secs_f64() * capacity).min(capacity);
+        self.last_refilled_at = now;
+    }
+}
+
+/// 上传限速器
+///
+/// 基于令牌桶算法限制上传速度，允许的突发流量不超过一秒的限速值。
+/// 克隆后的限速器与原限速器共享同一个令牌桶，因此可以将同一个限速器设置给多个存储空间上传器或文件上传器，
+/// 以限制它们的总上传速度，分片上传时所有并发上传的分片也将共享同一个令牌桶。
+///
+/// 表单或分片的请求体将以流的形式交给 `HTTPCaller` 发送，`HTTPCaller` 每次从请求体读取器中读取数据前都将消耗相应数量的令牌，
+/// 令牌不足时将等待，因此即使分片较大，发送过程中的瞬时速度也不会超过限速值。每次重试都会重新读取请求体，因此同样需要消耗令牌。
+/// 对于没有通过请求体读取器发送数据的 `HTTPCaller` 实现，将在每次发送结束后为整个请求体补充消耗令牌，
+/// 此时瞬时流量可能会达到一个分片或一个表单的大小，但长期平均上传速度依然不会超过限速值
+///
+/// ```rust,no_run
+/// use qiniu_ng::{storage::uploader::RateLimiter, Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let rate_limiter = RateLimiter::new(10 << 20);
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let response = client
+///     .upload()
+///     .for_upload_token("[Upload Token]".to_owned())?
+///     .rate_limiter(rate_limiter.to_owned())
+///     .upload_file("/path/to/file", "", None)?;
+/// // 在其他线程中随时调整限速值
+/// rate_limiter.set_bytes_per_second(1 << 20);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// 创建上传限速器
+    ///
+    /// `bytes_per_second` 为每秒允许上传的字节数，必须大于 0
+    pub fn new(bytes_per_second: u64) -> RateLimiter {
+        assert!(bytes_per_second > 0);
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                bytes_per_second,
+                tokens: bytes_per_second as f64,
+                last_refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    /// 获取当前每秒允许上传的字节数
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bucket.lock().unwrap().bytes_per_second
+    }
+
+    /// 调整每秒允许上传的字节数
+    ///
+    /// 调整将立即对所有共享该限速器的上传生效，`bytes_per_second` 必须大于 0
+    pub fn set_bytes_per_second(&self, bytes_per_second: u64) {
+        assert!(bytes_per_second > 0);
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill();
+        bucket.bytes_per_second = bytes_per_second;
+        bucket.tokens = bucket.tokens.min(bytes_per_second as f64);
+    }
+
+    // 消耗指定数量的令牌，如果令牌不足，将阻塞直到令牌桶中的令牌不再为负数
+    fn acquire(&self, size: u64) {
+        loop {
+            let wait_duration = {
+                let mut bucket = self.bucket.lock().unwrap();
+                bucket.refill();
+                if bucket.tokens >= 0f64 {
+                    bucket.tokens -= size as f64;
+                    return;
+                }
+                Duration::from_secs_f64(-bucket.tokens / bucket.bytes_per_second as f64)
+            };
+            sleep(min(wait_duration, MAX_SLEEP_DURATION));
+        }
+    }
+
+    // 为即将发送的数据消耗令牌，数据将被拆分为多个小块依次消耗令牌，
+    // 因此当数据尺寸超过令牌桶中剩余的令牌时，将阻塞直到令牌足以覆盖这些数据
+    fn acquire_before_sending(&self, size: u64) {
+        let mut rest = size;
+        while rest > 0 {
+            let size = min(rest, MAX_ACQUIRE_SIZE);
+            self.acquire(size);
+            rest -= size;
+        }
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+// 单个请求的请求体限速器
+//
+// 请求体被读取前为读取的数据消耗令牌，如果某次发送时请求体没有被读取过，
+// 则在下次发送前或请求结束后为整个请求体补充消耗令牌，确保每次发送都能被限速器计入
+pub(super) struct RequestBodyThrottle<'r> {
+    rate_limiter: &'r RateLimiter,
+    body_size: u64,
+    // 当前这次发送是否读取过请求体，尚未发送或已经结算时为 None
+    body_read: Cell<Option<bool>>,
+}
+
+impl<'r> RequestBodyThrottle<'r> {
+    pub(super) fn new(rate_limiter: &'r RateLimiter, body_size: u64) -> RequestBodyThrottle<'r> {
+        RequestBodyThrottle {
+            rate_limiter,
+            body_size,
+            body_read: Cell::new(None),
+        }
+    }
+
+    // 每次发送请求前调用
+    pub(super) fn on_attempt(&self) {
+        self.settle();
+        self.body_read.set(Some(false));
+    }
+
+    // 每次读取请求体前调用
+    pub(super) fn on_reading_body(&self, size: usize) {
+        self.body_read.set(Some(true));
+        self.rate_limiter.acquire_before_sending(size as u64);
+    }
+
+    // 请求结束后调用
+    pub(super) fn settle(&self) {
+        if self.body_read.replace(None) == Some(false) {
+            self.rate_limiter.acquire_before_sending(self.body_size);
+        }
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("bytes_per_second", &self.bytes_per_second())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{error::Error, result::Result};
+
+    #[test]
+    fn test_storage_uploader_rate_limiter_limits_sending() -> Result<(), Box<dyn Error>> {
+        let rate_limiter = RateLimiter::new(1 << 20);
+        let timer = Instant::now();
+        // 令牌桶初始时是满的，因此发送 1.5 MB 数据前应该等待 0.5 秒左右
+        rate_limiter.acquire_before_sending(3 << 19);
+        let elapsed = timer.elapsed();
+        assert!(elapsed >= Duration::from_millis(400), "elapsed: {:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(2), "elapsed: {:?}", elapsed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_rate_limiter_request_body_throttle() -> Result<(), Box<dyn Error>> {
+        let rate_limiter = RateLimiter::new(1 << 20);
+        let throttle = RequestBodyThrottle::new(&rate_limiter, 1 << 20);
+
+        // 请求体已经通过读取器消耗过令牌，结算时无需再次消耗
+        let timer = Instant::now();
+        throttle.on_attempt();
+        throttle.on_reading_body(1 << 20);
+        throttle.settle();
+        assert!(
+            timer.elapsed() < Duration::from_millis(400),
+            "elapsed: {:?}",
+            timer.elapsed()
+        );
+
+        // 请求体没有被读取过，下次发送前需要为整个请求体补充消耗令牌
+        throttle.on_attempt();
+        throttle.on_attempt();
+        assert!(
+            timer.elapsed() >= Duration::from_millis(800),
+            "elapsed: {:?}",
+            timer.elapsed()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_rate_limiter_adjusts_at_runtime() -> Result<(), Box<dyn Error>> {
+        let rate_limiter = RateLimiter::new(1 << 10);
+        rate_limiter.acquire(1 << 20);
+        assert_eq!(rate_limiter.bytes_per_second(), 1 << 10);
+
+        // 按照原有速率需要等待近 1024 秒，提高速率后应该很快就能继续读取
+        let timer = Instant::now();
+        let cloned = rate_limiter.to_owned();
+        cloned.set_bytes_per_second(10 << 20);
+        assert_eq!(rate_limiter.bytes_per_second(), 10 << 20);
+        rate_limiter.acquire(1 << 10);
+        assert!(
+            timer.elapsed() < Duration::from_secs(1),
+            "elapsed: {:?}",
+            timer.elapsed()
+        );
+        Ok(())
+    }
+}
@@ -1,28 +1,32 @@
 use super::{
-    io_status_manager::{IOStatusManager, Result as IOStatusResult},
+    adaptive_controller::AdaptiveController,
+    io_status_manager::{IOStatusManager, Result as IOStatusResult, UploadedPart},
+    rate_limiter::{RateLimiter, RequestBodyThrottle},
+    upload_event::{UploadEvent, UploadEventEmitter, UploadMethod},
+    upload_handle::UploadControl,
     upload_recorder::{FileUploadRecordMedium, FileUploadRecordMediumBlockItem, FileUploadRecordMediumMetadata},
     upload_response_callback, BucketUploader, TokenizedUploadLogger, UpType, UploadLoggerRecordBuilder, UploadResponse,
 };
 use crate::{
     http::{Client, Error as HTTPError, ErrorKind as HTTPErrorKind, Result as HTTPResult, RetryKind},
-    utils::{base64, ron::Ron, seek_adapter},
+    utils::{base64, crc32, ron::Ron, seek_adapter},
 };
 use mime::Mime;
-use rayon::{ThreadPool, ThreadPoolBuilder};
+use rayon::{Scope, ThreadPool, ThreadPoolBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
     borrow::Cow,
     boxed::Box,
-    cell::Cell,
-    collections::HashMap,
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
     convert::TryInto,
     fs::File,
     io::{Read, Result as IOResult, Seek, SeekFrom},
     path::Path,
     sync::{
         atomic::{AtomicU64, Ordering::Relaxed},
-        Mutex,
+        Arc, Mutex,
     },
     time::{Duration, Instant},
 };
@@ -45,6 +49,10 @@ struct UploadPartResult {
 struct Part {
     etag: Box<str>,
     part_number: usize,
+    #[serde(skip)]
+    offset: u64,
+    #[serde(skip)]
+    size: u64,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -64,11 +72,47 @@ struct FromResuming {
 }
 
 struct UploadingProgressCallback<'u> {
-    callback: &'u (dyn Fn(u64, Option<u64>) + Send + Sync),
+    callback: Option<&'u (dyn Fn(u64, Option<u64>) + Send + Sync)>,
+    event_emitter: Option<UploadEventEmitter<'u>>,
     completed_size: AtomicU64,
     total_size: Option<u64>,
 }
 
+impl<'u> UploadingProgressCallback<'u> {
+    fn new(
+        callback: Option<&'u (dyn Fn(u64, Option<u64>) + Send + Sync)>,
+        on_event: Option<&'u (dyn Fn(&UploadEvent) + Send + Sync)>,
+        total_size: Option<u64>,
+    ) -> Option<UploadingProgressCallback<'u>> {
+        if callback.is_none() && on_event.is_none() {
+            return None;
+        }
+        Some(UploadingProgressCallback {
+            callback,
+            event_emitter: on_event.map(UploadEventEmitter::new),
+            completed_size: AtomicU64::new(0),
+            total_size,
+        })
+    }
+
+    fn add_completed_size(&self, added_size: u64) {
+        let completed_size = self.completed_size.fetch_add(added_size, Relaxed) + added_size;
+        if let Some(callback) = self.callback {
+            (callback)(completed_size, self.total_size);
+        }
+        if let Some(event_emitter) = &self.event_emitter {
+            event_emitter.progress(completed_size, self.total_size);
+        }
+    }
+
+    fn reset_completed_size(&self, completed_size: u64) {
+        self.completed_size.store(completed_size, Relaxed);
+        if let Some(event_emitter) = &self.event_emitter {
+            event_emitter.reset_speed(completed_size);
+        }
+    }
+}
+
 pub(super) struct ResumableUploaderBuilder<'u> {
     bucket_uploader: &'u BucketUploader,
     upload_token: Cow<'u, str>,
@@ -76,9 +120,13 @@ pub(super) struct ResumableUploaderBuilder<'u> {
     metadata: Option<HashMap<Cow<'u, str>, Cow<'u, str>>>,
     custom_vars: Option<HashMap<Cow<'u, str>, Cow<'u, str>>>,
     on_uploading_progress: Option<&'u (dyn Fn(u64, Option<u64>) + Send + Sync)>,
+    on_event: Option<&'u (dyn Fn(&UploadEvent) + Send + Sync)>,
+    rate_limiter: Option<&'u RateLimiter>,
     thread_pool: Option<Ron<'u, ThreadPool>>,
     max_concurrency: usize,
+    adaptive: bool,
     upload_logger: Option<TokenizedUploadLogger>,
+    control: Option<Arc<UploadControl>>,
 }
 
 pub(super) struct ResumableUploader<'u, R: Read + Seek + Send + 'u> {
@@ -97,7 +145,10 @@ pub(super) struct ResumableUploader<'u, R: Read + Seek + Send + 'u> {
     uploading_progress_callback: Option<UploadingProgressCallback<'u>>,
     thread_pool: Ron<'u, ThreadPool>,
     max_concurrency: usize,
+    adaptive: bool,
     upload_logger: Option<TokenizedUploadLogger>,
+    rate_limiter: Option<&'u RateLimiter>,
+    control: Option<Arc<UploadControl>>,
 }
 
 impl<'u> ResumableUploaderBuilder<'u> {
@@ -109,6 +160,8 @@ impl<'u> ResumableUploaderBuilder<'u> {
             metadata: None,
             custom_vars: None,
             on_uploading_progress: None,
+            on_event: None,
+            rate_limiter: None,
             thread_pool: None,
             upload_logger: bucket_uploader.upload_logger().map(|upload_logger| {
                 upload_logger.tokenize(
@@ -117,6 +170,8 @@ impl<'u> ResumableUploaderBuilder<'u> {
                 )
             }),
             max_concurrency: 0,
+            adaptive: false,
+            control: None,
         }
     }
 
@@ -130,6 +185,16 @@ impl<'u> ResumableUploaderBuilder<'u> {
         self
     }
 
+    pub(super) fn adaptive(mut self, adaptive: bool) -> ResumableUploaderBuilder<'u> {
+        self.adaptive = adaptive;
+        self
+    }
+
+    pub(super) fn control(mut self, control: Arc<UploadControl>) -> ResumableUploaderBuilder<'u> {
+        self.control = Some(control);
+        self
+    }
+
     pub(super) fn key(mut self, key: Cow<'u, str>) -> ResumableUploaderBuilder<'u> {
         self.key = Some(key);
         self
@@ -157,6 +222,19 @@ impl<'u> ResumableUploaderBuilder<'u> {
         self
     }
 
+    pub(super) fn on_event(
+        mut self,
+        callback: &'u (dyn Fn(&UploadEvent) + Send + Sync),
+    ) -> ResumableUploaderBuilder<'u> {
+        self.on_event = Some(callback);
+        self
+    }
+
+    pub(super) fn rate_limiter(mut self, rate_limiter: &'u RateLimiter) -> ResumableUploaderBuilder<'u> {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     pub(super) fn file<'n: 'u>(
         self,
         file: File,
@@ -192,11 +270,11 @@ impl<'u> ResumableUploaderBuilder<'u> {
                 custom_vars: self.custom_vars,
             }),
             from_resuming: None,
-            uploading_progress_callback: self.on_uploading_progress.map(|callback| UploadingProgressCallback {
-                callback,
-                completed_size: AtomicU64::new(0),
-                total_size: Some(file_size),
-            }),
+            uploading_progress_callback: UploadingProgressCallback::new(
+                self.on_uploading_progress,
+                self.on_event,
+                Some(file_size),
+            ),
             thread_pool: self
                 .thread_pool
                 .or_else(|| bucket_uploader.thread_pool().map(|pool| Ron::Referenced(pool)))
@@ -209,7 +287,10 @@ impl<'u> ResumableUploaderBuilder<'u> {
                     )
                 }),
             max_concurrency: self.max_concurrency,
+            adaptive: self.adaptive,
             upload_logger: self.upload_logger,
+            rate_limiter: self.rate_limiter,
+            control: self.control,
         })
     }
 
@@ -240,11 +321,11 @@ impl<'u> ResumableUploaderBuilder<'u> {
                 custom_vars: self.custom_vars,
             }),
             from_resuming: None,
-            uploading_progress_callback: self.on_uploading_progress.map(|callback| UploadingProgressCallback {
-                callback,
-                completed_size: AtomicU64::new(0),
-                total_size: None,
-            }),
+            uploading_progress_callback: UploadingProgressCallback::new(
+                self.on_uploading_progress,
+                self.on_event,
+                None,
+            ),
             thread_pool: self
                 .thread_pool
                 .or_else(|| bucket_uploader.thread_pool().map(|pool| Ron::Referenced(pool)))
@@ -257,13 +338,29 @@ impl<'u> ResumableUploaderBuilder<'u> {
                     )
                 }),
             max_concurrency: self.max_concurrency,
+            adaptive: self.adaptive,
             upload_logger: self.upload_logger,
+            rate_limiter: self.rate_limiter,
+            control: self.control,
         })
     }
 }
 
 impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
     pub(super) fn send(&mut self) -> HTTPResult<UploadResponse> {
+        let result = self.send_to_all_zones();
+        if let Some(progress) = &self.uploading_progress_callback {
+            if let Some(event_emitter) = &progress.event_emitter {
+                match &result {
+                    Ok(_) => event_emitter.completed(self.uploaded_size.load(Relaxed)),
+                    Err(err) => event_emitter.emit(UploadEvent::Failed { error: err }),
+                }
+            }
+        }
+        result
+    }
+
+    fn send_to_all_zones(&mut self) -> HTTPResult<UploadResponse> {
         let base_path = self.make_base_path();
         let authorization = self.make_authorization();
         if let Ok(Some(result)) = self.try_to_resume(&base_path, &authorization) {
@@ -311,7 +408,7 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         }
         self.uploaded_size.store(0, Relaxed);
         if let Some(uploading_progress_callback) = &self.uploading_progress_callback {
-            uploading_progress_callback.completed_size.store(0, Relaxed);
+            uploading_progress_callback.reset_completed_size(0);
         }
         self.completed_parts.lock().unwrap().parts.clear();
         let timer = Instant::now();
@@ -352,6 +449,12 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         authorization: &str,
     ) -> HTTPResult<UploadResponse> {
         let upload_id = self.init_parts(&base_path, up_urls, &authorization)?;
+        if let Some(event_emitter) = self.event_emitter() {
+            event_emitter.emit(UploadEvent::Started {
+                method: UploadMethod::Resumable,
+                upload_id: Some(&upload_id),
+            });
+        }
         let recorder = self.file_path.as_ref().and_then(|file_path| {
             self.bucket_uploader
                 .recorder()
@@ -379,100 +482,147 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         authorization: &str,
         upload_recorder: Option<FileUploadRecordMedium>,
     ) -> HTTPResult<UploadResponse> {
+        let concurrency = {
+            let mut c = self.thread_pool.current_num_threads();
+            if (1..c).contains(&self.max_concurrency) {
+                c = self.max_concurrency;
+            }
+            c
+        };
+        let block_size = self.block_size;
+        let adaptive_controller = if self.adaptive {
+            Some(AdaptiveController::new(block_size, self.io_size, concurrency))
+        } else {
+            None
+        };
         let io_status_manager = IOStatusManager::new(
             &mut self.io,
-            self.block_size,
-            &self
-                .completed_parts
+            adaptive_controller
+                .as_ref()
+                .map_or(block_size, |controller| controller.max_part_size().max(block_size)),
+            self.completed_parts
                 .lock()
                 .unwrap()
                 .parts
                 .iter()
-                .map(|part| part.part_number)
-                .collect::<Vec<_>>(),
+                .map(|part| {
+                    (
+                        part.part_number,
+                        UploadedPart {
+                            offset: part.offset,
+                            size: part.size,
+                        },
+                    )
+                })
+                .collect::<BTreeMap<_, _>>(),
         );
+        let adaptive_controller = adaptive_controller.as_ref();
         let http_client = self.bucket_uploader.http_client();
-        let block_size = self.block_size;
         let completed_parts = &self.completed_parts;
         let uploaded_size = &self.uploaded_size;
         let uploading_progress_callback = self.uploading_progress_callback.as_ref();
+        let event_emitter = uploading_progress_callback.and_then(|progress| progress.event_emitter.as_ref());
         let checksum_enabled = self.checksum_enabled;
         let upload_logger = self.upload_logger.as_ref();
-        let concurrency = {
-            let mut c = self.thread_pool.current_num_threads();
-            if (1..c).contains(&self.max_concurrency) {
-                c = self.max_concurrency;
+        let rate_limiter = self.rate_limiter;
+        let control = self.control.as_ref().map(|control| &**control);
+
+        // 读取并上传下一个分片，没有需要上传的分片或上传失败时返回 false
+        let upload_next_part = || match io_status_manager.read(
+            adaptive_controller.map_or(block_size, |controller| controller.part_size()),
+            control,
+        ) {
+            Some(part_data) => {
+                let last_block_uploaded = Cell::new(0);
+                let timer = Instant::now();
+                match Self::upload_part(
+                    http_client,
+                    &(base_path.to_owned() + "/" + &part_data.part_number.to_string()),
+                    up_urls,
+                    authorization,
+                    &part_data.data,
+                    part_data.part_number,
+                    part_data.offset,
+                    &mut OptionalMd5::new(checksum_enabled),
+                    |block_uploaded, _| {
+                        if let Some(progress) = uploading_progress_callback {
+                            progress.add_completed_size(block_uploaded - last_block_uploaded.replace(block_uploaded));
+                        }
+                    },
+                    |_, _, _| {
+                        if let Some(progress) = uploading_progress_callback {
+                            progress
+                                .completed_size
+                                .fetch_sub(last_block_uploaded.replace(0), Relaxed);
+                        }
+                        if let Some(controller) = adaptive_controller {
+                            controller.part_failed();
+                        }
+                    },
+                    upload_logger,
+                    upload_recorder.as_ref(),
+                    event_emitter,
+                    rate_limiter,
+                ) {
+                    Ok(etag) => {
+                        let part_size = part_data.data.len() as u64;
+                        if let Some(controller) = adaptive_controller {
+                            controller.part_completed(part_size, timer.elapsed());
+                        }
+                        completed_parts.lock().unwrap().parts.push(Part {
+                            etag,
+                            part_number: part_data.part_number,
+                            offset: part_data.offset,
+                            size: part_size,
+                        });
+                        uploaded_size.fetch_add(part_size, Relaxed);
+                        true
+                    }
+                    Err(err) => {
+                        io_status_manager.error(err);
+                        false
+                    }
+                }
             }
-            c
+            None => false,
         };
 
-        self.thread_pool.scope(|s| {
-            for _ in 0..concurrency {
-                s.spawn(|_| {
-                    let mut md5 = OptionalMd5::new(checksum_enabled);
-                    loop {
-                        match io_status_manager.read() {
-                            Some(part_data) => {
-                                let last_block_uploaded = Cell::new(0);
-                                match Self::upload_part(
-                                    http_client,
-                                    &(base_path.to_owned() + "/" + &part_data.part_number.to_string()),
-                                    up_urls,
-                                    authorization,
-                                    &part_data.data,
-                                    part_data.part_number,
-                                    &mut md5,
-                                    |block_uploaded, _| {
-                                        if let Some(progress) = uploading_progress_callback {
-                                            let added_size =
-                                                block_uploaded - last_block_uploaded.replace(block_uploaded);
-                                            (progress.callback)(
-                                                progress.completed_size.fetch_add(added_size, Relaxed) + added_size,
-                                                progress.total_size,
-                                            );
-                                        }
-                                    },
-                                    |_, _, _| {
-                                        if let Some(progress) = uploading_progress_callback {
-                                            progress
-                                                .completed_size
-                                                .fetch_sub(last_block_uploaded.replace(0), Relaxed);
-                                        }
-                                    },
-                                    upload_logger,
-                                    upload_recorder.as_ref(),
-                                ) {
-                                    Ok(etag) => {
-                                        completed_parts.lock().unwrap().parts.push(Part {
-                                            etag,
-                                            part_number: part_data.part_number,
-                                        });
-                                        uploaded_size.fetch_add(block_size.into(), Relaxed);
-                                    }
-                                    Err(err) => {
-                                        io_status_manager.error(err);
-                                        return;
-                                    }
-                                };
-                            }
-                            None => {
-                                return;
-                            }
-                        }
+        loop {
+            self.thread_pool.scope(|s| match adaptive_controller {
+                Some(controller) => spawn_adaptively(s, controller, &upload_next_part),
+                None => {
+                    for _ in 0..concurrency {
+                        s.spawn(|_| while upload_next_part() {});
                     }
-                });
+                }
+            });
+            // 上传被暂停时，线程池中的线程将不再读取新的分片并退出，从而不会长时间占用线程池。
+            // 此时在当前线程中等待上传被恢复或取消，恢复后将重新调度剩余分片的上传
+            match control {
+                Some(control) if io_status_manager.is_uploading() => {
+                    if let Err(err) = control.wait_until_runnable() {
+                        io_status_manager.error(err);
+                        break;
+                    }
+                }
+                _ => break,
             }
-        });
+        }
 
         match io_status_manager.result() {
-            IOStatusResult::Success => self.complete_parts(base_path, up_urls, authorization).tap_ok(|_| {
-                self.file_path.as_ref().tap_some(|file_path| {
-                    let _ = self
-                        .bucket_uploader
-                        .recorder()
-                        .drop(file_path, self.key.as_ref().map(|key| key.as_ref()));
-                })
-            }),
+            IOStatusResult::Success => self
+                .control
+                .as_ref()
+                .map_or(Ok(()), |control| control.enter_uninterruptible())
+                .and_then(|_| self.complete_parts(base_path, up_urls, authorization))
+                .tap_ok(|_| {
+                    self.file_path.as_ref().tap_some(|file_path| {
+                        let _ = self
+                            .bucket_uploader
+                            .recorder()
+                            .drop(file_path, self.key.as_ref().map(|key| key.as_ref()));
+                    })
+                }),
             IOStatusResult::IOError(err) => Err(HTTPError::new_unretryable_error_from_parts(
                 HTTPErrorKind::IOError(err),
                 None,
@@ -496,8 +646,10 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
                 completed_parts.parts.push(Part {
                     etag: block_record.etag,
                     part_number: block_record.part_number,
+                    offset: block_record.offset,
+                    size: block_record.size,
                 });
-                io_offset += u64::from(file_record.block_size);
+                io_offset += block_record.size;
             }
         }
         self.from_resuming = Some(FromResuming {
@@ -516,6 +668,11 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
             .post(base_path, up_urls)
             .header("Authorization", authorization)
             .idempotent()
+            .on_host_frozen(&|host, err| {
+                if let Some(event_emitter) = self.event_emitter() {
+                    event_emitter.emit(UploadEvent::HostFrozen { host, error: err });
+                }
+            })
             .on_response(&|response, duration| {
                 let result = upload_response_callback(response);
                 if result.is_ok() {
@@ -560,12 +717,17 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         authorization: &str,
         part: &[u8],
         part_number: usize,
+        part_offset: u64,
         md5_hasher: &mut OptionalMd5,
         on_progress: impl Fn(u64, u64),
         on_error: impl Fn(Option<&str>, &HTTPError, Duration),
         upload_logger: Option<&TokenizedUploadLogger>,
         upload_recorder: Option<&FileUploadRecordMedium>,
+        event_emitter: Option<&UploadEventEmitter>,
+        rate_limiter: Option<&RateLimiter>,
     ) -> HTTPResult<Box<str>> {
+        let part_size = part.len() as u64;
+        let last_host = RefCell::new(None::<Box<str>>);
         let mut builder = http_client
             .put(path, up_urls)
             .header("Authorization", authorization)
@@ -573,8 +735,39 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         if let Some(md5) = md5_hasher.hash(part) {
             builder = builder.header("Content-MD5", md5);
         }
-        let result: UploadPartResult = builder
+        let throttle = rate_limiter.map(|rate_limiter| RequestBodyThrottle::new(rate_limiter, part_size));
+        let result: HTTPResult<UploadPartResult> = builder
             .idempotent()
+            .on_attempt(&|host, prev_err| {
+                if let Some(throttle) = &throttle {
+                    throttle.on_attempt();
+                }
+                if let Some(event_emitter) = event_emitter {
+                    *last_host.borrow_mut() = Some(host.into());
+                    event_emitter.emit(match prev_err {
+                        Some(err) => UploadEvent::PartRetried {
+                            part_number,
+                            host,
+                            error: err,
+                        },
+                        None => UploadEvent::PartStarted {
+                            part_number,
+                            part_size,
+                            host,
+                        },
+                    });
+                }
+            })
+            .on_reading_body(&|size| {
+                if let Some(throttle) = &throttle {
+                    throttle.on_reading_body(size);
+                }
+            })
+            .on_host_frozen(&|host, err| {
+                if let Some(event_emitter) = event_emitter {
+                    event_emitter.emit(UploadEvent::HostFrozen { host, error: err });
+                }
+            })
             .on_response(&|response, duration| {
                 let result = upload_response_callback(response);
                 if result.is_ok() {
@@ -610,13 +803,38 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
             })
             .accept_json()
             .raw_body("application/octet-stream", part.as_ref())
-            .send()?
-            .parse_json()?;
+            .send()
+            .and_then(|mut response| response.parse_json());
+        if let Some(throttle) = &throttle {
+            throttle.settle();
+        }
+        let result = result.tap_err(|err| {
+            if let Some(event_emitter) = event_emitter {
+                event_emitter.emit(UploadEvent::PartFailed {
+                    part_number,
+                    host: last_host.borrow().as_ref().map(|host| &**host),
+                    error: err,
+                });
+            }
+        })?;
         if let Some(upload_recorder) = upload_recorder {
             upload_recorder
-                .append(&result.etag, part_number)
+                .append(
+                    &result.etag,
+                    part_number,
+                    part_offset,
+                    part_size,
+                    crc32::from_bytes(part),
+                )
                 .map_err(|err| HTTPError::new_unretryable_error_from_parts(HTTPErrorKind::IOError(err), None, None))?;
         }
+        if let (Some(event_emitter), Some(host)) = (event_emitter, last_host.borrow().as_ref().map(|host| &**host)) {
+            event_emitter.emit(UploadEvent::PartCompleted {
+                part_number,
+                part_size,
+                host,
+            });
+        }
         Ok(result.etag)
     }
 
@@ -629,6 +847,11 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
             .post(path, up_urls)
             .header("Authorization", authorization)
             .idempotent()
+            .on_host_frozen(&|host, err| {
+                if let Some(event_emitter) = self.event_emitter() {
+                    event_emitter.emit(UploadEvent::HostFrozen { host, error: err });
+                }
+            })
             .on_response(&|response, duration| {
                 let result = upload_response_callback(response);
                 if result.is_ok() {
@@ -673,9 +896,18 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         if let Some(from_resuming) = self.from_resuming.take() {
             let init_uploaded_size = self.uploaded_size.load(Relaxed);
             if let Some(uploading_progress_callback) = &self.uploading_progress_callback {
-                uploading_progress_callback
-                    .completed_size
-                    .store(init_uploaded_size, Relaxed);
+                uploading_progress_callback.reset_completed_size(init_uploaded_size);
+                if let Some(event_emitter) = &uploading_progress_callback.event_emitter {
+                    event_emitter.emit(UploadEvent::Started {
+                        method: UploadMethod::Resumable,
+                        upload_id: Some(&from_resuming.upload_id),
+                    });
+                    event_emitter.emit(UploadEvent::ResumedFromRecord {
+                        upload_id: &from_resuming.upload_id,
+                        completed_parts: self.completed_parts.lock().unwrap().parts.len(),
+                        uploaded_size: init_uploaded_size,
+                    });
+                }
             }
             let timer = Instant::now();
             self.start_uploading_blocks(
@@ -724,6 +956,12 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
         }
     }
 
+    fn event_emitter(&self) -> Option<&UploadEventEmitter<'u>> {
+        self.uploading_progress_callback
+            .as_ref()
+            .and_then(|progress| progress.event_emitter.as_ref())
+    }
+
     fn make_base_path(&self) -> String {
         "/buckets/".to_owned()
             + self.bucket_uploader.bucket_name().as_ref()
@@ -737,6 +975,26 @@ impl<'u, R: Read + Seek + Send> ResumableUploader<'u, R> {
     }
 }
 
+// 按照自适应控制器当前的并发度调度分片上传任务
+//
+// 任务在获得并发名额后才会被创建，而不会在线程池中等待名额，每个任务上传一个分片后释放名额，并根据最新的并发度创建后续任务。
+// 因此并发度降低时，线程池中空闲的线程可以用于执行其他任务
+fn spawn_adaptively<'s, F: Fn() -> bool + Sync>(
+    s: &Scope<'s>,
+    controller: &'s AdaptiveController,
+    upload_next_part: &'s F,
+) {
+    while let Some(permit) = controller.try_acquire() {
+        s.spawn(move |s| {
+            let uploaded = upload_next_part();
+            drop(permit);
+            if uploaded {
+                spawn_adaptively(s, controller, upload_next_part);
+            }
+        });
+    }
+}
+
 fn encode_key(key: Option<&str>) -> Cow<'static, str> {
     key.map_or_else(|| "~".into(), |key| base64::urlsafe(key.as_bytes()).into())
 }
@@ -777,11 +1035,11 @@ mod tests {
     };
     use qiniu_http::ResponseBuilder;
     use qiniu_test_utils::{
-        http_call_mock::{fake_req_id, CallHandlers, UploadingProgressErrorMock},
+        http_call_mock::{fake_req_id, json_response, CallHandlers, UploadingProgressErrorMock},
         temp_file::create_temp_file,
     };
     use serde_json::json;
-    use std::{error::Error, result::Result};
+    use std::{error::Error, result::Result, sync::mpsc::channel, thread::spawn};
 
     #[test]
     fn test_storage_uploader_resumable_uploader_upload_file() -> Result<(), Box<dyn Error>> {
@@ -974,9 +1232,9 @@ mod tests {
                 &["http://z1h1.com"],
                 1 << 22,
             )?;
-            medium.append("etag_1", 1)?;
-            medium.append("etag_3", 3)?;
-            medium.append("etag_5", 5)?;
+            medium.append("etag_1", 1, 0, 1 << 22, part_crc32(&temp_path, 0, 1 << 22)?)?;
+            medium.append("etag_3", 3, 2 << 22, 1 << 22, part_crc32(&temp_path, 2 << 22, 1 << 22)?)?;
+            medium.append("etag_5", 5, 4 << 22, 1 << 22, part_crc32(&temp_path, 4 << 22, 1 << 22)?)?;
         }
         let result = bucket_uploader
             .upload_token(UploadToken::new(policy, get_credential()))
@@ -987,6 +1245,255 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_storage_uploader_resumable_uploader_upload_file_adaptively_with_recovering() -> Result<(), Box<dyn Error>> {
+        let temp_path = create_temp_file(6 * (1 << 20))?.into_temp_path();
+        let uploads_path = "/buckets/test_bucket/objects/".to_owned() + &encode_key(Some("test-key")) + "/uploads";
+        let part_sizes = Arc::new(Mutex::new(HashMap::<String, usize>::new()));
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(
+                    Method::PUT,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id/"))
+                        + "\\d$",
+                    {
+                        let part_sizes = part_sizes.to_owned();
+                        move |request, _| {
+                            let part_number = request.url().rsplit('/').next().unwrap().to_owned();
+                            let part_size = request.body().as_ref().map_or(0, |body| body.len());
+                            part_sizes.lock().unwrap().insert(part_number.to_owned(), part_size);
+                            Ok(json_response(200, json!({ "etag": format!("etag_{}", part_number) })))
+                        }
+                    },
+                )
+                .install(
+                    Method::POST,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id"))
+                        + "$",
+                    |request, _| {
+                        let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                        assert_eq!(
+                            body["parts"],
+                            json!([
+                                {"etag": "etag_1", "partNumber": 1},
+                                {"etag": "etag_2", "partNumber": 2},
+                                {"etag": "etag_3", "partNumber": 3},
+                                {"etag": "etag_4", "partNumber": 4},
+                            ])
+                        );
+                        Ok(json_response(200, json!({"hash": "abcdef", "key": "test-key"})))
+                    },
+                ),
+            )
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_bucket("test_bucket", &config).build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test_bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .build();
+        {
+            let medium = bucket_uploader.recorder().open_and_write_metadata(
+                &temp_path,
+                Some("test-key"),
+                "test_upload_id",
+                &["http://z1h1.com"],
+                1 << 22,
+            )?;
+            medium.append("etag_1", 1, 0, 1 << 20, part_crc32(&temp_path, 0, 1 << 20)?)?;
+            medium.append("etag_3", 3, 3 << 20, 2 << 20, part_crc32(&temp_path, 3 << 20, 2 << 20)?)?;
+        }
+        let result = bucket_uploader
+            .upload_token(UploadToken::new(policy, get_credential()))
+            .key("test-key")
+            .enable_adaptive_uploading()
+            .upload_file(&temp_path, "", None)?;
+        assert_eq!(result.hash(), Some("abcdef"));
+
+        // 第 2 个分片只能覆盖第 1 个分片和第 3 个分片之间的数据
+        let part_sizes = part_sizes.lock().unwrap();
+        assert_eq!(part_sizes.len(), 2);
+        assert_eq!(part_sizes.get("2"), Some(&(2 << 20)));
+        assert_eq!(part_sizes.get("4"), Some(&(1 << 20)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_resumable_uploader_upload_file_adaptively_without_occupying_thread_pool(
+    ) -> Result<(), Box<dyn Error>> {
+        let temp_path = create_temp_file(16 * (1 << 20))?.into_temp_path();
+        let uploads_path = "/buckets/test_bucket/objects/".to_owned() + &encode_key(Some("test-key")) + "/uploads";
+        let (started_tx, started_rx) = channel::<()>();
+        let (release_tx, release_rx) = channel::<()>();
+        let started_tx = Mutex::new(started_tx);
+        let release_rx = Mutex::new(release_rx);
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(
+                    Method::POST,
+                    "^".to_owned() + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path)) + "$",
+                    |_, _| Ok(json_response(200, json!({"uploadId": "test_upload_id"}))),
+                )
+                .install(
+                    Method::PUT,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id/"))
+                        + "\\d+$",
+                    move |request, _| {
+                        let _ = started_tx.lock().unwrap().send(());
+                        // 释放信号的发送方被销毁后，所有分片都将立即上传完毕
+                        let _ = release_rx.lock().unwrap().recv();
+                        let part_number = request.url().rsplit('/').next().unwrap().to_owned();
+                        Ok(json_response(200, json!({ "etag": format!("etag_{}", part_number) })))
+                    },
+                )
+                .install(
+                    Method::POST,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id"))
+                        + "$",
+                    |_, _| Ok(json_response(200, json!({"hash": "abcdef", "key": "test-key"}))),
+                ),
+            )
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_bucket("test_bucket", &config).build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test_bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .build();
+        let thread_pool = Arc::new(ThreadPoolBuilder::new().num_threads(4).build()?);
+        let uploader_thread = {
+            let thread_pool = thread_pool.to_owned();
+            spawn(move || {
+                let result = bucket_uploader
+                    .upload_token(UploadToken::new(policy, get_credential()))
+                    .key("test-key")
+                    .thread_pool(thread_pool.as_ref())
+                    .enable_adaptive_uploading()
+                    .upload_file(&temp_path, "", None)
+                    .unwrap();
+                assert_eq!(result.hash(), Some("abcdef"));
+            })
+        };
+
+        // 初始并发度为线程池大小的一半，两个分片正在上传时，线程池中的其他线程不应该被占用
+        for _ in 0..2 {
+            started_rx.recv_timeout(Duration::from_secs(5))?;
+        }
+        let (probe_tx, probe_rx) = channel();
+        thread_pool.spawn(move || probe_tx.send(()).unwrap());
+        probe_rx.recv_timeout(Duration::from_secs(5))?;
+
+        drop(release_tx);
+        uploader_thread.join().unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_resumable_uploader_upload_file_with_mismatched_records() -> Result<(), Box<dyn Error>> {
+        let temp_path = create_temp_file(4 * (1 << 20))?.into_temp_path();
+        let uploads_path = "/buckets/test_bucket/objects/".to_owned() + &encode_key(Some("test-key")) + "/uploads";
+        let uploaded_part_numbers = Arc::new(Mutex::new(Vec::<String>::new()));
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(
+                    Method::PUT,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id/"))
+                        + "\\d$",
+                    {
+                        let uploaded_part_numbers = uploaded_part_numbers.to_owned();
+                        move |request, _| {
+                            let part_number = request.url().rsplit('/').next().unwrap().to_owned();
+                            uploaded_part_numbers.lock().unwrap().push(part_number.to_owned());
+                            Ok(json_response(
+                                200,
+                                json!({ "etag": format!("new_etag_{}", part_number) }),
+                            ))
+                        }
+                    },
+                )
+                .install(
+                    Method::POST,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id"))
+                        + "$",
+                    |request, _| {
+                        let body: Value = serde_json::from_slice(request.body().as_ref().unwrap()).unwrap();
+                        assert_eq!(
+                            body["parts"],
+                            json!([
+                                {"etag": "etag_1", "partNumber": 1},
+                                {"etag": "new_etag_2", "partNumber": 2},
+                                {"etag": "new_etag_3", "partNumber": 3},
+                                {"etag": "etag_4", "partNumber": 4},
+                            ])
+                        );
+                        Ok(json_response(200, json!({"hash": "abcdef", "key": "test-key"})))
+                    },
+                ),
+            )
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_bucket("test_bucket", &config).build();
+        let bucket_uploader = BucketUploaderBuilder::new(
+            "test_bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .build();
+        {
+            let medium = bucket_uploader.recorder().open_and_write_metadata(
+                &temp_path,
+                Some("test-key"),
+                "test_upload_id",
+                &["http://z1h1.com"],
+                1 << 20,
+            )?;
+            medium.append("etag_1", 1, 0, 1 << 20, part_crc32(&temp_path, 0, 1 << 20)?)?;
+            // 第 2 个和第 3 个分片记录的 CRC32 与文件内容不符，相当于文件在上传后被修改过，都应该被重新上传
+            medium.append(
+                "etag_2",
+                2,
+                1 << 20,
+                1 << 20,
+                part_crc32(&temp_path, 1 << 20, 1 << 20)? ^ 1,
+            )?;
+            medium.append("etag_3", 3, 2 << 20, 1 << 20, crc32::from_bytes(vec![0u8; 1 << 20]))?;
+            medium.append("etag_4", 4, 3 << 20, 1 << 20, part_crc32(&temp_path, 3 << 20, 1 << 20)?)?;
+        }
+        let result = bucket_uploader
+            .upload_token(UploadToken::new(policy, get_credential()))
+            .key("test-key")
+            .always_be_resumable()
+            .upload_file(&temp_path, "", None)?;
+        assert_eq!(result.hash(), Some("abcdef"));
+
+        let mut uploaded_part_numbers = uploaded_part_numbers.lock().unwrap();
+        uploaded_part_numbers.sort();
+        assert_eq!(*uploaded_part_numbers, ["2", "3"]);
+        Ok(())
+    }
+
     #[test]
     fn test_storage_uploader_resumable_uploader_upload_file_with_many_retryable_errors() -> Result<(), Box<dyn Error>> {
         let temp_path = create_temp_file(10 * (1 << 20))?.into_temp_path();
@@ -1201,6 +1708,97 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_storage_uploader_resumable_uploader_upload_file_with_events() -> Result<(), Box<dyn Error>> {
+        let temp_path = create_temp_file(10 * (1 << 20))?.into_temp_path();
+        let uploads_path = "/buckets/test_bucket/objects/".to_owned() + &encode_key(Some("test-key")) + "/uploads";
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(
+                    Method::POST,
+                    "^".to_owned() + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path)) + "$",
+                    |_, _| Ok(json_response(200, json!({"uploadId":"test_upload_id"}))),
+                )
+                .install(
+                    Method::PUT,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h1.com".to_owned() + &uploads_path + "/test_upload_id/"))
+                        + "\\d$",
+                    |_, called| {
+                        if called >= 3 {
+                            return Err(HTTPError::new_retryable_error_from_parts(
+                                HTTPErrorKind::MaliciousResponse,
+                                true,
+                                None,
+                                None,
+                            ));
+                        }
+                        Ok(json_response(200, json!({ "etag": format!("etag_{}", called) })))
+                    },
+                )
+                .install(
+                    Method::PUT,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h2.com".to_owned() + &uploads_path + "/test_upload_id/"))
+                        + "\\d$",
+                    |_, called| Ok(json_response(200, json!({ "etag": format!("etag_{}", called) }))),
+                )
+                .install(
+                    Method::POST,
+                    "^".to_owned()
+                        + &regex::escape(&("http://z1h2.com".to_owned() + &uploads_path + "/test_upload_id"))
+                        + "$",
+                    |_, _| Ok(json_response(200, json!({"hash": "abcdef", "key": "test-key"}))),
+                ),
+            )
+            .http_request_retries(3)
+            .http_request_retry_delay(Duration::from_millis(1))
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_bucket("test_bucket", &config).build();
+        let events = Mutex::new(Vec::<String>::new());
+        let on_event = |event: &UploadEvent| {
+            let event = match event {
+                UploadEvent::Started { method, upload_id } => format!("started {:?} {:?}", method, upload_id),
+                UploadEvent::PartStarted { host, .. } => format!("part_started {}", host),
+                UploadEvent::PartCompleted { host, .. } => format!("part_completed {}", host),
+                UploadEvent::PartRetried { host, .. } => format!("part_retried {}", host),
+                UploadEvent::HostFrozen { host, .. } => format!("host_frozen {}", host),
+                UploadEvent::Completed { uploaded_size, .. } => format!("completed {}", uploaded_size),
+                UploadEvent::Progress { .. } => return,
+                event => panic!("Unexpected event: {:?}", event),
+            };
+            events.lock().unwrap().push(event);
+        };
+        let result = BucketUploaderBuilder::new(
+            "test_bucket".into(),
+            vec![vec![Box::from("http://z1h1.com"), Box::from("http://z1h2.com")].into()].into(),
+            config,
+        )
+        .build()
+        .upload_token(UploadToken::new(policy, get_credential()))
+        .key("test-key")
+        .on_event_ref(&on_event)
+        .upload_file(&temp_path, "", None)?;
+        assert_eq!(result.hash(), Some("abcdef"));
+
+        let events = events.into_inner().unwrap();
+        let count = |event: &str| events.iter().filter(|e| e.as_str() == event).count();
+        assert_eq!(events.first().unwrap(), "started Resumable Some(\"test_upload_id\")");
+        assert_eq!(events.last().unwrap(), &format!("completed {}", 10 * (1 << 20)));
+        assert_eq!(count("part_started http://z1h1.com"), 3);
+        assert_eq!(count("part_retried http://z1h1.com"), 3);
+        assert_eq!(count("host_frozen http://z1h1.com"), 1);
+        assert_eq!(count("part_retried http://z1h2.com"), 1);
+        assert_eq!(count("part_completed http://z1h1.com"), 2);
+        assert_eq!(count("part_completed http://z1h2.com"), 1);
+        Ok(())
+    }
+
     #[test]
     fn test_storage_uploader_resumable_uploader_upload_file_with_1_zone_failure() -> Result<(), Box<dyn Error>> {
         let temp_path = create_temp_file(10 * (1 << 20))?.into_temp_path();
@@ -1708,6 +2306,12 @@ mod tests {
         Ok(())
     }
 
+    fn part_crc32(path: &Path, offset: u64, size: u64) -> Result<u32, Box<dyn Error>> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        Ok(crc32::from(&mut file.take(size))?)
+    }
+
     fn get_credential() -> Credential {
         Credential::new("abcdefghklmnopq", "1234567890")
     }
@@ -0,0 +1,222 @@
+use crate::http::Error as HTTPError;
+use assert_impl::assert_impl;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// 上传方式
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum UploadMethod {
+    /// 表单上传
+    Form,
+    /// 分片上传
+    Resumable,
+}
+
+/// 上传事件
+///
+/// 通过 `FileUploaderBuilder::on_event` 注册回调后，上传期间将按发生顺序回调以下事件。
+/// 对于分片上传，由于分片是并发上传的，不同分片的事件可能会交错出现
+#[derive(Debug, Clone)]
+pub enum UploadEvent<'e> {
+    /// 上传开始
+    ///
+    /// 对于分片上传，每次初始化上传 ID 或从上传记录中恢复上传 ID 后都会触发该事件，
+    /// 如果在区域间切换重试，可能会多次触发
+    Started {
+        /// 上传方式
+        method: UploadMethod,
+        /// 分片上传 ID，仅在分片上传时有值
+        upload_id: Option<&'e str>,
+    },
+    /// 从上传记录中恢复了分片上传
+    ResumedFromRecord {
+        /// 分片上传 ID
+        upload_id: &'e str,
+        /// 已经上传的分片数量
+        completed_parts: usize,
+        /// 已经上传的数据量，单位为字节
+        uploaded_size: u64,
+    },
+    /// 分片开始上传
+    PartStarted {
+        /// 分片编号
+        part_number: usize,
+        /// 分片尺寸，单位为字节
+        part_size: u64,
+        /// 上传使用的域名
+        host: &'e str,
+    },
+    /// 分片上传成功
+    PartCompleted {
+        /// 分片编号
+        part_number: usize,
+        /// 分片尺寸，单位为字节
+        part_size: u64,
+        /// 上传使用的域名
+        host: &'e str,
+    },
+    /// 分片上传失败，即将重试
+    PartRetried {
+        /// 分片编号
+        part_number: usize,
+        /// 重试使用的域名
+        host: &'e str,
+        /// 上一次尝试的错误
+        error: &'e HTTPError,
+    },
+    /// 分片上传最终失败
+    PartFailed {
+        /// 分片编号
+        part_number: usize,
+        /// 最后一次尝试使用的域名
+        host: Option<&'e str>,
+        /// 上传错误
+        error: &'e HTTPError,
+    },
+    /// 域名因为请求出错而被冻结，在冻结期间将不再被使用
+    HostFrozen {
+        /// 被冻结的域名
+        host: &'e str,
+        /// 导致冻结的错误
+        error: &'e HTTPError,
+    },
+    /// 上传进度
+    Progress {
+        /// 已经上传的数据量，单位为字节
+        uploaded_size: u64,
+        /// 数据总量，如果为 `None` 表示数据总量不可预知，单位为字节
+        total_size: Option<u64>,
+        /// 平滑后的上传速度，单位为字节每秒
+        bytes_per_second: u64,
+        /// 预计剩余时间，如果数据总量不可预知或尚未测得上传速度，则为 `None`
+        estimated_remaining_time: Option<Duration>,
+    },
+    /// 上传成功
+    Completed {
+        /// 已经上传的数据总量，对于断点续传的情况，包含此前已经上传的部分，单位为字节
+        uploaded_size: u64,
+        /// 从开始上传到上传成功的耗时
+        elapsed: Duration,
+    },
+    /// 上传失败
+    Failed {
+        /// 上传错误
+        error: &'e HTTPError,
+    },
+}
+
+// 两次测速之间的最小间隔
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+// 指数加权移动平均中新样本的权重
+const SPEED_SMOOTHING_FACTOR: f64 = 0.3;
+
+struct SpeedMeter {
+    last_sampled_at: Instant,
+    last_sampled_size: u64,
+    bytes_per_second: Option<f64>,
+}
+
+impl SpeedMeter {
+    fn sample(&mut self, uploaded_size: u64) -> Option<f64> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_sampled_at);
+        if elapsed >= SPEED_SAMPLE_INTERVAL {
+            let speed = uploaded_size.saturating_sub(self.last_sampled_size) as f64 / elapsed.as_secs_f64();
+            self.bytes_per_second = Some(match self.bytes_per_second {
+                Some(prev) => prev + (speed - prev) * SPEED_SMOOTHING_FACTOR,
+                None => speed,
+            });
+            self.last_sampled_at = now;
+            self.last_sampled_size = uploaded_size;
+        }
+        self.bytes_per_second
+    }
+}
+
+pub(super) struct UploadEventEmitter<'u> {
+    callback: &'u (dyn Fn(&UploadEvent) + Send + Sync),
+    started_at: Instant,
+    speed_meter: Mutex<SpeedMeter>,
+}
+
+impl<'u> UploadEventEmitter<'u> {
+    pub(super) fn new(callback: &'u (dyn Fn(&UploadEvent) + Send + Sync)) -> UploadEventEmitter<'u> {
+        let now = Instant::now();
+        UploadEventEmitter {
+            callback,
+            started_at: now,
+            speed_meter: Mutex::new(SpeedMeter {
+                last_sampled_at: now,
+                last_sampled_size: 0,
+                bytes_per_second: None,
+            }),
+        }
+    }
+
+    pub(super) fn emit(&self, event: UploadEvent) {
+        (self.callback)(&event)
+    }
+
+    // 重新开始测速，用于已上传数据量被重置后调用
+    pub(super) fn reset_speed(&self, uploaded_size: u64) {
+        *self.speed_meter.lock().unwrap() = SpeedMeter {
+            last_sampled_at: Instant::now(),
+            last_sampled_size: uploaded_size,
+            bytes_per_second: None,
+        };
+    }
+
+    pub(super) fn progress(&self, uploaded_size: u64, total_size: Option<u64>) {
+        let bytes_per_second = self.speed_meter.lock().unwrap().sample(uploaded_size);
+        let estimated_remaining_time = match (bytes_per_second, total_size) {
+            (Some(bytes_per_second), Some(total_size)) if bytes_per_second > 0f64 => Some(Duration::from_secs_f64(
+                total_size.saturating_sub(uploaded_size) as f64 / bytes_per_second,
+            )),
+            _ => None,
+        };
+        self.emit(UploadEvent::Progress {
+            uploaded_size,
+            total_size,
+            bytes_per_second: bytes_per_second.unwrap_or(0f64) as u64,
+            estimated_remaining_time,
+        });
+    }
+
+    pub(super) fn completed(&self, uploaded_size: u64) {
+        self.emit(UploadEvent::Completed {
+            uploaded_size,
+            elapsed: self.started_at.elapsed(),
+        });
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn test_storage_uploader_upload_event_speed_meter() {
+        let mut speed_meter = SpeedMeter {
+            last_sampled_at: Instant::now(),
+            last_sampled_size: 0,
+            bytes_per_second: None,
+        };
+        assert!(speed_meter.sample(1 << 20).is_none());
+        sleep(SPEED_SAMPLE_INTERVAL);
+        let first = speed_meter.sample(1 << 20).unwrap();
+        assert!(first > 0f64 && first <= (5 << 20) as f64);
+        sleep(SPEED_SAMPLE_INTERVAL);
+        let second = speed_meter.sample(1 << 20).unwrap();
+        assert!(second < first);
+        assert!(second > first * (1f64 - SPEED_SMOOTHING_FACTOR) * 0.99);
+    }
+}
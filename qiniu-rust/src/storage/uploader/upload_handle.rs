@@ -0,0 +1,506 @@
+use super::UploadResult;
+use crate::http::{Error as HTTPError, ErrorKind as HTTPErrorKind};
+use assert_impl::assert_impl;
+use std::{
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering::Relaxed},
+        Arc, Condvar, Mutex,
+    },
+    thread::JoinHandle,
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum State {
+    Running,
+    Paused,
+    Canceled,
+    // 上传已经进入无法中断的阶段，例如表单上传已经开始发送，或分片上传已经开始合并分片
+    Uninterruptible,
+}
+
+pub(super) struct UploadControl {
+    state: Mutex<State>,
+    condvar: Condvar,
+    finished: AtomicBool,
+}
+
+impl UploadControl {
+    pub(super) fn new() -> UploadControl {
+        UploadControl {
+            state: Mutex::new(State::Running),
+            condvar: Condvar::new(),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    // 如果上传被暂停，将阻塞直到上传被恢复或取消，如果上传被取消，返回用户取消错误
+    pub(super) fn wait_until_runnable(&self) -> Result<(), HTTPError> {
+        let mut state = self.state.lock().unwrap();
+        while *state == State::Paused {
+            state = self.condvar.wait(state).unwrap();
+        }
+        if *state == State::Canceled {
+            Err(Self::canceled_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    // 与 `wait_until_runnable` 相同，但不会阻塞，如果上传被暂停，返回 `false`
+    pub(super) fn is_runnable(&self) -> Result<bool, HTTPError> {
+        match *self.state.lock().unwrap() {
+            State::Running => Ok(true),
+            State::Paused => Ok(false),
+            State::Canceled => Err(Self::canceled_error()),
+            State::Uninterruptible => Ok(true),
+        }
+    }
+
+    // 等待上传可以运行，并进入无法中断的阶段，此后上传将不能再被暂停或取消。
+    // 如果上传被暂停，将阻塞直到上传被恢复或取消，如果上传被取消，返回用户取消错误
+    pub(super) fn enter_uninterruptible(&self) -> Result<(), HTTPError> {
+        let mut state = self.state.lock().unwrap();
+        while *state == State::Paused {
+            state = self.condvar.wait(state).unwrap();
+        }
+        match *state {
+            State::Canceled => Err(Self::canceled_error()),
+            _ => {
+                *state = State::Uninterruptible;
+                Ok(())
+            }
+        }
+    }
+
+    pub(super) fn finish(&self) {
+        let _state = self.state.lock().unwrap();
+        self.finished.store(true, Relaxed);
+    }
+
+    fn canceled_error() -> HTTPError {
+        HTTPError::new_unretryable_error_from_parts(HTTPErrorKind::UserCanceled, None, None)
+    }
+
+    // 上传结束后，状态将不再改变
+    fn transit(&self, from: &[State], to: State) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if from.contains(&state) && !self.finished.load(Relaxed) {
+            *state = to;
+            self.condvar.notify_all();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn state(&self) -> State {
+        *self.state.lock().unwrap()
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+/// 上传句柄
+///
+/// 通过 `FileUploaderBuilder::upload_file_in_background` 或 `FileUploaderBuilder::upload_stream_in_background` 获取，
+/// 上传将在后台线程中进行，可以通过句柄暂停，恢复或取消上传，并等待上传结果。
+///
+/// 对于分片上传，暂停后将不再开始上传新的分片，但正在上传的分片依然会上传完毕，
+/// 恢复后将继续使用原有的上传 ID 上传剩余分片，但所有分片上传完毕并开始合并分片后将不能再被暂停或取消。
+/// 对于表单上传，仅当上传尚未开始发送时才能被暂停或取消
+///
+/// ```rust,no_run
+/// use qiniu_ng::{Client, Config};
+/// # use std::{result::Result, error::Error};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = Client::new("[Access Key]", "[Secret Key]", Config::default());
+/// let handle = client
+///     .upload()
+///     .for_upload_token("[Upload Token]".to_owned())?
+///     .key("[Key]")
+///     .upload_file_in_background("/path/to/file", "", None);
+/// handle.pause();
+/// handle.resume();
+/// let response = handle.join()?;
+/// println!("hash: {:?}", response.hash());
+/// # Ok(())
+/// # }
+/// ```
+pub struct UploadHandle {
+    control: Arc<UploadControl>,
+    thread: JoinHandle<UploadResult>,
+}
+
+impl UploadHandle {
+    pub(super) fn new(control: Arc<UploadControl>, thread: JoinHandle<UploadResult>) -> UploadHandle {
+        UploadHandle { control, thread }
+    }
+
+    /// 暂停上传
+    ///
+    /// 如果上传已经被暂停，被取消，已经无法中断或已经结束，将返回 `false`
+    pub fn pause(&self) -> bool {
+        self.control.transit(&[State::Running], State::Paused)
+    }
+
+    /// 恢复上传
+    ///
+    /// 如果上传没有被暂停，将返回 `false`
+    pub fn resume(&self) -> bool {
+        self.control.transit(&[State::Paused], State::Running)
+    }
+
+    /// 取消上传
+    ///
+    /// 取消成功后，`join` 将返回用户取消错误。
+    /// 对于从文件分片上传的情况，已经上传的分片记录将被保留，下次上传同一个文件时依然可以断点续传。
+    ///
+    /// 如果上传已经被取消，已经无法中断或已经结束，将返回 `false`，此时上传将不受影响
+    pub fn cancel(&self) -> bool {
+        self.control.transit(&[State::Running, State::Paused], State::Canceled)
+    }
+
+    /// 上传是否已经被暂停
+    pub fn is_paused(&self) -> bool {
+        self.control.state() == State::Paused
+    }
+
+    /// 上传是否已经被取消
+    pub fn is_canceled(&self) -> bool {
+        self.control.state() == State::Canceled
+    }
+
+    /// 上传是否已经结束
+    ///
+    /// 结束后调用 `join` 将立即返回上传结果
+    pub fn is_finished(&self) -> bool {
+        self.control.finished.load(Relaxed)
+    }
+
+    /// 等待上传结束并获取上传结果
+    ///
+    /// 如果上传被暂停，将一直等待直到上传被恢复或取消
+    pub fn join(self) -> UploadResult {
+        match self.thread.join() {
+            Ok(result) => result,
+            Err(err) => panic::resume_unwind(err),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn ignore() {
+        assert_impl!(Send: Self);
+        assert_impl!(Sync: Self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            BucketUploader, BucketUploaderBuilder, FileUploaderBuilder, UploadError, UploadPolicyBuilder, UploadToken,
+        },
+        *,
+    };
+    use crate::{
+        config::ConfigBuilder,
+        credential::Credential,
+        http::{DomainsManagerBuilder, Method},
+        utils::{base64, ron::Ron},
+    };
+    use lazy_static::lazy_static;
+    use matches::matches;
+    use qiniu_test_utils::{
+        http_call_mock::{json_response, CallHandlers},
+        temp_file::create_temp_file,
+    };
+    use rayon::{ThreadPool, ThreadPoolBuilder};
+    use serde_json::json;
+    use std::{
+        error::Error,
+        io::{Cursor, Read, Result as IOResult},
+        result::Result,
+        sync::mpsc::{channel, Receiver, Sender},
+        thread::sleep,
+        time::{Duration, Instant},
+    };
+    use tempfile::TempPath;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    lazy_static! {
+        // 测试用线程池，用于检查暂停期间线程池中的线程是否被释放
+        static ref THREAD_POOL: ThreadPool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    }
+
+    // 模拟服务器收到请求时发出事件，并等待测试允许其继续处理请求，释放信号的发送方被销毁后将不再等待
+    struct Server {
+        events: Mutex<Sender<&'static str>>,
+        release: Mutex<Receiver<()>>,
+    }
+
+    impl Server {
+        fn handle(&self, event: &'static str, wait: bool) {
+            let _ = self.events.lock().unwrap().send(event);
+            if wait {
+                let _ = self.release.lock().unwrap().recv();
+            }
+        }
+    }
+
+    struct Uploading {
+        handle: UploadHandle,
+        release: Sender<()>,
+        events: Receiver<&'static str>,
+        _temp_path: Option<TempPath>,
+    }
+
+    fn new_file_uploader(server: Server) -> FileUploaderBuilder<'static> {
+        let server = Arc::new(server);
+        let uploads_url =
+            "http://z1h1.com/buckets/test_bucket/objects/".to_owned() + &base64::urlsafe(b"test-key") + "/uploads";
+        let config = ConfigBuilder::default()
+            .http_request_handler(
+                CallHandlers::new(|request| {
+                    panic!("Unexpected Request: {} {}", request.method(), request.url());
+                })
+                .install(Method::POST, r"^http://z1h1\.com/?$", {
+                    let server = server.to_owned();
+                    move |_, _| {
+                        server.handle("form", true);
+                        Ok(json_response(200, json!({"hash": "abcdef", "key": "test-key"})))
+                    }
+                })
+                .install(Method::POST, "^".to_owned() + &regex::escape(&uploads_url) + "$", {
+                    let server = server.to_owned();
+                    move |_, _| {
+                        server.handle("init", true);
+                        Ok(json_response(200, json!({"uploadId": "test_upload_id"})))
+                    }
+                })
+                .install(
+                    Method::PUT,
+                    "^".to_owned() + &regex::escape(&(uploads_url.to_owned() + "/test_upload_id/")) + "\\d$",
+                    {
+                        let server = server.to_owned();
+                        move |_, called| {
+                            server.handle("put", true);
+                            Ok(json_response(200, json!({ "etag": format!("etag_{}", called) })))
+                        }
+                    },
+                )
+                .install(
+                    Method::POST,
+                    "^".to_owned() + &regex::escape(&(uploads_url.to_owned() + "/test_upload_id")) + "$",
+                    move |_, _| {
+                        server.handle("complete", false);
+                        Ok(json_response(200, json!({"hash": "abcdef", "key": "test-key"})))
+                    },
+                ),
+            )
+            .upload_logger(None)
+            .domains_manager(DomainsManagerBuilder::default().disable_url_resolution().build())
+            .build();
+        let policy = UploadPolicyBuilder::new_policy_for_bucket("test_bucket", &config).build();
+        let upload_token = UploadToken::new(policy, Credential::new("abcdefghklmnopq", "1234567890")).to_string();
+        let bucket_uploader: BucketUploader = BucketUploaderBuilder::new(
+            "test_bucket".into(),
+            vec![vec![Box::from("http://z1h1.com")].into()].into(),
+            config,
+        )
+        .build();
+        FileUploaderBuilder::new(Ron::Owned(bucket_uploader), upload_token.into()).key("test-key")
+    }
+
+    fn new_server() -> (Server, Sender<()>, Receiver<&'static str>) {
+        let (events_sender, events) = channel();
+        let (release, release_receiver) = channel();
+        let server = Server {
+            events: Mutex::new(events_sender),
+            release: Mutex::new(release_receiver),
+        };
+        (server, release, events)
+    }
+
+    fn start_uploading_by_blocks() -> Result<Uploading, Box<dyn Error>> {
+        let temp_path = create_temp_file(10 * (1 << 20))?.into_temp_path();
+        let (server, release, events) = new_server();
+        let handle = new_file_uploader(server)
+            .always_be_resumable()
+            .thread_pool(Ron::Referenced(&*THREAD_POOL))
+            .upload_file_in_background(temp_path.to_path_buf(), "", None);
+        Ok(Uploading {
+            handle,
+            release,
+            events,
+            _temp_path: Some(temp_path),
+        })
+    }
+
+    // 第一次读取时将阻塞，直到测试允许其继续读取，用于确保表单上传尚未开始发送
+    struct BlockingReader {
+        release: Option<Receiver<()>>,
+        data: Cursor<Vec<u8>>,
+    }
+
+    impl Read for BlockingReader {
+        fn read(&mut self, buf: &mut [u8]) -> IOResult<usize> {
+            if let Some(release) = self.release.take() {
+                let _ = release.recv();
+            }
+            self.data.read(buf)
+        }
+    }
+
+    fn start_uploading_by_form() -> (Uploading, Sender<()>) {
+        let (server, release, events) = new_server();
+        let (release_reader, reader_release) = channel();
+        let handle = new_file_uploader(server)
+            .never_be_resumable()
+            .upload_stream_in_background(
+                BlockingReader {
+                    release: Some(reader_release),
+                    data: Cursor::new(vec![0u8; 1 << 10]),
+                },
+                "",
+                None,
+            );
+        (
+            Uploading {
+                handle,
+                release,
+                events,
+                _temp_path: None,
+            },
+            release_reader,
+        )
+    }
+
+    fn assert_canceled(result: UploadResult) {
+        match result {
+            Err(UploadError::QiniuError(err)) => assert!(matches!(err.error_kind(), HTTPErrorKind::UserCanceled)),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_storage_uploader_upload_handle_pause_and_resume() -> Result<(), Box<dyn Error>> {
+        let Uploading {
+            handle,
+            release,
+            events,
+            _temp_path,
+        } = start_uploading_by_blocks()?;
+        release.send(())?;
+        assert_eq!(events.recv_timeout(TIMEOUT)?, "init");
+
+        // 线程池中的两个线程都开始上传分片后再暂停
+        assert_eq!(events.recv_timeout(TIMEOUT)?, "put");
+        assert_eq!(events.recv_timeout(TIMEOUT)?, "put");
+        assert!(handle.pause());
+        assert!(!handle.pause());
+        assert!(handle.is_paused());
+        release.send(())?;
+        release.send(())?;
+
+        // 暂停期间，正在上传的分片完成后线程池中的线程将被释放，且不会开始上传新的分片
+        let (probe_sender, probe_receiver) = channel();
+        THREAD_POOL.spawn(move || probe_sender.send(()).unwrap());
+        probe_receiver.recv_timeout(TIMEOUT)?;
+        assert!(events.try_recv().is_err());
+        assert!(!handle.is_finished());
+
+        assert!(handle.resume());
+        assert!(!handle.resume());
+        drop(release);
+        assert_eq!(events.recv_timeout(TIMEOUT)?, "put");
+        assert_eq!(events.recv_timeout(TIMEOUT)?, "complete");
+
+        // 上传结束后不能再被暂停或取消
+        let timer = Instant::now();
+        while !handle.is_finished() {
+            assert!(timer.elapsed() < TIMEOUT);
+            sleep(Duration::from_millis(10));
+        }
+        assert!(!handle.pause());
+        assert!(!handle.cancel());
+        assert!(!handle.is_canceled());
+        let response = handle.join()?;
+        assert_eq!(response.key(), Some("test-key"));
+        assert_eq!(response.hash(), Some("abcdef"));
+        assert!(events.try_recv().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_upload_handle_cancel() -> Result<(), Box<dyn Error>> {
+        let Uploading {
+            handle,
+            release,
+            events,
+            _temp_path,
+        } = start_uploading_by_blocks()?;
+        assert!(handle.pause());
+        assert!(handle.cancel());
+        assert!(!handle.cancel());
+        assert!(handle.is_canceled());
+        assert!(!handle.resume());
+        drop(release);
+        assert_canceled(handle.join());
+        assert!(events.try_iter().all(|event| event == "init"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_upload_handle_cancel_form_uploading_before_sending() -> Result<(), Box<dyn Error>> {
+        let (
+            Uploading {
+                handle,
+                release,
+                events,
+                ..
+            },
+            release_reader,
+        ) = start_uploading_by_form();
+        assert!(handle.pause());
+        drop(release_reader);
+        assert!(handle.cancel());
+        assert!(handle.is_canceled());
+        drop(release);
+        assert_canceled(handle.join());
+        assert!(events.try_recv().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_uploader_upload_handle_form_uploading_is_uninterruptible() -> Result<(), Box<dyn Error>> {
+        let (
+            Uploading {
+                handle,
+                release,
+                events,
+                ..
+            },
+            release_reader,
+        ) = start_uploading_by_form();
+        drop(release_reader);
+        assert_eq!(events.recv_timeout(TIMEOUT)?, "form");
+
+        // 表单已经开始发送，不能再被暂停或取消
+        assert!(!handle.pause());
+        assert!(!handle.cancel());
+        assert!(!handle.is_paused());
+        assert!(!handle.is_canceled());
+        drop(release);
+        let response = handle.join()?;
+        assert_eq!(response.key(), Some("test-key"));
+        assert_eq!(response.hash(), Some("abcdef"));
+        Ok(())
+    }
+}
@@ -44,8 +44,8 @@ impl LockPolicy {
     #[allow(dead_code)]
     fn lock_for_appending(self, file: &File) -> IOResult<()> {
         match self {
-            LockPolicy::LockSharedDuringAppendingAndLockExclusiveDuringUploading => file.lock_shared(),
-            LockPolicy::AlwaysLockExclusive => file.lock_exclusive(),
+            LockPolicy::LockSharedDuringAppendingAndLockExclusiveDuringUploading => FileExt::lock_shared(file),
+            LockPolicy::AlwaysLockExclusive => FileExt::lock_exclusive(file),
             LockPolicy::None => Ok(()),
         }
     }
@@ -53,7 +53,7 @@ impl LockPolicy {
     fn lock_for_uploading(self, file: &File) -> IOResult<()> {
         match self {
             LockPolicy::LockSharedDuringAppendingAndLockExclusiveDuringUploading | LockPolicy::AlwaysLockExclusive => {
-                file.lock_exclusive()
+                FileExt::lock_exclusive(file)
             }
             LockPolicy::None => Ok(()),
         }
@@ -61,8 +61,8 @@ impl LockPolicy {
     #[allow(dead_code)]
     fn try_lock_for_appending(self, file: &File) -> IOResult<()> {
         match self {
-            LockPolicy::LockSharedDuringAppendingAndLockExclusiveDuringUploading => file.try_lock_shared(),
-            LockPolicy::AlwaysLockExclusive => file.try_lock_exclusive(),
+            LockPolicy::LockSharedDuringAppendingAndLockExclusiveDuringUploading => FileExt::try_lock_shared(file),
+            LockPolicy::AlwaysLockExclusive => FileExt::try_lock_exclusive(file),
             LockPolicy::None => Ok(()),
         }
     }
@@ -70,7 +70,7 @@ impl LockPolicy {
     fn try_lock_for_uploading(self, file: &File) -> IOResult<()> {
         match self {
             LockPolicy::LockSharedDuringAppendingAndLockExclusiveDuringUploading | LockPolicy::AlwaysLockExclusive => {
-                file.try_lock_exclusive()
+                FileExt::try_lock_exclusive(file)
             }
             LockPolicy::None => Ok(()),
         }
@@ -39,6 +39,13 @@ impl UploadManager {
                 .unwrap_or_else(|_| Self::all_possible_up_urls_list(self.config.use_https())),
             self.config.to_owned(),
         )
+        .rs_urls(
+            bucket
+                .rs_urls()
+                .into_iter()
+                .map(|url| url.into_owned().into())
+                .collect(),
+        )
     }
 
     /// 根据存储空间名称和对应的 Access Key 创建存储空间上传器生成器
@@ -48,10 +55,20 @@ impl UploadManager {
         access_key: impl AsRef<str>,
     ) -> BucketUploaderBuilder {
         let bucket_name = bucket_name.into();
-        let up_urls_list = Region::query(bucket_name.as_ref(), access_key.as_ref(), self.config.to_owned())
+        let regions = Region::query(bucket_name.as_ref(), access_key.as_ref(), self.config.to_owned());
+        let up_urls_list = regions
+            .as_ref()
             .map(|regions| Self::extract_up_urls_list_from_regions(regions.iter(), self.config.use_https()))
             .unwrap_or_else(|_| Self::all_possible_up_urls_list(self.config.use_https()));
+        let mut rs_urls = regions
+            .as_ref()
+            .ok()
+            .and_then(|regions| regions.first())
+            .map(|region| region.rs_urls_owned(self.config.use_https()))
+            .unwrap_or_default();
+        rs_urls.push(Cow::Owned(self.config.rs_url()));
         BucketUploaderBuilder::new(bucket_name.into_owned().into(), up_urls_list, self.config.to_owned())
+            .rs_urls(rs_urls.into_iter().map(|url| url.into_owned().into()).collect())
     }
 
     fn extract_up_urls_list_from_regions<'a>(
@@ -45,6 +45,16 @@ pub struct UploadPolicy<'p> {
     #[serde(skip_serializing_if = "Option::is_none")]
     callback_body_type: Option<Cow<'p, str>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistent_ops: Option<Cow<'p, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistent_notify_url: Option<Cow<'p, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    persistent_pipeline: Option<Cow<'p, str>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_user: Option<Cow<'p, str>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     save_key: Option<Cow<'p, str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -148,6 +158,30 @@ impl<'p> UploadPolicy<'p> {
         Self::convert_to_optional_str(&self.callback_body_type)
     }
 
+    /// 上传成功后，触发执行的持久化数据处理指令列表
+    ///
+    /// 每个指令是一个 API 规格字符串，多个指令将并行处理
+    pub fn persistent_ops(&self) -> Option<Split<char>> {
+        Self::convert_to_optional_splited_str(&self.persistent_ops, ';')
+    }
+
+    /// 持久化数据处理完成后，七牛云向业务服务器发送处理结果的 URL
+    pub fn persistent_notify_url(&self) -> Option<&str> {
+        Self::convert_to_optional_str(&self.persistent_notify_url)
+    }
+
+    /// 持久化数据处理使用的私有队列名称
+    pub fn persistent_pipeline(&self) -> Option<&str> {
+        Self::convert_to_optional_str(&self.persistent_pipeline)
+    }
+
+    /// 上传用户的唯一标识
+    ///
+    /// 该值将出现在回调请求中，可以用于业务服务器识别上传用户
+    pub fn end_user(&self) -> Option<&str> {
+        Self::convert_to_optional_str(&self.end_user)
+    }
+
     /// 自定义对象名称
     ///
     /// 支持[魔法变量](https://developer.qiniu.com/kodo/manual/1235/vars#magicvar)和[自定义变量](https://developer.qiniu.com/kodo/manual/1235/vars#xvar)
@@ -422,6 +456,39 @@ impl<'p> UploadPolicyBuilder<'p> {
         self
     }
 
+    /// 上传成功后，触发执行的持久化数据处理指令列表
+    ///
+    /// 每个指令是一个 API 规格字符串，多个指令将并行处理，可以使用 `processing::Pipeline` 生成。
+    /// 处理结果可以通过 `persistentId` 调用 `Bucket::prefop` 查询
+    pub fn persistent_ops(&mut self, ops: &[impl AsRef<str>]) -> &mut Self {
+        self.inner.persistent_ops = Some(ops.iter().map(|op| op.as_ref()).collect::<Vec<_>>().join(";").into());
+        self
+    }
+
+    /// 持久化数据处理完成后，七牛云向业务服务器发送处理结果的 URL
+    ///
+    /// 必须是公网上可以正常进行 POST 请求并能响应 HTTP 200 OK 的有效 URL
+    pub fn persistent_notify_url(&mut self, url: impl Into<Cow<'p, str>>) -> &mut Self {
+        self.inner.persistent_notify_url = Some(url.into());
+        self
+    }
+
+    /// 持久化数据处理使用的私有队列名称
+    ///
+    /// 如果不指定，将使用公共队列
+    pub fn persistent_pipeline(&mut self, pipeline: impl Into<Cow<'p, str>>) -> &mut Self {
+        self.inner.persistent_pipeline = Some(pipeline.into());
+        self
+    }
+
+    /// 上传用户的唯一标识
+    ///
+    /// 该值将出现在回调请求中，可以用于业务服务器识别上传用户
+    pub fn end_user(&mut self, end_user: impl Into<Cow<'p, str>>) -> &mut Self {
+        self.inner.end_user = Some(end_user.into());
+        self
+    }
+
     /// 自定义对象名称
     ///
     /// 支持[魔法变量](https://developer.qiniu.com/kodo/manual/1235/vars#magicvar)和[自定义变量](https://developer.qiniu.com/kodo/manual/1235/vars#xvar)。
@@ -506,6 +573,10 @@ impl<'p> UploadPolicyBuilder<'p> {
         self.inner.callback_host = None;
         self.inner.callback_body = None;
         self.inner.callback_body_type = None;
+        self.inner.persistent_ops = None;
+        self.inner.persistent_notify_url = None;
+        self.inner.persistent_pipeline = None;
+        self.inner.end_user = None;
         self.inner.save_key = None;
         self.inner.force_save_key = None;
         self.inner.fsize_min = None;
@@ -527,7 +598,10 @@ pub type UploadPolicyResult<T> = Result<T, UploadPolicyError>;
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::{
+        super::super::processing::{Avthumb, Pipeline, Vframe},
+        *,
+    };
     use serde_json::{json, Value};
     use std::{boxed::Box, error::Error, result::Result};
 
@@ -827,6 +901,59 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_upload_policy_with_persistent_ops() -> Result<(), Box<dyn Error>> {
+        let policy = UploadPolicyBuilder::new_policy_for_object("test_bucket", "test.mp4", &Config::default())
+            .persistent_ops(&[
+                Pipeline::from(Avthumb::new("m3u8")).save_as("test_bucket", "test.m3u8"),
+                Pipeline::from(Vframe::new("jpg", Duration::from_secs(7))),
+            ])
+            .persistent_notify_url("http://www.qiniu.io/notify")
+            .persistent_pipeline("test_pipeline")
+            .end_user("test_user")
+            .build();
+        assert_eq!(
+            policy.persistent_ops().map(|ops| ops.collect::<Vec<&str>>()),
+            Some(vec![
+                "avthumb/m3u8|saveas/dGVzdF9idWNrZXQ6dGVzdC5tM3U4",
+                "vframe/jpg/offset/7"
+            ])
+        );
+        assert_eq!(policy.persistent_notify_url(), Some("http://www.qiniu.io/notify"));
+        assert_eq!(policy.persistent_pipeline(), Some("test_pipeline"));
+        assert_eq!(policy.end_user(), Some("test_user"));
+        let v: Value = serde_json::from_str(policy.as_json().as_str())?;
+        assert_eq!(
+            v["persistentOps"],
+            "avthumb/m3u8|saveas/dGVzdF9idWNrZXQ6dGVzdC5tM3U4;vframe/jpg/offset/7"
+        );
+        assert_eq!(v["persistentNotifyUrl"], "http://www.qiniu.io/notify");
+        assert_eq!(v["persistentPipeline"], "test_pipeline");
+        assert_eq!(v["endUser"], "test_user");
+        Ok(())
+    }
+
+    #[test]
+    fn test_upload_policy_json_round_trip() -> Result<(), Box<dyn Error>> {
+        let json = json!({
+            "scope": "test_bucket:test_prefix",
+            "deadline": 1_600_000_000,
+            "isPrefixalScope": 1,
+            "returnBody": "{\"key\":$(key)}",
+            "persistentOps": "avthumb/mp4;vframe/jpg/offset/7",
+            "persistentNotifyUrl": "http://www.qiniu.io/notify",
+            "persistentPipeline": "test_pipeline",
+            "endUser": "test_user",
+            "fsizeMin": 15,
+            "fsizeLimit": 20,
+            "deleteAfterDays": 7,
+        });
+        let policy = UploadPolicy::from_json(json.to_string())?;
+        assert_eq!(serde_json::from_str::<Value>(&policy.as_json())?, json);
+        assert_eq!(UploadPolicy::from_json(policy.as_json())?, policy);
+        Ok(())
+    }
+
     #[test]
     fn test_build_upload_policy_with_save_key() -> Result<(), Box<dyn Error>> {
         let policy = UploadPolicyBuilder::new_policy_for_bucket("test_bucket", &Config::default())
@@ -1,10 +1,12 @@
 use super::super::recorder::{FileSystemRecorder, RecordMedium, Recorder};
+use crate::utils::crc32;
 use assert_impl::assert_impl;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use std::{
     fmt,
-    io::{BufRead, BufReader, Error, ErrorKind, Result},
+    fs::File,
+    io::{BufRead, BufReader, Error, ErrorKind, Read, Result, Seek, SeekFrom},
     path::Path,
     sync::Arc,
     sync::Mutex,
@@ -13,7 +15,9 @@ use std::{
 
 /// 上传进度记录仪
 ///
-/// 用于记录文件块上传进度，如果文件在上传期间发生错误，将可以在重试时避免再次上传已经成功上传的文件分块，实现断点续传
+/// 用于记录文件块上传进度，如果文件在上传期间发生错误，将可以在重试时避免再次上传已经成功上传的文件分块，实现断点续传。
+///
+/// 每个分块的记录中都包含其数据的 CRC32，断点续传前将重新读取文件验证每个分块，与文件内容不符的分块将被重新上传
 #[derive(Builder, Clone)]
 #[builder(pattern = "mutable", public, build_fn(name = "inner_build", private))]
 pub struct UploadRecorder {
@@ -65,17 +69,32 @@ struct SerializableFileUploadRecordMediumMetadata<'a> {
     block_size: u32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub(super) struct FileUploadRecordMediumBlockItem {
     pub(super) etag: Box<str>,
     pub(super) part_number: usize,
-    pub(super) created_timestamp: u64,
+    pub(super) offset: u64,
+    pub(super) size: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct DeserializableFileUploadRecordMediumBlockItem {
+    etag: Box<str>,
+    part_number: usize,
+    // 早期版本的记录中没有以下三个字段，这样的分片记录无法验证，将在加载时被丢弃
+    offset: Option<u64>,
+    size: Option<u64>,
+    crc32: Option<u32>,
+    created_timestamp: u64,
 }
 
 #[derive(Serialize, Debug, Clone)]
 struct SerializableFileUploadRecordMediumBlockItem<'a> {
     etag: &'a str,
     part_number: usize,
+    offset: u64,
+    size: u64,
+    crc32: u32,
     created_timestamp: u64,
 }
 
@@ -158,13 +177,14 @@ impl UploadRecorder {
         {
             return Ok(None);
         }
+        let mut file = File::open(path)?;
         let mut block_items = Vec::<FileUploadRecordMediumBlockItem>::new();
         loop {
             line.clear();
             if reader.read_line(&mut line)? == 0 {
                 return Ok(Some((metadata, block_items.into())));
             }
-            let block_item: FileUploadRecordMediumBlockItem =
+            let block_item: DeserializableFileUploadRecordMediumBlockItem =
                 serde_json::from_str(&line).map_err(|err| Error::new(ErrorKind::Other, err))?;
             if SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
@@ -174,8 +194,33 @@ impl UploadRecorder {
             {
                 return Ok(Some((metadata, block_items.into())));
             }
-            // TODO: 验证文件内容与记录的 Etag 是否符合
-            block_items.push(block_item);
+            if let Some(block_item) = Self::verify_block_item(&mut file, block_item)? {
+                block_items.push(block_item);
+            }
+        }
+    }
+
+    // 验证文件内容与分片记录中的 CRC32 是否相符，仅返回相符的分片，不相符的分片将被丢弃并重新上传。
+    // 早期版本的分片记录中没有 CRC32，无法验证，同样视为不相符
+    fn verify_block_item(
+        file: &mut File,
+        block_item: DeserializableFileUploadRecordMediumBlockItem,
+    ) -> Result<Option<FileUploadRecordMediumBlockItem>> {
+        match (block_item.offset, block_item.size, block_item.crc32) {
+            (Some(offset), Some(size), Some(expected_crc32)) => {
+                file.seek(SeekFrom::Start(offset))?;
+                if crc32::from(&mut file.take(size))? == expected_crc32 {
+                    Ok(Some(FileUploadRecordMediumBlockItem {
+                        etag: block_item.etag,
+                        part_number: block_item.part_number,
+                        offset,
+                        size,
+                    }))
+                } else {
+                    Ok(None)
+                }
+            }
+            _ => Ok(None),
         }
     }
 
@@ -223,8 +268,6 @@ impl Default for UploadRecorder {
 
 mod default {
     use super::*;
-    use digest::{FixedOutput, Input};
-    use sha1::Sha1;
 
     #[inline]
     pub fn recorder() -> Arc<dyn Recorder> {
@@ -241,24 +284,17 @@ mod default {
         false
     }
 
-    pub fn id_generator(name: &str, path: &Path, key: Option<&str>) -> String {
-        let mut sha1 = Sha1::default();
-        if let Some(key) = key {
-            sha1.input(key.as_bytes());
-            sha1.input(b"_._");
-        }
-        sha1.input(name.as_bytes());
-        sha1.input(b"_._");
-        sha1.input(path.to_string_lossy().as_ref().as_bytes());
-        hex::encode(sha1.fixed_result())
-    }
+    pub(super) use crate::storage::recorder::default_id_generator as id_generator;
 }
 
 impl FileUploadRecordMedium {
-    pub(super) fn append(&self, etag: &str, part_number: usize) -> Result<()> {
+    pub(super) fn append(&self, etag: &str, part_number: usize, offset: u64, size: u64, crc32: u32) -> Result<()> {
         let mut item = serde_json::to_string(&SerializableFileUploadRecordMediumBlockItem {
             etag,
             part_number,
+            offset,
+            size,
+            crc32,
             created_timestamp: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .expect("Clock may have gone backwards")